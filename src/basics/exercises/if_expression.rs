@@ -0,0 +1,22 @@
+// 演習: if式
+//
+// `sign`は数値が正なら"正"、負なら"負"、0なら"ゼロ"を返す関数です。
+// `if`は式なので、値を返すことができます。`todo!()`を置き換えて完成させてください。
+//
+// 確認: cargo run -- exercise check if_expression
+
+pub fn sign(number: i32) -> &'static str {
+    todo!()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn returns_sign() {
+        assert_eq!(sign(7), "正");
+        assert_eq!(sign(-3), "負");
+        assert_eq!(sign(0), "ゼロ");
+    }
+}
@@ -0,0 +1,25 @@
+// 演習: 変数と可変性
+//
+// `count_up`は`start`から`times`回だけ1を足した値を返す関数です。
+// このままではコンパイルできません。エラーメッセージを読んで修正してください。
+//
+// 確認: cargo run -- exercise check variables
+
+pub fn count_up(start: i32, times: i32) -> i32 {
+    let value = start;
+    for _ in 0..times {
+        value += 1;
+    }
+    value
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn counts_up() {
+        assert_eq!(count_up(0, 3), 3);
+        assert_eq!(count_up(10, 0), 10);
+    }
+}
@@ -1,72 +1,77 @@
 // 基本的な文法の学習
 // このモジュールでは、Rustの基本的な文法要素を学びます
 
+use crate::section::{self, Demo, DocPage, Exercise, Section, SourceFile};
+use std::io::{self, Write};
+
 // 1. 変数と可変性
-pub fn variables_and_mutability() {
+pub fn variables_and_mutability(out: &mut dyn Write) -> io::Result<()> {
     // 不変な変数（デフォルト）
     let x = 5;
-    println!("xの値: {}", x);
+    writeln!(out, "xの値: {}", x)?;
     
     // 可変な変数
     let mut y = 10;
-    println!("yの初期値: {}", y);
+    writeln!(out, "yの初期値: {}", y)?;
     y = 20;
-    println!("yの変更後: {}", y);
+    writeln!(out, "yの変更後: {}", y)?;
     
     // シャドーイング
     let z = 5;
     let z = z + 1;  // 同じ名前で新しい変数を作成
     let z = z * 2;
-    println!("シャドーイング後のz: {}", z); // 12
+    writeln!(out, "シャドーイング後のz: {}", z)?; // 12
     
     // 定数
     const MAX_POINTS: u32 = 100_000;
-    println!("定数MAX_POINTS: {}", MAX_POINTS);
+    writeln!(out, "定数MAX_POINTS: {}", MAX_POINTS)?;
+    Ok(())
 }
 
 // 2. データ型
-pub fn data_types() {
+pub fn data_types(out: &mut dyn Write) -> io::Result<()> {
     // 整数型
     let a: i32 = -42;      // 符号付き32ビット整数
     let b: u64 = 100;      // 符号なし64ビット整数
     let c: usize = 10;     // アーキテクチャ依存のサイズ
-    println!("整数型: i32={}, u64={}, usize={}", a, b, c);
+    writeln!(out, "整数型: i32={}, u64={}, usize={}", a, b, c)?;
     
     // 浮動小数点数
     let x = 2.5;           // f64（デフォルト）
     let y: f32 = 3.14;     // f32
-    println!("浮動小数点数: f64={}, f32={}", x, y);
+    writeln!(out, "浮動小数点数: f64={}, f32={}", x, y)?;
     
     // 真偽値
     let t = true;
     let f: bool = false;
-    println!("真偽値: true={}, false={}", t, f);
+    writeln!(out, "真偽値: true={}, false={}", t, f)?;
     
     // 文字型
     let ch = 'A';
     let emoji = '😊';
     let kanji = '漢';
-    println!("文字型: {}, {}, {}", ch, emoji, kanji);
+    writeln!(out, "文字型: {}, {}, {}", ch, emoji, kanji)?;
     
     // タプル
     let tup: (i32, f64, char) = (500, 6.4, 'a');
     let (x, y, z) = tup;  // 分解
-    println!("タプル: ({}, {}, {})", x, y, z);
-    println!("タプルの要素アクセス: {}", tup.1);
+    writeln!(out, "タプル: ({}, {}, {})", x, y, z)?;
+    writeln!(out, "タプルの要素アクセス: {}", tup.1)?;
     
     // 配列
     let arr = [1, 2, 3, 4, 5];
     let months = ["January", "February", "March"];
     let zeros = [0; 5];  // [0, 0, 0, 0, 0]
-    println!("配列の最初の要素: {}", arr[0]);
-    println!("月: {}", months[1]);
-    println!("ゼロ配列の長さ: {}", zeros.len());
+    writeln!(out, "配列の最初の要素: {}", arr[0])?;
+    writeln!(out, "月: {}", months[1])?;
+    writeln!(out, "ゼロ配列の長さ: {}", zeros.len())?;
+    Ok(())
 }
 
 // 3. 関数
-pub fn functions_demo() {
-    fn greet(name: &str) {
-        println!("こんにちは、{}さん！", name);
+pub fn functions_demo(out: &mut dyn Write) -> io::Result<()> {
+    fn greet(out: &mut dyn Write, name: &str) -> io::Result<()> {
+        writeln!(out, "こんにちは、{}さん！", name)
     }
     
     fn add(x: i32, y: i32) -> i32 {
@@ -80,38 +85,39 @@ pub fn functions_demo() {
         (quotient, remainder)  // タプルで複数の値を返す
     }
     
-    greet("太郎");
+    greet(out, "太郎")?;
     
     let sum = add(10, 20);
-    println!("10 + 20 = {}", sum);
+    writeln!(out, "10 + 20 = {}", sum)?;
     
     let (q, r) = divide_and_remainder(17, 5);
-    println!("17 ÷ 5 = 商: {}, 余り: {}", q, r);
+    writeln!(out, "17 ÷ 5 = 商: {}, 余り: {}", q, r)?;
     
     // 式と文
     let y = {
         let x = 3;
         x + 1  // セミコロンなし = 式として評価される
     };
-    println!("ブロック式の結果: {}", y);
+    writeln!(out, "ブロック式の結果: {}", y)?;
+    Ok(())
 }
 
 // 4. 制御フロー
-pub fn control_flow() {
+pub fn control_flow(out: &mut dyn Write) -> io::Result<()> {
     // if式
     let number = 7;
     if number < 5 {
-        println!("条件は真");
+        writeln!(out, "条件は真")?;
     } else if number == 5 {
-        println!("数値は5");
+        writeln!(out, "数値は5")?;
     } else {
-        println!("条件は偽");
+        writeln!(out, "条件は偽")?;
     }
     
     // if式で値を返す
     let condition = true;
     let result = if condition { 5 } else { 6 };
-    println!("if式の結果: {}", result);
+    writeln!(out, "if式の結果: {}", result)?;
     
     // loop（無限ループ）
     let mut counter = 0;
@@ -121,31 +127,32 @@ pub fn control_flow() {
             break counter * 2;  // ループから値を返す
         }
     };
-    println!("ループの結果: {}", result);
+    writeln!(out, "ループの結果: {}", result)?;
     
     // while
     let mut number = 3;
     while number != 0 {
-        println!("{}!", number);
+        writeln!(out, "{}!", number)?;
         number -= 1;
     }
-    println!("発射！");
+    writeln!(out, "発射！")?;
     
     // for（最も一般的なループ）
     let arr = [10, 20, 30, 40, 50];
     for element in arr.iter() {
-        println!("配列の要素: {}", element);
+        writeln!(out, "配列の要素: {}", element)?;
     }
     
     // 範囲を使ったfor
     for i in 1..4 {  // 1, 2, 3（4は含まない）
-        println!("カウント: {}", i);
+        writeln!(out, "カウント: {}", i)?;
     }
     
     // 逆順
     for i in (1..4).rev() {
-        println!("カウントダウン: {}", i);
+        writeln!(out, "カウントダウン: {}", i)?;
     }
+    Ok(())
 }
 
 // 5. コメントとドキュメンテーション
@@ -154,6 +161,8 @@ pub fn control_flow() {
 /// # 例
 /// 
 /// ```
+/// use hello_cargo::basics::find_max;
+///
 /// let max = find_max(10, 20);
 /// assert_eq!(max, 20);
 /// ```
@@ -166,21 +175,106 @@ pub fn find_max(a: i32, b: i32) -> i32 {
     }
 }
 
-// メインの実行関数
-pub fn run_all_basics() {
-    println!("\n=== 変数と可変性 ===");
-    variables_and_mutability();
-    
-    println!("\n=== データ型 ===");
-    data_types();
-    
-    println!("\n=== 関数 ===");
-    functions_demo();
-    
-    println!("\n=== 制御フロー ===");
-    control_flow();
-    
-    println!("\n=== 最大値の検索 ===");
+// 最大値の検索の実行例
+pub fn find_max_demo(out: &mut dyn Write) -> io::Result<()> {
     let max = find_max(42, 38);
-    println!("42と38の最大値: {}", max);
-}
\ No newline at end of file
+    writeln!(out, "42と38の最大値: {}", max)?;
+    Ok(())
+}
+
+// デモの一覧（実行順）
+pub static DEMOS: &[Demo] = &[
+    Demo { name: "variables_and_mutability", title: "変数と可変性", title_en: "Variables and Mutability", run: variables_and_mutability },
+    Demo { name: "data_types", title: "データ型", title_en: "Data Types", run: data_types },
+    Demo { name: "functions_demo", title: "関数", title_en: "Functions", run: functions_demo },
+    Demo { name: "control_flow", title: "制御フロー", title_en: "Control Flow", run: control_flow },
+    Demo { name: "find_max", title: "最大値の検索", title_en: "Finding the Maximum", run: find_max_demo },
+];
+
+// メインの実行関数
+pub fn run_all_basics(out: &mut dyn Write) -> io::Result<()> {
+    section::run_demos(DEMOS, out)
+}
+
+// セクションの登録
+pub struct BasicsSection;
+
+impl Section for BasicsSection {
+    fn id(&self) -> &'static str {
+        "basics"
+    }
+
+    fn title_ja(&self) -> &'static str {
+        "基本的な文法"
+    }
+
+    fn title_en(&self) -> &'static str {
+        "Basics"
+    }
+
+    fn topics(&self) -> &'static str {
+        "変数、データ型、関数、制御フロー"
+    }
+
+    fn topics_en(&self) -> &'static str {
+        "Variables, data types, functions, control flow"
+    }
+
+    fn run(&self, out: &mut dyn Write) -> io::Result<()> {
+        run_all_basics(out)
+    }
+
+    fn demos(&self) -> &'static [Demo] {
+        DEMOS
+    }
+
+    fn source(&self) -> SourceFile {
+        SourceFile {
+            path: "basics/mod.rs",
+            content: include_str!("mod.rs"),
+        }
+    }
+
+    fn doc_pages(&self) -> &'static [DocPage] {
+        &[DocPage {
+            name: "readme",
+            path: "basics/README.md",
+            description: "Rustの基本文法の詳細",
+            content: include_str!("README.md"),
+            path_en: "basics/README.en.md",
+            description_en: "Details of Rust's basic syntax",
+            content_en: include_str!("README.en.md"),
+        }]
+    }
+
+    fn quiz(&self) -> SourceFile {
+        SourceFile {
+            path: "basics/quiz.md",
+            content: include_str!("quiz.md"),
+        }
+    }
+
+    fn cards(&self) -> SourceFile {
+        SourceFile {
+            path: "basics/cards.md",
+            content: include_str!("cards.md"),
+        }
+    }
+
+    fn exercises(&self) -> &'static [Exercise] {
+        &[
+            Exercise {
+                name: "variables",
+                path: "basics/exercises/variables.rs",
+                hint: "変数はデフォルトで不変です。値を変更する変数は`let mut`で宣言します。",
+                content: include_str!("exercises/variables.rs"),
+            },
+            Exercise {
+                name: "if_expression",
+                path: "basics/exercises/if_expression.rs",
+                hint: "`if number > 0 { \"正\" } else if number < 0 { \"負\" } else { \"ゼロ\" }`のように、ifの各分岐が値を返す式として書けます。",
+                content: include_str!("exercises/if_expression.rs"),
+            },
+        ]
+    }
+}
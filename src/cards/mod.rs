@@ -0,0 +1,695 @@
+// 復習カード（間隔反復）
+// 各セクションの`cards.md`に書かれたカードを、SM-2方式で次の復習日を決めながら出題します
+// 復習の予定は`$XDG_DATA_HOME/hello_cargo/cards.tsv`に保存し、
+// `cards export`でAnkiに読み込めるタブ区切りのファイルとして書き出せます
+//
+// カードの書き方:
+//
+//   ## 所有権の移動                 ← 出典のREADMEの見出し（復習中に`doc`で開ける）
+//
+//   Q: 質問
+//   A: 答え
+//   デモ: ownership_basics          ← 確認できるデモ（省略可能）
+
+use crate::doc;
+use crate::markdown::{self, terminal, terminal::Style, Align, Block};
+use crate::progress;
+use crate::section::{self, Section};
+use std::collections::BTreeMap;
+use std::fs;
+use std::io::{self, BufRead, Write};
+use std::path::{Path, PathBuf};
+
+const HEADER: &str = "# hello_cargo cards v1";
+
+// 1回の復習で出題するカードの数（`--limit`で変更できる）
+const DEFAULT_LIMIT: usize = 20;
+
+const DAY: u64 = 24 * 60 * 60;
+
+// 1. カード
+#[derive(Debug, Clone, PartialEq)]
+pub struct Card {
+    pub section: &'static str,   // セクションID
+    pub heading: String,         // 出典のREADMEの見出し
+    pub line: usize,             // 見出しのcards.md内の行番号
+    pub front: String,           // 質問
+    pub back: String,            // 答え
+    pub demo: Option<String>,    // 確認できるデモの関数名
+}
+
+impl Card {
+    // 復習の予定を保存するときのキー
+    // 質問の文面から作るので、カードの並び順や答えを直しても予定は引き継がれる
+    pub fn id(&self) -> String {
+        format!("{}/{:016x}", self.section, fnv1a(&self.front))
+    }
+}
+
+// FNV-1a（64ビット）
+fn fnv1a(text: &str) -> u64 {
+    text.bytes().fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+    })
+}
+
+// 2. カードの読み込み
+pub fn parse_deck(section: &'static str, source: &str) -> Result<Vec<Card>, String> {
+    let mut cards: Vec<Card> = Vec::new();
+    let mut heading: Option<(String, usize)> = None;
+
+    for block in markdown::parse(source) {
+        match block {
+            Block::Heading { level: 1, .. } => {}
+            Block::Heading { text, line, .. } => heading = Some((text, line)),
+            Block::Paragraph { lines } => {
+                let Some((heading, line)) = &heading else {
+                    return Err("カードの前に出典の見出し（`## 見出し`）を書いてください".to_string());
+                };
+                let error = |message: &str| format!("{}行目の見出しのカード: {}", line, message);
+                let Some(front) = lines.first().and_then(|first| first.strip_prefix("Q:")) else {
+                    return Err(error("カードは`Q:`で始めてください"));
+                };
+                let mut card = Card {
+                    section,
+                    heading: heading.clone(),
+                    line: *line,
+                    front: front.trim().to_string(),
+                    back: String::new(),
+                    demo: None,
+                };
+                let mut answering = false;
+                for text in &lines[1..] {
+                    if let Some(back) = text.strip_prefix("A:") {
+                        card.back = back.trim().to_string();
+                        answering = true;
+                    } else if let Some(demo) = text.strip_prefix("デモ:") {
+                        card.demo = Some(demo.trim().to_string());
+                    } else {
+                        // 続きの行は直前の質問か答えに加える
+                        let field = if answering { &mut card.back } else { &mut card.front };
+                        field.push('\n');
+                        field.push_str(text.trim());
+                    }
+                }
+                if card.front.is_empty() || card.back.is_empty() {
+                    return Err(error("`Q:`と`A:`の両方を書いてください"));
+                }
+                cards.push(card);
+            }
+            _ => {
+                let line = heading.as_ref().map_or(0, |(_, line)| *line);
+                return Err(format!("{}行目の見出しのカード: `Q:`と`A:`の段落だけを書いてください", line));
+            }
+        }
+    }
+    Ok(cards)
+}
+
+pub fn deck(section: &dyn Section) -> Result<Vec<Card>, String> {
+    let source = section.cards();
+    parse_deck(section.id(), source.content).map_err(|e| tr!("cards.deck_error", e, source.path))
+}
+
+// 3. 復習の予定（SM-2）
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Rating {
+    Again,  // 思い出せなかった
+    Hard,   // 思い出すのに苦労した
+    Good,   // 思い出せた
+    Easy,   // すぐに思い出せた
+}
+
+impl Rating {
+    fn parse(text: &str) -> Option<Rating> {
+        match text {
+            "1" => Some(Rating::Again),
+            "2" => Some(Rating::Hard),
+            "3" => Some(Rating::Good),
+            "4" => Some(Rating::Easy),
+            _ => None,
+        }
+    }
+
+    // SM-2の評価（0〜5、3以上なら思い出せたとみなす）
+    fn quality(self) -> u32 {
+        match self {
+            Rating::Again => 1,
+            Rating::Hard => 3,
+            Rating::Good => 4,
+            Rating::Easy => 5,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Schedule {
+    pub repetitions: u32,  // 続けて思い出せた回数
+    pub ease: u32,         // 間隔を伸ばす倍率（百分率、最小130）
+    pub interval: u32,     // 次の復習までの日数
+    pub due: u64,          // 次の復習の時刻（UNIX時間）
+    pub reviews: u32,      // 復習した回数
+}
+
+impl Default for Schedule {
+    fn default() -> Self {
+        Schedule { repetitions: 0, ease: 250, interval: 0, due: 0, reviews: 0 }
+    }
+}
+
+impl Schedule {
+    pub fn review(&mut self, rating: Rating, now: u64) {
+        let quality = rating.quality();
+        if quality >= 3 {
+            self.interval = match self.repetitions {
+                0 => 1,
+                1 => 6,
+                _ => (self.interval * self.ease).div_ceil(100),
+            };
+            self.repetitions += 1;
+        } else {
+            // 思い出せなかったら最初からやり直す
+            self.repetitions = 0;
+            self.interval = 1;
+        }
+        // EF' = EF + (0.1 - (5 - q) × (0.08 + (5 - q) × 0.02)) を百分率で計算する
+        let miss = 5 - quality;
+        self.ease = (self.ease + 10).saturating_sub(miss * (8 + miss * 2)).max(130);
+        self.due = now + u64::from(self.interval) * DAY;
+        self.reviews += 1;
+    }
+
+    pub fn is_due(&self, now: u64) -> bool {
+        self.due <= now
+    }
+}
+
+// 4. 予定の保存（タブ区切り、1行に1枚）
+//   <カードID>  <連続回数>  <倍率>  <間隔（日）>  <次の復習の時刻>  <復習回数>
+#[derive(Debug, Default, PartialEq)]
+pub struct Store {
+    pub schedules: BTreeMap<String, Schedule>,
+}
+
+impl Store {
+    pub fn parse(text: &str) -> Result<Store, String> {
+        let mut store = Store::default();
+        for (i, line) in text.lines().enumerate() {
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let error = || tr!("cards.invalid_line", i + 1);
+            let fields: Vec<&str> = line.split('\t').collect();
+            let [id, repetitions, ease, interval, due, reviews] = fields[..] else {
+                return Err(error());
+            };
+            let schedule = Schedule {
+                repetitions: repetitions.parse().map_err(|_| error())?,
+                ease: ease.parse().map_err(|_| error())?,
+                interval: interval.parse().map_err(|_| error())?,
+                due: due.parse().map_err(|_| error())?,
+                reviews: reviews.parse().map_err(|_| error())?,
+            };
+            store.schedules.insert(id.to_string(), schedule);
+        }
+        Ok(store)
+    }
+
+    pub fn to_text(&self) -> String {
+        let mut text = format!("{}\n", HEADER);
+        for (id, s) in &self.schedules {
+            text.push_str(&format!(
+                "{}\t{}\t{}\t{}\t{}\t{}\n",
+                id, s.repetitions, s.ease, s.interval, s.due, s.reviews
+            ));
+        }
+        text
+    }
+
+    pub fn load(path: &Path) -> io::Result<Store> {
+        match fs::read_to_string(path) {
+            Ok(text) => Store::parse(&text).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e)),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Store::default()),
+            Err(e) => Err(e),
+        }
+    }
+
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        // 書き込み途中で中断しても壊れないよう、一時ファイルに書いてから置き換える
+        let temp = path.with_extension("tsv.tmp");
+        fs::write(&temp, self.to_text())?;
+        fs::rename(&temp, path)
+    }
+
+    fn get(&self, card: &Card) -> Option<&Schedule> {
+        self.schedules.get(&card.id())
+    }
+
+    // 出題するカード: 復習日を過ぎたもの（古い順）のあとに、まだ学習していないもの（cards.mdの順）
+    pub fn queue<'a>(&self, cards: &'a [Card], now: u64, limit: usize) -> Vec<&'a Card> {
+        let mut due: Vec<(u64, &Card)> = cards
+            .iter()
+            .filter_map(|card| self.get(card).filter(|s| s.is_due(now)).map(|s| (s.due, card)))
+            .collect();
+        due.sort_by_key(|(time, _)| *time);
+        let new = cards.iter().filter(|card| self.get(card).is_none());
+        due.into_iter().map(|(_, card)| card).chain(new).take(limit).collect()
+    }
+}
+
+// 5. 出典の見出し
+// 見出しのアンカー（`doc <セクション>#<アンカー>`で開ける）と、見出しの節の内容
+fn reference(card: &Card) -> Option<(String, Vec<Block>)> {
+    let section = section::find(card.section)?;
+    let page = &section.doc_pages()[0];
+    let blocks = markdown::parse(page.localized_content());
+    let index = doc::find_heading(page, &blocks, &card.heading)?;
+    let Block::Heading { text, .. } = &blocks[index] else {
+        return None;
+    };
+    Some((markdown::anchor(text), markdown::heading_section(&blocks, index).to_vec()))
+}
+
+fn doc_command(card: &Card) -> String {
+    let anchor = reference(card).map_or_else(|| card.heading.clone(), |(anchor, _)| anchor);
+    format!("cargo run -- doc {}#{}", card.section, anchor)
+}
+
+// 6. 復習
+// 評価したカードの数を返す（`q`や入力の終わりで中断したら、それまでの数）
+pub fn review(
+    cards: &[&Card],
+    store: &mut Store,
+    now: u64,
+    input: &mut dyn BufRead,
+    out: &mut dyn Write,
+    style: Style,
+) -> io::Result<usize> {
+    let mut reviewed = 0;
+    for (i, card) in cards.iter().enumerate() {
+        let position = format!("[{}/{}]", i + 1, cards.len());
+        writeln!(
+            out,
+            "\n{} {}",
+            style.paint(terminal::DIM, &position),
+            style.paint(terminal::CYAN, &format!("{} - {}", card.section, card.heading))
+        )?;
+        for line in card.front.lines() {
+            writeln!(out, "{}", style.paint(terminal::BOLD, &terminal::inline(line, style)))?;
+        }
+        write!(out, "\n{} ", tr!("cards.show_prompt"))?;
+        out.flush()?;
+        if read_command(input)?.is_none_or(|command| command == "q") {
+            writeln!(out, "\n{}", tr!("cards.quit"))?;
+            return Ok(reviewed);
+        }
+
+        for line in card.back.lines() {
+            writeln!(out, "{}", terminal::inline(line, style))?;
+        }
+        writeln!(out, "  {} {}", style.paint(terminal::DIM, tr!("cards.see_doc")), doc_command(card))?;
+        if let Some(demo) = &card.demo {
+            writeln!(out, "  {} cargo run -- {} {}", style.paint(terminal::DIM, tr!("cards.see_demo")), card.section, demo)?;
+        }
+
+        let rating = loop {
+            write!(out, "\n{} ", tr!("cards.rate_prompt"))?;
+            out.flush()?;
+            let Some(command) = read_command(input)? else {
+                writeln!(out, "\n{}", tr!("cards.quit"))?;
+                return Ok(reviewed);
+            };
+            match command.as_str() {
+                "q" => {
+                    writeln!(out, "{}", tr!("cards.quit"))?;
+                    return Ok(reviewed);
+                }
+                // その場で出典の節を表示する
+                "d" => match reference(card) {
+                    Some((_, blocks)) => write!(out, "\n{}", terminal::render_blocks(&blocks, style))?,
+                    None => writeln!(out, "{}", tr!("cards.no_reference", card.heading))?,
+                },
+                other => match Rating::parse(other) {
+                    Some(rating) => break rating,
+                    None => writeln!(out, "{}", tr!("cards.invalid_rating"))?,
+                },
+            }
+        };
+
+        let schedule = store.schedules.entry(card.id()).or_default();
+        schedule.review(rating, now);
+        reviewed += 1;
+        writeln!(out, "{}", style.paint(terminal::DIM, &tr!("cards.next_review", schedule.interval)))?;
+    }
+    Ok(reviewed)
+}
+
+// 入力の終わりならNone
+fn read_command(input: &mut dyn BufRead) -> io::Result<Option<String>> {
+    let mut line = String::new();
+    if input.read_line(&mut line)? == 0 {
+        return Ok(None);
+    }
+    Ok(Some(line.trim().to_lowercase()))
+}
+
+// 7. Ankiへの書き出し
+// ヘッダーで区切り文字と列の意味を指定する（Anki 2.1.54以降で読み込める）
+// GUIDの列があるので、書き出し直して読み込むと同じノートが更新される
+pub fn to_anki(cards: &[Card]) -> String {
+    let mut text = String::from("#separator:tab\n#html:false\n#guid column:1\n#deck column:4\n#tags column:5\n");
+    for card in cards {
+        let back = format!(
+            "{}\n\n{}",
+            card.back,
+            tr!("cards.anki_reference", card.heading, doc_command(card))
+        );
+        let fields = [
+            card.id(),
+            card.front.clone(),
+            back,
+            format!("hello_cargo::{}", card.section),
+            format!("hello_cargo {}", card.section),
+        ];
+        let fields: Vec<String> = fields.iter().map(|field| anki_field(field)).collect();
+        text.push_str(&fields.join("\t"));
+        text.push('\n');
+    }
+    text
+}
+
+// タブ・改行・引用符を含むフィールドは引用符で囲む（中の引用符は2つ重ねる）
+fn anki_field(text: &str) -> String {
+    if text.contains(['\t', '\n', '"']) {
+        format!("\"{}\"", text.replace('"', "\"\""))
+    } else {
+        text.to_string()
+    }
+}
+
+// 8. コマンド
+// `cards [list]`、`cards review [セクション] [--limit N]`、`cards export <ファイル>`
+pub fn command(args: &[String]) {
+    match args.first().map(String::as_str) {
+        None | Some("list") => list_command(),
+        Some("review") => review_command(&args[1..]),
+        Some("export") => export_command(&args[1..]),
+        Some(_) => print_help(),
+    }
+}
+
+fn load_decks(sections: &[&'static dyn Section]) -> Option<Vec<Card>> {
+    let mut cards = Vec::new();
+    for section in sections {
+        match deck(*section) {
+            Ok(deck) => cards.extend(deck),
+            Err(e) => {
+                println!("{}", e);
+                return None;
+            }
+        }
+    }
+    Some(cards)
+}
+
+fn load_store() -> Option<(Store, PathBuf)> {
+    let Some(path) = progress::data_dir().map(|dir| dir.join("cards.tsv")) else {
+        println!("{}", tr!("progress.no_data_dir"));
+        return None;
+    };
+    match Store::load(&path) {
+        Ok(store) => Some((store, path)),
+        Err(e) => {
+            println!("{}", tr!("cards.load_error", e, path.display()));
+            None
+        }
+    }
+}
+
+fn list_command() {
+    let Some((store, _)) = load_store() else {
+        return;
+    };
+    let mut rows = Vec::new();
+    for section in section::all() {
+        let Some(cards) = load_decks(&[*section]) else {
+            return;
+        };
+        rows.push(count_row(section.id(), &cards, &store, progress::now()));
+    }
+    let table = Block::Table {
+        header: vec![
+            tr!("cards.col.section").to_string(),
+            tr!("cards.col.cards").to_string(),
+            tr!("cards.col.due").to_string(),
+            tr!("cards.col.new").to_string(),
+        ],
+        aligns: vec![Align::Left, Align::Right, Align::Right, Align::Right],
+        rows,
+    };
+    println!("{}\n", tr!("cards.list_title"));
+    print!("{}", terminal::render_blocks(&[table], Style::detect()));
+    println!("\n{}", tr!("cards.review_hint"));
+}
+
+// セクションのカードの数、復習日を過ぎた数、まだ学習していない数
+fn count_row(section: &str, cards: &[Card], store: &Store, now: u64) -> Vec<String> {
+    let due = cards.iter().filter(|card| store.get(card).is_some_and(|s| s.is_due(now))).count();
+    let new = cards.iter().filter(|card| store.get(card).is_none()).count();
+    vec![section.to_string(), cards.len().to_string(), due.to_string(), new.to_string()]
+}
+
+fn review_command(args: &[String]) {
+    let mut limit = DEFAULT_LIMIT;
+    let mut name: Option<&str> = None;
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--limit" => {
+                let Some(value) = iter.next().and_then(|v| v.parse::<usize>().ok()) else {
+                    println!("{}", tr!("quiz.needs_number", arg));
+                    return;
+                };
+                limit = value;
+            }
+            other => name = Some(other),
+        }
+    }
+
+    let sections: Vec<&'static dyn Section> = match name {
+        Some(name) => match section::find(name) {
+            Some(section) => vec![section],
+            None => {
+                println!("{}", tr!("common.unknown_section", name));
+                return;
+            }
+        },
+        None => section::all().to_vec(),
+    };
+    let Some(cards) = load_decks(&sections) else {
+        return;
+    };
+    let Some((mut store, path)) = load_store() else {
+        return;
+    };
+
+    let now = progress::now();
+    let queue = store.queue(&cards, now, limit);
+    if queue.is_empty() {
+        println!("{}", tr!("cards.nothing_due"));
+        return;
+    }
+    println!("{}", tr!("cards.review_header", queue.len()));
+
+    let style = Style::detect();
+    let mut stdout = io::stdout();
+    let reviewed = match review(&queue, &mut store, now, &mut io::stdin().lock(), &mut stdout, style) {
+        Ok(reviewed) => reviewed,
+        Err(e) => {
+            eprintln!("{}", e);
+            return;
+        }
+    };
+    if reviewed > 0
+        && let Err(e) = store.save(&path)
+    {
+        println!("{}", tr!("cards.save_error", e, path.display()));
+        return;
+    }
+    println!("\n{}", style.paint(terminal::BOLD, &tr!("cards.review_done", reviewed)));
+}
+
+fn export_command(args: &[String]) {
+    let Some(target) = args.first() else {
+        print_help();
+        return;
+    };
+    let Some(cards) = load_decks(section::all()) else {
+        return;
+    };
+    let text = to_anki(&cards);
+    // `-`なら標準出力に書き出す
+    if target == "-" {
+        print!("{}", text);
+        return;
+    }
+    match fs::write(target, text) {
+        Ok(()) => println!("{}", tr!("cards.exported", cards.len(), target)),
+        Err(e) => println!("{}", tr!("cards.export_error", e, target)),
+    }
+}
+
+fn print_help() {
+    println!("{}", tr!("cards.usage"));
+    println!("        {}", tr!("cards.usage_review"));
+    println!("        {}", tr!("cards.usage_export"));
+    println!("\n{}", tr!("help.examples"));
+    println!("  {}", tr!("cards.ex.review"));
+    println!("  {}", tr!("cards.ex.export"));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PLAIN: Style = Style { color: false };
+
+    const SAMPLE: &str = "# カード\n\n## 所有権の移動\n\nQ: `let s2 = s1;`の後の`s1`は？\nA: 無効になる。\n続きの行\nデモ: ownership_basics\n\nQ: 2枚目\nA: 答え\n";
+
+    fn sample() -> Vec<Card> {
+        parse_deck("ownership", SAMPLE).unwrap()
+    }
+
+    #[test]
+    fn parses_cards_under_headings() {
+        let cards = sample();
+        assert_eq!(cards.len(), 2);
+        assert_eq!(cards[0].heading, "所有権の移動");
+        assert_eq!(cards[0].front, "`let s2 = s1;`の後の`s1`は？");
+        assert_eq!(cards[0].back, "無効になる。\n続きの行");
+        assert_eq!(cards[0].demo.as_deref(), Some("ownership_basics"));
+        assert_eq!(cards[1].demo, None);
+        assert_ne!(cards[0].id(), cards[1].id());
+        assert!(cards[0].id().starts_with("ownership/"));
+    }
+
+    #[test]
+    fn rejects_invalid_cards() {
+        assert!(parse_deck("basics", "Q: 見出しがない\nA: a\n").is_err());
+        assert!(parse_deck("basics", "## 関数\n\nQ: 答えがない\n").is_err());
+        assert!(parse_deck("basics", "## 関数\n\n質問\nA: a\n").is_err());
+        assert!(parse_deck("basics", "## 関数\n\n- リスト\n").is_err());
+    }
+
+    #[test]
+    fn schedules_with_sm2() {
+        let mut schedule = Schedule::default();
+        schedule.review(Rating::Good, 0);
+        assert_eq!((schedule.interval, schedule.due, schedule.ease), (1, DAY, 250));
+        schedule.review(Rating::Good, DAY);
+        assert_eq!(schedule.interval, 6);
+        schedule.review(Rating::Easy, 7 * DAY);
+        assert_eq!((schedule.interval, schedule.ease), (15, 260));
+        schedule.review(Rating::Hard, 22 * DAY);
+        assert_eq!((schedule.interval, schedule.ease, schedule.repetitions), (39, 246, 4));
+
+        // 思い出せなければ間隔は1日に戻り、倍率は下がる（最小130）
+        schedule.review(Rating::Again, 61 * DAY);
+        assert_eq!((schedule.interval, schedule.repetitions, schedule.ease), (1, 0, 192));
+        for _ in 0..5 {
+            schedule.review(Rating::Again, 0);
+        }
+        assert_eq!(schedule.ease, 130);
+        assert_eq!(schedule.reviews, 10);
+    }
+
+    #[test]
+    fn round_trips_through_text() {
+        let mut store = Store::default();
+        let mut schedule = Schedule::default();
+        schedule.review(Rating::Good, 100);
+        store.schedules.insert("ownership/0123456789abcdef".to_string(), schedule);
+        store.schedules.insert("basics/fedcba9876543210".to_string(), Schedule::default());
+
+        let text = store.to_text();
+        assert!(text.starts_with(HEADER));
+        assert_eq!(Store::parse(&text), Ok(store));
+        assert!(Store::parse("basics/0\t1\t250\n").is_err());
+    }
+
+    #[test]
+    fn queues_due_cards_before_new_ones() {
+        let cards = sample();
+        let mut store = Store::default();
+        assert_eq!(store.queue(&cards, 0, 10), vec![&cards[0], &cards[1]]);
+        assert_eq!(store.queue(&cards, 0, 1), vec![&cards[0]]);
+
+        store.schedules.entry(cards[0].id()).or_default().review(Rating::Good, 0);
+        assert_eq!(store.queue(&cards, 0, 10), vec![&cards[1]]);
+        store.schedules.entry(cards[1].id()).or_default().review(Rating::Good, 0);
+        assert!(store.queue(&cards, DAY - 1, 10).is_empty());
+        assert_eq!(store.queue(&cards, DAY, 10).len(), 2);
+        assert_eq!(count_row("ownership", &cards, &store, DAY), vec!["ownership", "2", "2", "0"]);
+    }
+
+    #[test]
+    fn reviews_and_records_ratings() {
+        let cards = sample();
+        let queue: Vec<&Card> = cards.iter().collect();
+        let mut store = Store::default();
+        let mut out = Vec::new();
+
+        // 1枚目は答えを見てから説明を表示し、範囲外の評価のあとに「普通」、2枚目は答えの前で終了する
+        let mut input = "\nd\n9\n3\nq\n".as_bytes();
+        let reviewed = review(&queue, &mut store, 0, &mut input, &mut out, PLAIN).unwrap();
+        let output = String::from_utf8(out).unwrap();
+
+        assert_eq!(reviewed, 1);
+        assert_eq!(store.schedules.len(), 1);
+        assert_eq!(store.schedules[&cards[0].id()].interval, 1);
+        assert!(output.contains("[1/2] ownership - 所有権の移動"), "{}", output);
+        assert!(output.contains("cargo run -- doc ownership#3-所有権の移動move"), "{}", output);
+        assert!(output.contains("cargo run -- ownership ownership_basics"));
+        assert!(output.contains("基本的な移動"), "{}", output);
+        assert!(output.contains(tr!("cards.invalid_rating")));
+        assert!(output.ends_with(&format!("{}\n", tr!("cards.quit"))));
+    }
+
+    #[test]
+    fn exports_anki_tsv() {
+        let text = to_anki(&sample());
+        let lines: Vec<&str> = text.lines().collect();
+        assert_eq!(lines[0], "#separator:tab");
+        assert!(text.contains("#guid column:1\n#deck column:4\n#tags column:5\n"));
+
+        // 改行を含む答えは引用符で囲まれ、出典の見出しが付く
+        let first = text.split_once("#tags column:5\n").unwrap().1;
+        assert!(first.starts_with(&format!("{}\t`let s2 = s1;`の後の`s1`は？\t\"無効になる。\n続きの行\n\n", sample()[0].id())));
+        assert!(first.contains("ownership#3-所有権の移動move"));
+        assert!(first.contains("\"\thello_cargo::ownership\thello_cargo ownership\n"));
+        assert_eq!(anki_field("a\"b"), "\"a\"\"b\"");
+        assert_eq!(anki_field("plain"), "plain");
+    }
+
+    // すべてのセクションのカードが正しく書かれ、見出しとデモが見つかること
+    #[test]
+    fn every_deck_is_valid() {
+        let mut ids = std::collections::HashSet::new();
+        for section in section::all() {
+            let path = section.cards().path;
+            let cards = deck(*section).unwrap_or_else(|e| panic!("{}", e));
+            assert!(cards.len() >= 5, "{}: カードが少なすぎます", path);
+            for card in &cards {
+                let at = format!("{}:{}", path, card.line);
+                assert!(reference(card).is_some(), "{}: 見出しが見つかりません {}", at, card.heading);
+                assert!(ids.insert(card.id()), "{}: 同じ質問のカードがあります", at);
+                if let Some(demo) = &card.demo {
+                    assert!(section.demos().iter().any(|d| d.name == demo), "{}: 不明なデモ {}", at, demo);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,336 @@
+// コマンドラインの処理
+// 引数からコマンドを選んで、各モジュールの機能を呼び出します（`main.rs`はこれを呼ぶだけです）
+
+use crate::section::{self, Section};
+use crate::transcript::{self, Format};
+use crate::markdown::terminal::Style;
+use crate::{cards, curriculum, doc, exercise, explain, export, glossary, i18n, interactive, layout, play, progress, quiz, search, source, step, watch};
+use std::io;
+use std::path::PathBuf;
+
+// セクションより先に判定するコマンドの名前（セクションのIDや別名には使えない）
+pub(crate) const COMMANDS: &[&str] = &[
+    "doc", "list", "search", "source", "quiz", "cards", "glossary", "explain", "play", "layout",
+    "exercise", "export", "progress", "path", "next", "reset", "watch", "interactive", "all",
+];
+
+// `args`は`std::env::args()`と同じく、先頭がプログラム名
+pub fn run(mut args: Vec<String>) {
+    // 表示言語（省略時は環境変数LANGなどから判定する）
+    match take_option(&mut args, "--lang") {
+        Ok(Some(name)) => match i18n::Lang::parse(&name) {
+            Some(lang) => i18n::set_lang(lang),
+            None => {
+                i18n::set_lang(i18n::Lang::detect());
+                println!("{}", tr!("main.unknown_lang", name));
+                return;
+            }
+        },
+        Ok(None) => i18n::set_lang(i18n::Lang::detect()),
+        Err(message) => {
+            println!("{}", message);
+            return;
+        }
+    }
+
+    // ドキュメントをファイルから読み込む場合のディレクトリ（省略時は埋め込み版を使う）
+    let docs_dir = match take_option(&mut args, "--docs-dir") {
+        Ok(dir) => dir.map(PathBuf::from),
+        Err(message) => {
+            println!("{}", message);
+            return;
+        }
+    };
+
+    // デモの実行結果の出力形式（省略時はそのまま表示する）
+    let format = match take_option(&mut args, "--format") {
+        Ok(None) => Format::Text,
+        Ok(Some(name)) => match Format::parse(&name) {
+            Some(format) => format,
+            None => {
+                println!("{}", tr!("main.unknown_format", name));
+                return;
+            }
+        },
+        Err(message) => {
+            println!("{}", message);
+            return;
+        }
+    };
+
+    // デモを1つずつ、説明を表示しながら実行する
+    let step = take_flag(&mut args, "--step");
+
+    if args.len() < 2 {
+        print_help();
+        return;
+    }
+
+    match args[1].as_str() {
+        name if !COMMANDS.contains(&name) => match section::find(name) {
+            Some(section) => run_section(section, &args[2..], format, step),
+            None => {
+                println!("{}", tr!("common.unknown_section", name));
+                print_help();
+            }
+        },
+        "doc" => doc::command(&args[2..], docs_dir.as_deref()),
+        "list" => list_demos(args.get(2).map(String::as_str)),
+        "search" => search::command(&args[2..]),
+        "source" => source::command(&args[2..]),
+        "quiz" => quiz::command(&args[2..]),
+        "cards" => cards::command(&args[2..]),
+        "glossary" => glossary::command(&args[2..]),
+        "explain" => explain::command(&args[2..]),
+        "play" => play::command(&args[2..]),
+        "layout" => layout::command(&args[2..]),
+        "exercise" => exercise::command(&args[2..]),
+        "export" => export::command(&args[2..], docs_dir.as_deref()),
+        "progress" => progress::command(&args[2..]),
+        "path" => curriculum::path_command(),
+        "next" => curriculum::next_command(),
+        "reset" => progress::reset_command(&args[2..]),
+        "watch" => watch::command(&args[2..]),
+        "interactive" => interactive::Repl::new(docs_dir.as_deref()).run(),
+        "all" => run_all_sections(format, step),
+        name => unreachable!("`{}`はCOMMANDSにあるのに処理されていない", name),
+    }
+}
+
+// `--name value`または`--name=value`の形式のオプションを取り除いて値を返す
+fn take_option(args: &mut Vec<String>, name: &str) -> Result<Option<String>, String> {
+    let prefix = format!("{}=", name);
+
+    let Some(index) = args
+        .iter()
+        .position(|arg| arg == name || arg.starts_with(&prefix))
+    else {
+        return Ok(None);
+    };
+
+    let arg = args.remove(index);
+    if let Some(value) = arg.strip_prefix(&prefix) {
+        return Ok(Some(value.to_string()));
+    }
+    if index < args.len() {
+        return Ok(Some(args.remove(index)));
+    }
+    Err(tr!("main.option_needs_value", name))
+}
+
+// `--step`のように値を取らないオプションを取り除き、指定されていたかを返す
+fn take_flag(args: &mut Vec<String>, name: &str) -> bool {
+    let len = args.len();
+    args.retain(|arg| arg != name);
+    args.len() != len
+}
+
+pub(crate) fn run_section(section: &dyn Section, patterns: &[String], format: Format, step: bool) {
+    if format != Format::Text || step {
+        let demos = if patterns.is_empty() {
+            Ok(section.demos().iter().collect())
+        } else {
+            section::select_demos(section.demos(), patterns)
+        };
+        match demos {
+            Ok(demos) if format == Format::Text => {
+                println!("{}", tr!("main.section_header", section.title()));
+                run_steps(section, &demos);
+            }
+            Ok(demos) => write_transcript(format, [(section, demos)]),
+            Err(pattern) => {
+                println!("{}", tr!("main.unknown_demo", section.id(), pattern));
+                print_demos(section);
+            }
+        }
+        return;
+    }
+
+    if patterns.is_empty() {
+        println!("{}", tr!("main.section_header", section.title()));
+        if report(i18n::demo::run(&mut io::stdout(), |out| section.run(out))) {
+            progress::record_demos(section, section.demos());
+        }
+        return;
+    }
+
+    match section::select_demos(section.demos(), patterns) {
+        Ok(demos) => {
+            println!("{}", tr!("main.section_header", section.title()));
+            let run = |out: &mut dyn io::Write| section::run_demos(demos.iter().copied(), out);
+            if report(i18n::demo::run(&mut io::stdout(), run)) {
+                progress::record_demos(section, demos);
+            }
+        }
+        Err(pattern) => {
+            println!("{}", tr!("main.unknown_demo", section.id(), pattern));
+            print_demos(section);
+        }
+    }
+}
+
+fn list_demos(name: Option<&str>) {
+    match name {
+        Some(name) => match section::find(name) {
+            Some(section) => print_demos(section),
+            None => {
+                println!("{}", tr!("common.unknown_section", name));
+                print_help();
+            }
+        },
+        None => {
+            for (i, section) in section::all().iter().enumerate() {
+                if i > 0 {
+                    println!();
+                }
+                print_demos(*section);
+            }
+        }
+    }
+}
+
+pub(crate) fn print_demos(section: &dyn Section) {
+    println!("{} - {}", section.id(), section.title());
+    for (i, demo) in section.demos().iter().enumerate() {
+        println!("  {:>2}. {:<30}{}", i + 1, demo.name, demo.heading());
+    }
+}
+
+pub(crate) fn run_all_sections(format: Format, step: bool) {
+    if format != Format::Text {
+        let runs = section::all().iter().map(|section| (*section, section.demos().iter().collect()));
+        write_transcript(format, runs);
+        return;
+    }
+
+    println!("{}", tr!("main.run_all"));
+
+    for section in section::all() {
+        println!("\n>>> {}", section.title());
+        if step {
+            let demos: Vec<&section::Demo> = section.demos().iter().collect();
+            if !run_steps(*section, &demos) {
+                return;
+            }
+            continue;
+        }
+        if !report(i18n::demo::run(&mut io::stdout(), |out| section.run(out))) {
+            return;
+        }
+        progress::record_demos(*section, section.demos());
+    }
+}
+
+// デモを1つずつ実行し、説明を表示して入力を待つ（最後まで進んだらtrueを返す）
+fn run_steps(section: &dyn Section, demos: &[&section::Demo]) -> bool {
+    let mut input = io::stdin().lock();
+    match step::run(section, demos, &mut input, &mut io::stdout(), Style::detect()) {
+        Ok(finished) => finished,
+        Err(e) => report(Err(e)),
+    }
+}
+
+// デモを1つずつ実行し、結果をJSONで書き出す（正常に終わったデモを進捗に記録する）
+fn write_transcript<'a>(
+    format: Format,
+    runs: impl IntoIterator<Item = (&'a dyn Section, Vec<&'a section::Demo>)>,
+) {
+    let mut stdout = io::stdout().lock();
+    let record = |section, demo| progress::record_demos(section, [demo]);
+    report(transcript::write(&mut stdout, format, runs, record));
+}
+
+// デモの出力に失敗したらfalseを返す（`| head`などでパイプが閉じられた場合は何も表示しない）
+fn report(result: io::Result<()>) -> bool {
+    match result {
+        Ok(()) => true,
+        Err(e) if e.kind() == io::ErrorKind::BrokenPipe => false,
+        Err(e) => {
+            eprintln!("{}", tr!("main.output_error", e));
+            false
+        }
+    }
+}
+
+fn print_help() {
+    println!("{}", tr!("help.title"));
+    println!("\n{}", tr!("help.usage"));
+    println!("\n{}", tr!("help.commands"));
+    println!("  {}", tr!("help.cmd.doc"));
+    println!("  {}", tr!("help.cmd.list"));
+    println!("  {}", tr!("help.cmd.search"));
+    println!("  {}", tr!("help.cmd.glossary"));
+    println!("  {}", tr!("help.cmd.source"));
+    println!("  {}", tr!("help.cmd.quiz"));
+    println!("  {}", tr!("help.cmd.cards"));
+    println!("  {}", tr!("help.cmd.exercise"));
+    println!("  {}", tr!("help.cmd.explain"));
+    println!("  {}", tr!("help.cmd.play"));
+    println!("  {}", tr!("help.cmd.layout"));
+    println!("  {}", tr!("help.cmd.export"));
+    println!("  {}", tr!("help.cmd.progress"));
+    println!("  {}", tr!("help.cmd.reset"));
+    println!("  {}", tr!("help.cmd.path"));
+    println!("  {}", tr!("help.cmd.next"));
+    println!("  {}", tr!("help.cmd.watch"));
+    println!("  {}", tr!("help.cmd.interactive"));
+    println!("  {}", tr!("help.cmd.section"));
+    println!("  {}", tr!("help.cmd.demos"));
+    println!("\n{}", tr!("help.sections"));
+    for section in section::all() {
+        println!("  {:<13}- {}", section.id(), tr!("help.section_entry", section.title(), section.topic_summary()));
+    }
+    println!("  {:<13}- {}", "all", tr!("help.all"));
+    println!("\n{}", tr!("help.options"));
+    println!("  {}", tr!("help.opt.docs_dir"));
+    println!("                              {}", tr!("help.opt.docs_dir_example"));
+    println!("  {}", tr!("help.opt.lang"));
+    println!("  {}", tr!("help.opt.format"));
+    println!("  {}", tr!("help.opt.step"));
+    println!("\n{}", tr!("help.examples"));
+    println!("  {}", tr!("help.ex.basics"));
+    println!("  {}", tr!("help.ex.doc_basics"));
+    println!("  {}", tr!("help.ex.doc_anchor"));
+    println!("  {}", tr!("help.ex.demo"));
+    println!("  {}", tr!("help.ex.glob"));
+    println!("  {}", tr!("help.ex.list"));
+    println!("  {}", tr!("help.ex.all"));
+    println!("  {}", tr!("help.ex.interactive"));
+    println!("  {}", tr!("help.ex.watch"));
+    println!("  {}", tr!("help.ex.quiz"));
+    println!("  {}", tr!("help.ex.cards"));
+    println!("  {}", tr!("help.ex.exercise"));
+    println!("  {}", tr!("help.ex.explain"));
+    println!("  {}", tr!("help.ex.play"));
+    println!("  {}", tr!("help.ex.layout"));
+    println!("  {}", tr!("help.ex.next"));
+    println!("  {}", tr!("help.ex.doc"));
+    println!("  {}", tr!("help.ex.export"));
+    println!("  {}", tr!("help.ex.search"));
+    println!("  {}", tr!("help.ex.glossary"));
+    println!("  {}", tr!("help.ex.source"));
+    println!("  {}", tr!("help.ex.lang"));
+    println!("  {}", tr!("help.ex.json"));
+    println!("  {}", tr!("help.ex.step"));
+    println!("\n{}", tr!("help.testing"));
+    println!("  cargo test");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::source;
+
+    // `run`で判定しているコマンドと`COMMANDS`が一致すること
+    #[test]
+    fn commands_match_dispatch() {
+        let run = source::find_function(include_str!("mod.rs"), "run").unwrap();
+        let dispatched: Vec<&str> = run
+            .code
+            .lines()
+            .filter_map(|line| line.trim().strip_prefix('"')?.split_once("\" =>"))
+            .map(|(name, _)| name)
+            .collect();
+        assert_eq!(dispatched, COMMANDS);
+    }
+}
@@ -0,0 +1,28 @@
+// 演習: ベクタとイテレータ
+//
+// `evens_doubled`は偶数だけを取り出して2倍にしたベクタを返す関数です。
+// 借用中のベクタを変更しようとしているため、コンパイルできません。
+// 元のベクタを変更せず、イテレータ（`iter`・`filter`・`map`・`collect`）を使って書き直してください。
+//
+// 確認: cargo run -- exercise check vector_stats
+
+pub fn evens_doubled(numbers: &Vec<i32>) -> Vec<i32> {
+    for n in numbers {
+        if n % 2 != 0 {
+            numbers.retain(|x| x != n);
+        }
+    }
+    numbers.clone()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn doubles_even_numbers() {
+        let numbers = vec![1, 2, 3, 4, 5, 6];
+        assert_eq!(evens_doubled(&numbers), vec![4, 8, 12]);
+        assert_eq!(numbers.len(), 6);
+    }
+}
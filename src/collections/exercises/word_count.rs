@@ -0,0 +1,29 @@
+// 演習: ハッシュマップ
+//
+// `word_count`は文章に含まれる単語ごとの出現回数を数える関数です。
+// `entry`と`or_insert`を使って完成させてください。
+//
+// 確認: cargo run -- exercise check word_count
+
+use std::collections::HashMap;
+
+pub fn word_count(text: &str) -> HashMap<&str, u32> {
+    let mut map = HashMap::new();
+    for word in text.split_whitespace() {
+        todo!()
+    }
+    map
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn counts_words() {
+        let counts = word_count("hello world wonderful world");
+        assert_eq!(counts["hello"], 1);
+        assert_eq!(counts["world"], 2);
+        assert_eq!(counts.len(), 3);
+    }
+}
@@ -4,9 +4,11 @@
 use std::collections::HashMap;
 use std::collections::HashSet;
 use std::collections::VecDeque;
+use std::io::{self, Write};
+use crate::section::{self, Demo, DocPage, Exercise, Section, SourceFile};
 
 // 1. ベクタ（Vec<T>）
-pub fn vectors() {
+pub fn vectors(out: &mut dyn Write) -> io::Result<()> {
     // ベクタの作成
     let _v1: Vec<i32> = Vec::new();
     let _v2 = vec![1, 2, 3];  // vec!マクロ
@@ -18,32 +20,32 @@ pub fn vectors() {
     v.push(7);
     v.push(8);
     
-    println!("ベクタ: {:?}", v);
+    writeln!(out, "ベクタ: {:?}", v)?;
     
     // 要素へのアクセス
     let third: &i32 = &v[2];
-    println!("3番目の要素: {}", third);
+    writeln!(out, "3番目の要素: {}", third)?;
     
     // getメソッド（安全なアクセス）
     match v.get(2) {
-        Some(third) => println!("3番目の要素: {}", third),
-        None => println!("3番目の要素はありません"),
+        Some(third) => writeln!(out, "3番目の要素: {}", third)?,
+        None => writeln!(out, "3番目の要素はありません")?,
     }
     
     // 範囲外アクセスの処理
     let does_not_exist = v.get(100);
-    println!("100番目の要素: {:?}", does_not_exist);
+    writeln!(out, "100番目の要素: {:?}", does_not_exist)?;
     
     // イテレーション
     for i in &v {
-        println!("値: {}", i);
+        writeln!(out, "値: {}", i)?;
     }
     
     // 可変イテレーション
     for i in &mut v {
         *i += 50;
     }
-    println!("変更後: {:?}", v);
+    writeln!(out, "変更後: {:?}", v)?;
     
     // 異なる型を格納（列挙型を使用）
     #[derive(Debug)]
@@ -59,29 +61,30 @@ pub fn vectors() {
         SpreadsheetCell::Float(10.12),
     ];
     
-    println!("スプレッドシートの行: {:?}", row);
+    writeln!(out, "スプレッドシートの行: {:?}", row)?;
     
     // よく使うメソッド
     let mut v = vec![1, 2, 3, 4, 5];
     
     v.pop();  // 最後の要素を削除
-    println!("pop後: {:?}", v);
+    writeln!(out, "pop後: {:?}", v)?;
     
     v.insert(2, 10);  // 指定位置に挿入
-    println!("insert後: {:?}", v);
+    writeln!(out, "insert後: {:?}", v)?;
     
     v.remove(1);  // 指定位置の要素を削除
-    println!("remove後: {:?}", v);
+    writeln!(out, "remove後: {:?}", v)?;
     
     v.retain(|&x| x % 2 == 0);  // 条件を満たす要素のみ保持
-    println!("偶数のみ: {:?}", v);
+    writeln!(out, "偶数のみ: {:?}", v)?;
     
     v.clear();  // 全要素を削除
-    println!("clear後: {:?}", v);
+    writeln!(out, "clear後: {:?}", v)?;
+    Ok(())
 }
 
 // 2. 文字列（String）
-pub fn strings() {
+pub fn strings(out: &mut dyn Write) -> io::Result<()> {
     // 文字列の作成
     let mut _s = String::new();
     
@@ -100,24 +103,24 @@ pub fn strings() {
     // 文字列の更新
     let mut s = String::from("foo");
     s.push_str("bar");
-    println!("push_str: {}", s);
+    writeln!(out, "push_str: {}", s)?;
     
     let mut s = String::from("lo");
     s.push('l');  // 1文字を追加
-    println!("push: {}", s);
+    writeln!(out, "push: {}", s)?;
     
     // 文字列の結合
     let s1 = String::from("Hello, ");
     let s2 = String::from("world!");
     let s3 = s1 + &s2;  // s1は移動される
-    println!("結合: {}", s3);
+    writeln!(out, "結合: {}", s3)?;
     
     // format!マクロ
     let s1 = String::from("tic");
     let s2 = String::from("tac");
     let s3 = String::from("toe");
     let s = format!("{}-{}-{}", s1, s2, s3);
-    println!("format!: {}", s);
+    writeln!(out, "format!: {}", s)?;
     
     // 文字列のインデックス（注意が必要）
     let _s = String::from("こんにちは");
@@ -126,58 +129,61 @@ pub fn strings() {
     // 文字列のスライス（注意して使用）
     let hello = "Здравствуйте";
     let s = &hello[0..4];  // 最初の4バイト
-    println!("スライス: {}", s);
+    writeln!(out, "スライス: {}", s)?;
     
     // 文字列の反復処理
     for c in "नमस्ते".chars() {
-        println!("文字: {}", c);
+        writeln!(out, "文字: {}", c)?;
     }
     
     for b in "नमस्ते".bytes() {
-        println!("バイト: {}", b);
+        writeln!(out, "バイト: {}", b)?;
     }
     
     // 文字列の分割
     let text = "apple,banana,orange";
     let fruits: Vec<&str> = text.split(',').collect();
-    println!("フルーツ: {:?}", fruits);
+    writeln!(out, "フルーツ: {:?}", fruits)?;
     
     // 文字列の検索と置換
     let s = String::from("I like rust");
-    println!("含む？ {}", s.contains("rust"));
-    println!("置換: {}", s.replace("rust", "Rust"));
+    writeln!(out, "含む？ {}", s.contains("rust"))?;
+    writeln!(out, "置換: {}", s.replace("rust", "Rust"))?;
     
     // トリミング
     let s = String::from("  hello  ");
-    println!("トリム前: '{}'", s);
-    println!("トリム後: '{}'", s.trim());
+    writeln!(out, "トリム前: '{}'", s)?;
+    writeln!(out, "トリム後: '{}'", s.trim())?;
+    Ok(())
 }
 
 // 3. ハッシュマップ（HashMap<K, V>）
-pub fn hash_maps() {
+pub fn hash_maps(out: &mut dyn Write) -> io::Result<()> {
     // ハッシュマップの作成
     let mut scores = HashMap::new();
     
     scores.insert(String::from("Blue"), 10);
     scores.insert(String::from("Yellow"), 50);
     
-    println!("スコア: {:?}", scores);
+    writeln!(out, "スコア: {:?}", scores)?;
     
     // タプルのベクタから作成
     let teams = vec![String::from("Blue"), String::from("Yellow")];
     let initial_scores = vec![10, 50];
     
     let scores: HashMap<_, _> = teams.into_iter().zip(initial_scores.into_iter()).collect();
-    println!("zipで作成: {:?}", scores);
+    writeln!(out, "zipで作成: {:?}", scores)?;
     
     // 値へのアクセス
     let team_name = String::from("Blue");
     let score = scores.get(&team_name);
-    println!("Blueのスコア: {:?}", score);
+    writeln!(out, "Blueのスコア: {:?}", score)?;
     
-    // イテレーション
-    for (key, value) in &scores {
-        println!("{}: {}", key, value);
+    // イテレーション（HashMapの順序は実行ごとに変わるため、キーで並べ替えて表示する）
+    let mut entries: Vec<_> = scores.iter().collect();
+    entries.sort();
+    for (key, value) in entries {
+        writeln!(out, "{}: {}", key, value)?;
     }
     
     // 値の更新
@@ -186,12 +192,12 @@ pub fn hash_maps() {
     
     // 上書き
     scores.insert(String::from("Blue"), 25);
-    println!("上書き後: {:?}", scores);
+    writeln!(out, "上書き後: {:?}", scores)?;
     
     // エントリーAPI
     scores.entry(String::from("Yellow")).or_insert(50);
     scores.entry(String::from("Blue")).or_insert(50);
-    println!("or_insert後: {:?}", scores);
+    writeln!(out, "or_insert後: {:?}", scores)?;
     
     // 古い値に基づいて更新
     let text = "hello world wonderful world";
@@ -202,7 +208,7 @@ pub fn hash_maps() {
         *count += 1;
     }
     
-    println!("単語カウント: {:?}", map);
+    writeln!(out, "単語カウント: {:?}", map)?;
     
     // よく使うメソッド
     let mut scores = HashMap::new();
@@ -211,26 +217,27 @@ pub fn hash_maps() {
     scores.insert("Charlie", 90);
     
     // キーの存在確認
-    println!("Aliceは存在？ {}", scores.contains_key("Alice"));
+    writeln!(out, "Aliceは存在？ {}", scores.contains_key("Alice"))?;
     
     // 削除
     scores.remove("Bob");
-    println!("削除後: {:?}", scores);
+    writeln!(out, "削除後: {:?}", scores)?;
     
     // 条件付き削除
     scores.retain(|_, &mut v| v >= 90);
-    println!("90点以上のみ: {:?}", scores);
+    writeln!(out, "90点以上のみ: {:?}", scores)?;
+    Ok(())
 }
 
 // 4. その他のコレクション
-pub fn other_collections() {
+pub fn other_collections(out: &mut dyn Write) -> io::Result<()> {
     // HashSet（重複なしの集合）
     let mut books = HashSet::new();
     books.insert("プログラミングRust");
     books.insert("実践Rust入門");
     books.insert("プログラミングRust");  // 重複は無視される
     
-    println!("書籍セット: {:?}", books);
+    writeln!(out, "書籍セット: {:?}", books)?;
     
     // 集合演算
     let a: HashSet<i32> = vec![1, 2, 3].into_iter().collect();
@@ -240,9 +247,9 @@ pub fn other_collections() {
     let intersection: HashSet<_> = a.intersection(&b).collect();
     let difference: HashSet<_> = a.difference(&b).collect();
     
-    println!("和集合: {:?}", union);
-    println!("積集合: {:?}", intersection);
-    println!("差集合: {:?}", difference);
+    writeln!(out, "和集合: {:?}", union)?;
+    writeln!(out, "積集合: {:?}", intersection)?;
+    writeln!(out, "差集合: {:?}", difference)?;
     
     // VecDeque（両端キュー）
     let mut deque = VecDeque::new();
@@ -251,41 +258,42 @@ pub fn other_collections() {
     deque.push_back(2);
     deque.push_front(0);
     
-    println!("VecDeque: {:?}", deque);
+    writeln!(out, "VecDeque: {:?}", deque)?;
     
-    println!("前から取り出し: {:?}", deque.pop_front());
-    println!("後ろから取り出し: {:?}", deque.pop_back());
-    println!("残り: {:?}", deque);
+    writeln!(out, "前から取り出し: {:?}", deque.pop_front())?;
+    writeln!(out, "後ろから取り出し: {:?}", deque.pop_back())?;
+    writeln!(out, "残り: {:?}", deque)?;
+    Ok(())
 }
 
 // 5. イテレータとクロージャ
-pub fn iterators_and_closures() {
+pub fn iterators_and_closures(out: &mut dyn Write) -> io::Result<()> {
     let v1 = vec![1, 2, 3];
     let v1_iter = v1.iter();
     
     for val in v1_iter {
-        println!("値: {}", val);
+        writeln!(out, "値: {}", val)?;
     }
     
     // map
     let v1: Vec<i32> = vec![1, 2, 3];
     let v2: Vec<_> = v1.iter().map(|x| x + 1).collect();
-    println!("map: {:?}", v2);
+    writeln!(out, "map: {:?}", v2)?;
     
     // filter
     let v1 = vec![1, 2, 3, 4, 5, 6];
     let evens: Vec<_> = v1.iter().filter(|&&x| x % 2 == 0).collect();
-    println!("偶数: {:?}", evens);
+    writeln!(out, "偶数: {:?}", evens)?;
     
     // fold（畳み込み）
     let sum: i32 = vec![1, 2, 3, 4, 5].iter().fold(0, |acc, x| acc + x);
-    println!("合計: {}", sum);
+    writeln!(out, "合計: {}", sum)?;
     
     // クロージャ
     let x = 4;
     let equal_to_x = |z| z == x;  // xをキャプチャ
     let y = 4;
-    println!("equal_to_x(y): {}", equal_to_x(y));
+    writeln!(out, "equal_to_x(y): {}", equal_to_x(y))?;
     
     // メソッドチェーン
     let results: Vec<_> = vec![1, 2, 3, 4, 5, 6, 7, 8, 9, 10]
@@ -294,7 +302,8 @@ pub fn iterators_and_closures() {
         .map(|x| x * x)
         .take(3)
         .collect();
-    println!("最初の3つの偶数の二乗: {:?}", results);
+    writeln!(out, "最初の3つの偶数の二乗: {:?}", results)?;
+    Ok(())
 }
 
 // 6. 実践的な例：学生の成績管理システム
@@ -305,20 +314,20 @@ struct Student {
     grades: HashMap<String, f64>,
 }
 
-struct GradeBook {
+pub struct GradeBook {
     students: HashMap<u32, Student>,
     courses: HashSet<String>,
 }
 
 impl GradeBook {
-    fn new() -> Self {
+    pub fn new() -> Self {
         GradeBook {
             students: HashMap::new(),
             courses: HashSet::new(),
         }
     }
     
-    fn add_student(&mut self, name: String, id: u32) {
+    pub fn add_student(&mut self, name: String, id: u32) {
         let student = Student {
             name,
             id,
@@ -327,11 +336,11 @@ impl GradeBook {
         self.students.insert(id, student);
     }
     
-    fn add_course(&mut self, course: String) {
+    pub fn add_course(&mut self, course: String) {
         self.courses.insert(course);
     }
     
-    fn add_grade(&mut self, student_id: u32, course: String, grade: f64) {
+    pub fn add_grade(&mut self, student_id: u32, course: String, grade: f64) {
         if let Some(student) = self.students.get_mut(&student_id) {
             if self.courses.contains(&course) {
                 student.grades.insert(course, grade);
@@ -339,7 +348,7 @@ impl GradeBook {
         }
     }
     
-    fn get_average_grade(&self, student_id: u32) -> Option<f64> {
+    pub fn get_average_grade(&self, student_id: u32) -> Option<f64> {
         self.students.get(&student_id).map(|student| {
             if student.grades.is_empty() {
                 0.0
@@ -350,7 +359,7 @@ impl GradeBook {
         })
     }
     
-    fn get_top_students(&self, n: usize) -> Vec<(&str, f64)> {
+    pub fn get_top_students(&self, n: usize) -> Vec<(&str, f64)> {
         let mut student_avgs: Vec<_> = self.students
             .values()
             .filter_map(|student| {
@@ -364,7 +373,7 @@ impl GradeBook {
     }
 }
 
-pub fn gradebook_example() {
+pub fn gradebook_example(out: &mut dyn Write) -> io::Result<()> {
     let mut gradebook = GradeBook::new();
     
     // コースを追加
@@ -390,37 +399,118 @@ pub fn gradebook_example() {
     gradebook.add_grade(1003, "物理".to_string(), 82.0);
     gradebook.add_grade(1003, "化学".to_string(), 80.0);
     
-    // 平均点を表示
-    for (id, student) in &gradebook.students {
+    // 平均点を表示（学籍番号の順）
+    let mut ids: Vec<_> = gradebook.students.keys().copied().collect();
+    ids.sort();
+    for id in &ids {
+        let student = &gradebook.students[id];
         if let Some(avg) = gradebook.get_average_grade(*id) {
-            println!("{} の平均点: {:.2}", student.name, avg);
+            writeln!(out, "{} の平均点: {:.2}", student.name, avg)?;
         }
     }
     
     // トップ学生を表示
-    println!("\nトップ2の学生:");
+    writeln!(out, "\nトップ2の学生:")?;
     for (name, avg) in gradebook.get_top_students(2) {
-        println!("  {} - 平均点: {:.2}", name, avg);
+        writeln!(out, "  {} - 平均点: {:.2}", name, avg)?;
     }
+    Ok(())
 }
 
+// デモの一覧（実行順）
+pub static DEMOS: &[Demo] = &[
+    Demo { name: "vectors", title: "ベクタ", title_en: "Vectors", run: vectors },
+    Demo { name: "strings", title: "文字列", title_en: "Strings", run: strings },
+    Demo { name: "hash_maps", title: "ハッシュマップ", title_en: "Hash Maps", run: hash_maps },
+    Demo { name: "other_collections", title: "その他のコレクション", title_en: "Other Collections", run: other_collections },
+    Demo { name: "iterators_and_closures", title: "イテレータとクロージャ", title_en: "Iterators and Closures", run: iterators_and_closures },
+    Demo { name: "gradebook_example", title: "成績管理システムの例", title_en: "Example: Grade Book", run: gradebook_example },
+];
+
 // メインの実行関数
-pub fn run_all_collections() {
-    println!("\n=== ベクタ ===");
-    vectors();
-    
-    println!("\n=== 文字列 ===");
-    strings();
-    
-    println!("\n=== ハッシュマップ ===");
-    hash_maps();
-    
-    println!("\n=== その他のコレクション ===");
-    other_collections();
-    
-    println!("\n=== イテレータとクロージャ ===");
-    iterators_and_closures();
-    
-    println!("\n=== 成績管理システムの例 ===");
-    gradebook_example();
-}
\ No newline at end of file
+pub fn run_all_collections(out: &mut dyn Write) -> io::Result<()> {
+    section::run_demos(DEMOS, out)
+}
+
+// セクションの登録
+pub struct CollectionsSection;
+
+impl Section for CollectionsSection {
+    fn id(&self) -> &'static str {
+        "collections"
+    }
+
+    fn title_ja(&self) -> &'static str {
+        "コレクション"
+    }
+
+    fn title_en(&self) -> &'static str {
+        "Collections"
+    }
+
+    fn topics(&self) -> &'static str {
+        "Vec、String、HashMap"
+    }
+
+    fn topics_en(&self) -> &'static str {
+        "Vec, String, HashMap"
+    }
+
+    fn run(&self, out: &mut dyn Write) -> io::Result<()> {
+        run_all_collections(out)
+    }
+
+    fn demos(&self) -> &'static [Demo] {
+        DEMOS
+    }
+
+    fn source(&self) -> SourceFile {
+        SourceFile {
+            path: "collections/mod.rs",
+            content: include_str!("mod.rs"),
+        }
+    }
+
+    fn doc_pages(&self) -> &'static [DocPage] {
+        &[DocPage {
+            name: "readme",
+            path: "collections/README.md",
+            description: "コレクションの詳細",
+            content: include_str!("README.md"),
+            path_en: "collections/README.en.md",
+            description_en: "Details of collections",
+            content_en: include_str!("README.en.md"),
+        }]
+    }
+
+    fn quiz(&self) -> SourceFile {
+        SourceFile {
+            path: "collections/quiz.md",
+            content: include_str!("quiz.md"),
+        }
+    }
+
+    fn cards(&self) -> SourceFile {
+        SourceFile {
+            path: "collections/cards.md",
+            content: include_str!("cards.md"),
+        }
+    }
+
+    fn exercises(&self) -> &'static [Exercise] {
+        &[
+            Exercise {
+                name: "word_count",
+                path: "collections/exercises/word_count.rs",
+                hint: "`*map.entry(word).or_insert(0) += 1;`で、初めての単語は0を挿入してから1を足せます。",
+                content: include_str!("exercises/word_count.rs"),
+            },
+            Exercise {
+                name: "vector_stats",
+                path: "collections/exercises/vector_stats.rs",
+                hint: "`numbers.iter().filter(|n| *n % 2 == 0).map(|n| n * 2).collect()`のように書けます。",
+                content: include_str!("exercises/vector_stats.rs"),
+            },
+        ]
+    }
+}
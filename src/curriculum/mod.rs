@@ -0,0 +1,324 @@
+// 学習の順序（カリキュラム）
+// `curriculum.txt`に書いたセクション・デモの前提と記録された進捗から、
+// `path`で学習の順序と現在の位置を、`next`で次に学習するデモを表示します
+
+use crate::doc;
+use crate::markdown;
+use crate::markdown::{terminal, terminal::Style};
+use crate::progress::{self, Progress};
+use crate::section::{self, Demo, Section};
+
+const CURRICULUM: &str = include_str!("curriculum.txt");
+
+// `next`で次のおすすめと一緒に表示する、他に学習できるデモの数
+const OTHER_TOPICS: usize = 3;
+
+// 1. トピック（デモ）と前提
+pub struct Topic {
+    pub section: &'static dyn Section,
+    pub section_index: usize,  // セクションの一覧（`section::all()`）での位置
+    pub demo: &'static Demo,
+    pub requires: Vec<usize>,  // `curriculum.txt`に個別に書かれた前提（トピックの番号）
+}
+
+impl Topic {
+    pub fn id(&self) -> String {
+        format!("{}/{}", self.section.id(), self.demo.name)
+    }
+}
+
+// トピックはセクションの一覧の順、セクション内ではデモの順に並ぶ
+// 前提はすべてそれより前にあるので、この順に学習すれば前提を満たせる
+pub struct Curriculum {
+    pub topics: Vec<Topic>,
+    pub section_requires: Vec<Vec<usize>>,  // セクションごとの前提のセクション（`section::all()`での位置）
+}
+
+impl Curriculum {
+    pub fn load() -> Result<Curriculum, String> {
+        Curriculum::parse(CURRICULUM)
+    }
+
+    pub fn parse(text: &str) -> Result<Curriculum, String> {
+        let sections = section::all();
+        let mut topics: Vec<Topic> = Vec::new();
+        for (section_index, section) in sections.iter().enumerate() {
+            for demo in section.demos() {
+                topics.push(Topic { section: *section, section_index, demo, requires: Vec::new() });
+            }
+        }
+        let mut section_requires = vec![Vec::new(); sections.len()];
+
+        for (i, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let error = |name: &str| tr!("curriculum.invalid_line", i + 1, name);
+            let Some((target, requires)) = line.split_once("<-") else {
+                return Err(error(line));
+            };
+            let target = target.trim();
+
+            // 前提は対象より前にあるものだけ（循環しないことが保証される）
+            if target.contains('/') {
+                let index = find_topic(&topics, target).ok_or_else(|| error(target))?;
+                for name in requires.split_whitespace() {
+                    let required = find_topic(&topics, name).filter(|&r| r < index).ok_or_else(|| error(name))?;
+                    topics[index].requires.push(required);
+                }
+            } else {
+                let index = find_section(target).ok_or_else(|| error(target))?;
+                for name in requires.split_whitespace() {
+                    let required = find_section(name).filter(|&r| r < index).ok_or_else(|| error(name))?;
+                    section_requires[index].push(required);
+                }
+            }
+        }
+        Ok(Curriculum { topics, section_requires })
+    }
+
+    // トピックの直接の前提
+    // （同じセクションの1つ前のデモ、前提のセクションのすべてのデモ、個別に書かれた前提）
+    pub fn requirements(&self, index: usize) -> Vec<usize> {
+        let topic = &self.topics[index];
+        let mut required = Vec::new();
+        if index > 0 && self.topics[index - 1].section_index == topic.section_index {
+            required.push(index - 1);
+        }
+        for &section in &self.section_requires[topic.section_index] {
+            required.extend((0..self.topics.len()).filter(|&i| self.topics[i].section_index == section));
+        }
+        required.extend(&topic.requires);
+        required
+    }
+
+    // 2. 進捗に応じた状態
+    pub fn states(&self, progress: &Progress) -> Vec<State> {
+        let done: Vec<bool> = self
+            .topics
+            .iter()
+            .map(|topic| progress.demos.contains_key(&(topic.section.id().to_string(), topic.demo.name.to_string())))
+            .collect();
+        (0..self.topics.len())
+            .map(|i| {
+                if done[i] {
+                    State::Done
+                } else if self.requirements(i).iter().all(|&r| done[r]) {
+                    State::Available
+                } else {
+                    State::Locked
+                }
+            })
+            .collect()
+    }
+}
+
+fn find_topic(topics: &[Topic], id: &str) -> Option<usize> {
+    topics.iter().position(|topic| topic.id() == id)
+}
+
+fn find_section(id: &str) -> Option<usize> {
+    section::all().iter().position(|section| section.id() == id)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum State {
+    Done,       // デモを実行した
+    Available,  // 前提をすべて終えていて、学習できる
+    Locked,     // まだ終えていない前提がある
+}
+
+// 次に学習するデモ（一覧の順で最初の学習できるデモ）
+pub fn next(states: &[State]) -> Option<usize> {
+    states.iter().position(|&state| state == State::Available)
+}
+
+// 3. コマンド
+// `path`: セクションとデモを前提の関係とともに表示し、次のおすすめに印を付ける
+pub fn path_command() {
+    let Some((curriculum, states)) = load_with_progress() else {
+        return;
+    };
+    let style = Style::detect();
+    let recommended = next(&states);
+    let names: Vec<&str> = section::all().iter().map(|section| section.id()).collect();
+
+    println!("{}", tr!("curriculum.path_title"));
+    println!("{}", style.paint(terminal::DIM, tr!("curriculum.legend")));
+
+    for (section_index, section) in section::all().iter().enumerate() {
+        let indices: Vec<usize> = (0..curriculum.topics.len())
+            .filter(|&i| curriculum.topics[i].section_index == section_index)
+            .collect();
+        let done = indices.iter().filter(|&&i| states[i] == State::Done).count();
+        let section_state = if done == indices.len() {
+            State::Done
+        } else if indices.iter().any(|&i| states[i] == State::Available) {
+            State::Available
+        } else {
+            State::Locked
+        };
+        let contains_next = recommended.is_some_and(|r| indices.contains(&r));
+
+        let requires: Vec<&str> = curriculum.section_requires[section_index].iter().map(|&r| names[r]).collect();
+        let mut line = format!(
+            "{} {} - {} ({}/{})",
+            mark(section_state, contains_next, style),
+            style.paint(terminal::BOLD, section.id()),
+            section.title(),
+            done,
+            indices.len()
+        );
+        if !requires.is_empty() {
+            line.push_str(&format!("  {}", style.paint(terminal::DIM, &tr!("curriculum.requires", requires.join(", ")))));
+        }
+        println!("\n{}", line);
+
+        for (number, &i) in indices.iter().enumerate() {
+            let topic = &curriculum.topics[i];
+            let mut line = format!(
+                "    {} {:>2}. {:<30}{}",
+                mark(states[i], recommended == Some(i), style),
+                number + 1,
+                topic.demo.name,
+                topic.demo.heading()
+            );
+            if !topic.requires.is_empty() {
+                let ids: Vec<String> = topic.requires.iter().map(|&r| curriculum.topics[r].id()).collect();
+                line.push_str(&format!("  {}", style.paint(terminal::DIM, &tr!("curriculum.requires", ids.join(", ")))));
+            }
+            if recommended == Some(i) {
+                line.push_str(&format!("  {}", style.paint(terminal::YELLOW, tr!("curriculum.you_are_here"))));
+            }
+            println!("{}", line);
+        }
+    }
+    println!("\n{}", tr!("curriculum.next_hint"));
+}
+
+fn mark(state: State, recommended: bool, style: Style) -> String {
+    match state {
+        _ if recommended => style.paint(&format!("{}{}", terminal::BOLD, terminal::YELLOW), "▶"),
+        State::Done => style.paint(terminal::GREEN, "✓"),
+        State::Available => style.paint(terminal::CYAN, "○"),
+        State::Locked => style.paint(terminal::DIM, "·"),
+    }
+}
+
+// `next`: 次に学習するデモと、その実行方法・説明の場所を表示する
+pub fn next_command() {
+    let Some((curriculum, states)) = load_with_progress() else {
+        return;
+    };
+    let Some(index) = next(&states) else {
+        println!("{}", tr!("curriculum.all_done"));
+        return;
+    };
+
+    let topic = &curriculum.topics[index];
+    let (section, demo) = (topic.section, topic.demo);
+    println!("{}", tr!("curriculum.next_title", demo.heading(), section.title()));
+    println!("\n{}", tr!("curriculum.next_run"));
+    println!("  cargo run -- {} {} --step", section.id(), demo.name);
+    println!("  cargo run -- source {} {}", section.id(), demo.name);
+
+    // READMEに対応する見出しがあれば、説明を読むコマンドも表示する
+    let page = &section.doc_pages()[0];
+    let blocks = markdown::parse(page.localized_content());
+    if let Some(index) = doc::find_heading(page, &blocks, demo.title)
+        && let markdown::Block::Heading { text, .. } = &blocks[index]
+    {
+        println!("\n{}", tr!("curriculum.next_doc"));
+        println!("  cargo run -- doc {}#{}", section.id(), markdown::anchor(text));
+    }
+
+    let others: Vec<&Topic> = (index + 1..curriculum.topics.len())
+        .filter(|&i| states[i] == State::Available)
+        .take(OTHER_TOPICS)
+        .map(|i| &curriculum.topics[i])
+        .collect();
+    if !others.is_empty() {
+        println!("\n{}", tr!("curriculum.others"));
+        for other in others {
+            println!("  {:<40}{}", other.id(), other.demo.heading());
+        }
+    }
+}
+
+fn load_with_progress() -> Option<(Curriculum, Vec<State>)> {
+    let curriculum = match Curriculum::load() {
+        Ok(curriculum) => curriculum,
+        Err(e) => {
+            println!("{}", e);
+            return None;
+        }
+    };
+    let progress = progress::load_saved()?;
+    let states = curriculum.states(&progress);
+    Some((curriculum, states))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn id(curriculum: &Curriculum, index: usize) -> String {
+        curriculum.topics[index].id()
+    }
+
+    // 前提がすべて存在し、それより前にあること
+    #[test]
+    fn embedded_curriculum_is_valid() {
+        let curriculum = Curriculum::load().unwrap();
+        for i in 0..curriculum.topics.len() {
+            assert!(curriculum.requirements(i).iter().all(|&r| r < i), "{}", id(&curriculum, i));
+        }
+        let ownership = find_section("ownership").unwrap();
+        assert_eq!(curriculum.section_requires[ownership], vec![find_section("basics").unwrap()]);
+    }
+
+    #[test]
+    fn rejects_unknown_and_forward_requirements() {
+        assert!(Curriculum::parse("ownership <- no_such_section").is_err());
+        assert!(Curriculum::parse("basics <- ownership").is_err());
+        assert!(Curriculum::parse("basics/control_flow <- ownership/slices").is_err());
+        assert!(Curriculum::parse("ownership basics").is_err());
+        assert!(Curriculum::parse("# コメント\n\nownership/slices <- basics/control_flow").is_ok());
+    }
+
+    #[test]
+    fn recommends_first_unlocked_topic() {
+        let curriculum = Curriculum::load().unwrap();
+        let mut progress = Progress::default();
+        let states = curriculum.states(&progress);
+        assert_eq!(next(&states).map(|i| id(&curriculum, i)), Some("basics/variables_and_mutability".to_string()));
+
+        // 順番を飛ばして実行したデモは修了になるが、前提の続きが先に勧められる
+        progress.record_demo("ownership", "slices", 0);
+        for demo in section::find("basics").unwrap().demos().iter().take(2) {
+            progress.record_demo("basics", demo.name, 0);
+        }
+        let states = curriculum.states(&progress);
+        assert_eq!(next(&states).map(|i| id(&curriculum, i)), Some("basics/functions_demo".to_string()));
+        let slices = find_topic(&curriculum.topics, "ownership/slices").unwrap();
+        assert_eq!(states[slices], State::Done);
+        assert_eq!(states[slices + 1], State::Locked);
+
+        // basicsをすべて終えると、ownershipの最初のデモが学習できる
+        for demo in section::find("basics").unwrap().demos() {
+            progress.record_demo("basics", demo.name, 0);
+        }
+        let states = curriculum.states(&progress);
+        assert_eq!(next(&states).map(|i| id(&curriculum, i)), Some("ownership/ownership_basics".to_string()));
+    }
+
+    #[test]
+    fn explicit_requirements_cross_sections() {
+        let curriculum = Curriculum::load().unwrap();
+        let lifetimes = find_topic(&curriculum.topics, "generics/lifetimes_example").unwrap();
+        let required: Vec<String> = curriculum.topics[lifetimes].requires.iter().map(|&r| id(&curriculum, r)).collect();
+        assert_eq!(required, ["ownership/references_and_borrowing", "ownership/slices"]);
+        assert!(curriculum.requirements(lifetimes).contains(&(lifetimes - 1)));
+    }
+}
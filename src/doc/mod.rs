@@ -0,0 +1,339 @@
+// ドキュメントの表示
+// `doc [セクション][#見出し] [ページ][#見出し]`の形式で、ページや見出しを指定して表示します
+// `--split`を付けると、見出しの節と対応するデモの関数を左右に並べて表示します
+
+use crate::glossary;
+use crate::i18n::{self, Lang};
+use crate::markdown::{self, terminal, terminal::Style, Block};
+use crate::progress;
+use crate::section::{self, DocPage, Section};
+use crate::source;
+use std::ops::Range;
+use std::path::Path;
+
+mod verify;
+
+// 1. 表示する対象
+#[derive(Debug, PartialEq)]
+struct Target<'a> {
+    section: &'a str,
+    page: Option<&'a str>,
+    anchor: Option<&'a str>,
+}
+
+// `basics#制御フロー`や`ownership stack-heap#まとめ`を分解する
+fn parse_target<'a>(section: &'a str, page: Option<&'a str>) -> Target<'a> {
+    let (section, section_anchor) = split_anchor(section);
+    let (page, page_anchor) = match page.map(split_anchor) {
+        Some((page, anchor)) => (Some(page).filter(|p| !p.is_empty()), anchor),
+        None => (None, None),
+    };
+    Target { section, page, anchor: page_anchor.or(section_anchor) }
+}
+
+fn split_anchor(arg: &str) -> (&str, Option<&str>) {
+    match arg.split_once('#') {
+        Some((name, anchor)) => (name, Some(anchor).filter(|a| !a.is_empty())),
+        None => (arg, None),
+    }
+}
+
+// 2. コマンドの実行
+pub fn command(args: &[String], docs_dir: Option<&Path>) {
+    if args.first().is_some_and(|arg| arg == "verify") {
+        verify::command(&args[1..], docs_dir);
+        return;
+    }
+
+    let toc_only = args.iter().any(|arg| arg == "--toc");
+    let split = args.iter().any(|arg| arg == "--split");
+    let args: Vec<&str> = args
+        .iter()
+        .map(String::as_str)
+        .filter(|arg| *arg != "--toc" && *arg != "--split")
+        .collect();
+
+    let Some(&first) = args.first() else {
+        print_help();
+        return;
+    };
+    let target = parse_target(first, args.get(1).copied());
+
+    let Some(section) = section::find(target.section) else {
+        println!("{}", tr!("common.unknown_section", target.section));
+        print_help();
+        return;
+    };
+
+    // ページが指定されていなければ、見出しはすべてのページから探す
+    let pages: Vec<&DocPage> = match target.page {
+        Some(name) => match section.doc_page(name) {
+            Some(page) => vec![page],
+            None => {
+                println!("{}", tr!("doc.unknown_page", section.id(), name));
+                print_pages(section);
+                return;
+            }
+        },
+        None if target.anchor.is_some() => section.doc_pages().iter().collect(),
+        None => vec![&section.doc_pages()[0]],
+    };
+
+    let mut loaded = Vec::new();
+    for page in pages {
+        match page.load(docs_dir) {
+            Ok(content) => loaded.push((page, markdown::parse(&content))),
+            Err(e) => {
+                let doc_path = docs_dir.unwrap_or(Path::new("")).join(page.localized_path());
+                println!("{}", tr!("doc.load_error", e, doc_path.display()));
+                return;
+            }
+        }
+    }
+
+    let style = Style::detect();
+    let shown = match target.anchor {
+        _ if split => show_split(section, &loaded, target.anchor, style),
+        Some(anchor) => show_heading(section, &loaded, anchor, style),
+        None => {
+            let (page, blocks) = &loaded[0];
+            show_page(section, page, blocks, toc_only, style);
+            Some(*page).filter(|_| !toc_only)
+        }
+    };
+    if let Some(page) = shown {
+        progress::record_doc(section, page);
+    }
+}
+
+fn show_page(section: &dyn Section, page: &DocPage, blocks: &[Block], toc_only: bool, style: Style) {
+    println!();
+    println!("{}", style.paint(terminal::DIM, tr!("doc.toc")));
+    print!("{}", terminal::render_toc(&markdown::toc(blocks), style));
+
+    if !toc_only {
+        print_annotated(blocks, style);
+    }
+
+    let others: Vec<&DocPage> = section
+        .doc_pages()
+        .iter()
+        .filter(|other| other.name != page.name)
+        .collect();
+    if !others.is_empty() {
+        println!("\n{}", style.paint(terminal::DIM, tr!("doc.other_pages")));
+        for other in others {
+            println!("  doc {} {:<12}- {}", section.id(), other.name, other.localized_description());
+        }
+    }
+}
+
+// 見出しが見つかったら、そのページを返す
+fn show_heading<'a>(
+    section: &dyn Section,
+    pages: &[(&'a DocPage, Vec<Block>)],
+    anchor: &str,
+    style: Style,
+) -> Option<&'a DocPage> {
+    for (page, blocks) in pages {
+        if let Some(index) = find_heading(page, blocks, anchor) {
+            print_annotated(markdown::heading_section(blocks, index), style);
+            return Some(page);
+        }
+    }
+
+    println!("{}", tr!("doc.heading_not_found", section.id(), anchor));
+    for (page, blocks) in pages {
+        println!("\n{} ({})", page.localized_description(), page.name);
+        print!("{}", terminal::render_toc(&markdown::toc(blocks), style));
+    }
+    None
+}
+
+// 用語集の用語を強調して表示し、最後に出てきた用語を一覧にする
+fn print_annotated(blocks: &[Block], style: Style) {
+    let terms = glossary::load().unwrap_or_default();
+    let (blocks, found) = glossary::annotate(blocks, &terms, i18n::lang());
+    print!("\n{}", terminal::render_blocks(&blocks, style));
+    glossary::print_terms(&found, style);
+}
+
+// 見出しの節（見出しがなければページ全体）にあるデモを、関数のソースコードと並べて表示する
+fn show_split<'a>(
+    section: &dyn Section,
+    pages: &[(&'a DocPage, Vec<Block>)],
+    anchor: Option<&str>,
+    style: Style,
+) -> Option<&'a DocPage> {
+    let found = match anchor {
+        Some(anchor) => pages.iter().find_map(|(page, blocks)| {
+            let index = find_heading(page, blocks, anchor)?;
+            Some((*page, blocks, index..index + markdown::heading_section(blocks, index).len()))
+        }),
+        None => pages.first().map(|(page, blocks)| (*page, blocks, 0..blocks.len())),
+    };
+    let Some((page, blocks, range)) = found else {
+        // 見つからなかったことと、各ページの目次を表示する
+        return show_heading(section, pages, anchor.unwrap_or_default(), style);
+    };
+
+    let source = section.source();
+    let width = terminal::width();
+    let mut shown = 0;
+    for (demo, left) in split_pairs(section, page, blocks, range.clone()) {
+        let Some(function) = source::find_function(source.content, demo.name) else {
+            continue;
+        };
+        let location = format!("src/{}:{}", source.path, function.first_line);
+        let mut right = vec![style.paint(terminal::DIM, &location)];
+        right.extend(terminal::render_source(function.code, function.first_line, style));
+        let left: Vec<String> = terminal::render_blocks(&blocks[left], style).lines().map(str::to_string).collect();
+
+        println!();
+        for line in terminal::side_by_side(&left, &right, width, style) {
+            println!("{}", line);
+        }
+        shown += 1;
+    }
+
+    if shown == 0 {
+        println!("{}", tr!("doc.split_no_demo", section.id()));
+        print!("\n{}", terminal::render_blocks(&blocks[range], style));
+    }
+    Some(page)
+}
+
+// デモとその左側に表示するブロックの範囲の組
+// デモの見出しが範囲内にあればその節を、範囲がデモの節の一部ならその範囲を表示する
+fn split_pairs(
+    section: &dyn Section,
+    page: &DocPage,
+    blocks: &[Block],
+    range: Range<usize>,
+) -> Vec<(&'static section::Demo, Range<usize>)> {
+    section
+        .demos()
+        .iter()
+        .filter_map(|demo| {
+            let index = find_heading(page, blocks, demo.title)?;
+            let demo_range = index..index + markdown::heading_section(blocks, index).len();
+            if range.contains(&index) {
+                Some((demo, demo_range))
+            } else if demo_range.contains(&range.start) {
+                Some((demo, range.clone()))
+            } else {
+                None
+            }
+        })
+        .collect()
+}
+
+// 英語で表示しているときは、日本語の見出し（クイズの参照など）でも探せるようにする
+// 英語版は日本語版と同じ順に見出しが並んでいるので、何番目の見出しかで対応させる
+pub(crate) fn find_heading(page: &DocPage, blocks: &[Block], anchor: &str) -> Option<usize> {
+    if let Some(index) = markdown::find_heading(blocks, anchor) {
+        return Some(index);
+    }
+    if i18n::lang() == Lang::Ja {
+        return None;
+    }
+    let original = markdown::parse(page.content);
+    let index = markdown::find_heading(&original, anchor)?;
+    let nth = original[..index].iter().filter(|block| matches!(block, Block::Heading { .. })).count();
+    blocks
+        .iter()
+        .enumerate()
+        .filter(|(_, block)| matches!(block, Block::Heading { .. }))
+        .nth(nth)
+        .map(|(i, _)| i)
+}
+
+// 3. ページの一覧
+pub fn print_help() {
+    println!("{}", tr!("doc.title"));
+    println!("\n{}", tr!("doc.usage"));
+    println!("        {}", tr!("doc.usage_anchor"));
+    println!("        {}", tr!("doc.usage_verify"));
+    println!("\n{}", tr!("doc.available"));
+    for section in section::all() {
+        let pages = section.doc_pages();
+        println!("  {:<13}- {} ({})", section.id(), pages[0].localized_description(), section.title_en());
+        for page in &pages[1..] {
+            println!("  {:<13}  {:<12}- {}", "", page.name, page.localized_description());
+        }
+    }
+    println!("\n{}", tr!("help.options"));
+    println!("  {}", tr!("doc.opt.toc"));
+    println!("  {}", tr!("doc.opt.split"));
+    println!("\n{}", tr!("help.examples"));
+    println!("  {}", tr!("doc.ex.page"));
+    println!("  {}", tr!("doc.ex.anchor"));
+    println!("  {}", tr!("doc.ex.split"));
+    println!("  {}", tr!("doc.ex.verify"));
+}
+
+fn print_pages(section: &dyn Section) {
+    println!("{}", tr!("doc.pages", section.id()));
+    for page in section.doc_pages() {
+        println!("  {:<12}- {}", page.name, page.localized_description());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_page_and_anchor() {
+        let target = |section, page| parse_target(section, page);
+        assert_eq!(
+            target("basics#制御フロー", None),
+            Target { section: "basics", page: None, anchor: Some("制御フロー") }
+        );
+        assert_eq!(
+            target("ownership", Some("stack-heap#まとめ")),
+            Target { section: "ownership", page: Some("stack-heap"), anchor: Some("まとめ") }
+        );
+        assert_eq!(
+            target("ownership#", Some("#まとめ")),
+            Target { section: "ownership", page: None, anchor: Some("まとめ") }
+        );
+    }
+
+    // デモの見出しに対応する節と並べること
+    #[test]
+    fn demos_pair_with_headings() {
+        let ownership = section::find("ownership").unwrap();
+        let page = &ownership.doc_pages()[0];
+        let blocks = markdown::parse(page.content);
+        let pairs = split_pairs(ownership, page, &blocks, 0..blocks.len());
+        let names: Vec<&str> = pairs.iter().map(|(demo, _)| demo.name).collect();
+        assert!(names.contains(&"slices"), "{:?}", names);
+        assert!(names.contains(&"references_and_borrowing"), "{:?}", names);
+
+        // 小見出しを指定した場合は、それを含む節のデモと並べる
+        let index = find_heading(page, &blocks, "文字列スライス").unwrap();
+        let pairs = split_pairs(ownership, page, &blocks, index..index + 1);
+        assert_eq!(pairs.len(), 1);
+        assert_eq!((pairs[0].0.name, pairs[0].1.start), ("slices", index));
+    }
+
+    // 各ページの目次にある見出しは、すべてアンカーで移動できること
+    #[test]
+    fn every_toc_entry_is_reachable() {
+        for section in section::all() {
+            for page in section.doc_pages() {
+                let blocks = markdown::parse(page.content);
+                for entry in markdown::toc(&blocks) {
+                    let index = markdown::find_heading(&blocks, &markdown::anchor(&entry.text));
+                    assert!(
+                        matches!(blocks[index.unwrap()], Block::Heading { line, .. } if line == entry.line),
+                        "{}: {}",
+                        page.path,
+                        entry.text
+                    );
+                }
+            }
+        }
+    }
+}
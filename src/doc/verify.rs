@@ -0,0 +1,339 @@
+// ドキュメントのコード例の検査
+// `doc verify [セクション]`で、READMEの```rustのコードブロックを手元の`rustc`でコンパイルして確認します。
+//
+// rustdocのドキュメンテーションテストと同じく、`fn main`がなければ全体を`fn main`で包み、
+// コンパイルできたものは実行します。コードブロックの言語名の後ろに書いた指定に従います:
+//
+//   ```rust,ignore        検査しない（外部クレートを使うものや、わざと省略した断片など）
+//   ```rust,no_run        コンパイルだけ確認する（ファイルやネットワークを使うものなど）
+//   ```rust,compile_fail  コンパイルエラーになることを確認する
+//   ```rust,should_panic  実行するとpanicすることを確認する
+//
+// `ignore`には理由を添えます（例: ```rust,ignore (anyhowクレートを使う)）。
+// 足りない`use`や前のコード例の定義は、`# `で始まる隠し行に書くと表示せずにコンパイルできます。
+
+use crate::exercise;
+use crate::markdown::{self, terminal, terminal::Style, Block};
+use crate::section::{self, Section};
+use std::env;
+use std::fs;
+use std::io::{self, Read, Write};
+use std::path::Path;
+use std::process::{Command, Stdio};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::thread;
+use std::time::{Duration, Instant};
+
+// 実行が終わらないコード例（無限ループなど）を打ち切るまでの時間
+const TIMEOUT: Duration = Duration::from_secs(10);
+
+// 1. 検査するコードブロック
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Mode {
+    Run,          // コンパイルして実行し、正常に終わること
+    NoRun,        // コンパイルできること
+    CompileFail,  // コンパイルエラーになること
+    ShouldPanic,  // 実行するとpanicすること
+}
+
+#[derive(Debug)]
+pub struct Snippet {
+    pub path: String,  // `src`ディレクトリからの相対パス
+    pub line: usize,   // ```の行番号
+    pub code: String,
+    pub mode: Mode,
+}
+
+// コードブロックの指定（`rust,ignore`など）を読む
+// Rustのコードでなければ`None`、`ignore`なら`Some(None)`を返す
+pub fn parse_info(info: &str) -> Option<Option<Mode>> {
+    let mut words = info.split([',', ' ']).map(str::trim).filter(|word| !word.is_empty());
+    if words.next()? != "rust" {
+        return None;
+    }
+    let mut mode = Mode::Run;
+    for word in words {
+        match word {
+            "ignore" => return Some(None),
+            "no_run" => mode = Mode::NoRun,
+            "compile_fail" => mode = Mode::CompileFail,
+            "should_panic" => mode = Mode::ShouldPanic,
+            _ => {}
+        }
+    }
+    Some(Some(mode))
+}
+
+// ページ内のコードブロックを取り出す（`ignore`の数も返す）
+pub fn snippets(path: &str, content: &str) -> (Vec<Snippet>, usize) {
+    let mut snippets = Vec::new();
+    let mut ignored = 0;
+    for block in markdown::parse(content) {
+        if let Block::Code { info, code, line } = block {
+            match parse_info(&info) {
+                Some(Some(mode)) => snippets.push(Snippet { path: path.to_string(), line, code, mode }),
+                Some(None) => ignored += 1,
+                None => {}
+            }
+        }
+    }
+    (snippets, ignored)
+}
+
+// 隠し行の`# `を外し、`fn main`がなければ全体を包む（使っていない変数などの警告は出さない）
+// 行番号が変わらないように、隠し行も1行ずつそのまま残す
+pub fn wrap(code: &str) -> String {
+    let lines: Vec<&str> = code.lines().map(|line| markdown::hidden_line(line).unwrap_or(line)).collect();
+    let code = lines.join("\n");
+    if code.contains("fn main(") {
+        format!("#![allow(unused)]\n{}\n", code)
+    } else {
+        format!("#![allow(unused)]\nfn main() {{\n{}\n}}\n", code)
+    }
+}
+
+// 2. コンパイルと実行
+#[derive(Debug, PartialEq)]
+pub enum Failure {
+    CompileError(String),  // rustcの診断メッセージ
+    Compiled,              // compile_failなのにコンパイルできた
+    RunFailed(String),     // 実行時のエラー出力
+    NotPanicked,           // should_panicなのに正常に終わった
+    TimedOut,
+}
+
+// 検査の結果（rustcを起動できなかった場合は`io::Error`）
+type Outcome = io::Result<Result<(), Failure>>;
+
+pub fn verify(snippet: &Snippet, build_dir: &Path) -> Outcome {
+    // 診断メッセージに`snippet.rs:行:列`と出るように、作業用のディレクトリで相対パスを渡す
+    fs::write(build_dir.join("snippet.rs"), wrap(&snippet.code))?;
+
+    let compiled = Command::new(exercise::rustc())
+        .args(["--edition", "2024", "--crate-type", "bin", "--crate-name", "snippet"])
+        .args(["--color", "never", "--cap-lints", "allow"])
+        .args(["-o", "snippet", "snippet.rs"])
+        .current_dir(build_dir)
+        .output()?;
+
+    match (snippet.mode, compiled.status.success()) {
+        (Mode::CompileFail, success) => return Ok(if success { Err(Failure::Compiled) } else { Ok(()) }),
+        (_, false) => {
+            let diagnostics = String::from_utf8_lossy(&compiled.stderr).into_owned();
+            return Ok(Err(Failure::CompileError(diagnostics)));
+        }
+        (Mode::NoRun, true) => return Ok(Ok(())),
+        _ => {}
+    }
+
+    // ファイルを作るコード例もあるので、作業用のディレクトリで実行する
+    let mut child = Command::new(build_dir.join("snippet"))
+        .current_dir(build_dir)
+        .env_remove("RUST_BACKTRACE")
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .spawn()?;
+    let start = Instant::now();
+    let status = loop {
+        if let Some(status) = child.try_wait()? {
+            break status;
+        }
+        if start.elapsed() > TIMEOUT {
+            child.kill().ok();
+            child.wait().ok();
+            return Ok(Err(Failure::TimedOut));
+        }
+        thread::sleep(Duration::from_millis(10));
+    };
+    let mut stderr = String::new();
+    if let Some(mut pipe) = child.stderr.take() {
+        pipe.read_to_string(&mut stderr).ok();
+    }
+
+    Ok(match (snippet.mode, status.success()) {
+        (Mode::ShouldPanic, true) => Err(Failure::NotPanicked),
+        (Mode::ShouldPanic, false) | (_, true) => Ok(()),
+        (_, false) => Err(Failure::RunFailed(stderr)),
+    })
+}
+
+// 3. コマンドの実行
+// コード例ごとに作業用のディレクトリを作り、CPUの数だけ並行して検査する
+pub fn command(args: &[String], docs_dir: Option<&Path>) {
+    let sections: Vec<&dyn Section> = match args.first() {
+        Some(name) => match section::find(name) {
+            Some(section) => vec![section],
+            None => {
+                println!("{}", tr!("common.unknown_section", name));
+                return;
+            }
+        },
+        None => section::all().to_vec(),
+    };
+
+    let mut all = Vec::new();
+    let mut ignored = 0;
+    for section in sections {
+        for page in section.doc_pages() {
+            let content = match page.load(docs_dir) {
+                Ok(content) => content,
+                Err(e) => {
+                    let doc_path = docs_dir.unwrap_or(Path::new("")).join(page.localized_path());
+                    println!("{}", tr!("doc.load_error", e, doc_path.display()));
+                    return;
+                }
+            };
+            let (snippets, count) = snippets(&format!("src/{}", page.localized_path()), &content);
+            all.extend(snippets);
+            ignored += count;
+        }
+    }
+
+    let style = Style::detect();
+    println!("{}", tr!("verify.checking", all.len()));
+    let results = run_all(&all);
+
+    let mut failed = 0;
+    for (snippet, result) in all.iter().zip(&results) {
+        let failure = match result {
+            Ok(Ok(())) => continue,
+            Ok(Err(failure)) => failure,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => {
+                println!("{}", tr!("exercise.rustc_not_found"));
+                return;
+            }
+            Err(e) => {
+                println!("{}", tr!("verify.error", e));
+                return;
+            }
+        };
+        failed += 1;
+        report(snippet, failure, style);
+    }
+
+    let passed = all.len() - failed;
+    let summary = tr!("verify.summary", passed, failed, ignored);
+    let color = if failed == 0 { terminal::GREEN } else { terminal::RED };
+    println!("\n{}", style.paint(color, &summary));
+    if failed > 0 {
+        std::process::exit(1);
+    }
+}
+
+fn run_all(snippets: &[Snippet]) -> Vec<Outcome> {
+    let workers = thread::available_parallelism().map_or(1, |n| n.get()).min(snippets.len().max(1));
+    let next = AtomicUsize::new(0);
+    let results: Mutex<Vec<Option<Outcome>>> = Mutex::new((0..snippets.len()).map(|_| None).collect());
+
+    thread::scope(|scope| {
+        for _ in 0..workers {
+            scope.spawn(|| {
+                loop {
+                    let index = next.fetch_add(1, Ordering::Relaxed);
+                    let Some(snippet) = snippets.get(index) else {
+                        break;
+                    };
+                    let build_dir = env::temp_dir().join(format!("hello_cargo-{}-doc-{}", std::process::id(), index));
+                    let result = fs::create_dir_all(&build_dir).and_then(|()| verify(snippet, &build_dir));
+                    fs::remove_dir_all(&build_dir).ok();
+                    results.lock().unwrap()[index] = Some(result);
+                    print!(".");
+                    io::stdout().flush().ok();
+                }
+            });
+        }
+    });
+    println!();
+
+    results.into_inner().unwrap().into_iter().map(|result| result.unwrap()).collect()
+}
+
+fn report(snippet: &Snippet, failure: &Failure, style: Style) {
+    let (reason, detail) = match failure {
+        Failure::CompileError(diagnostics) => (tr!("verify.compile_error"), diagnostics.as_str()),
+        Failure::Compiled => (tr!("verify.compiled"), ""),
+        Failure::RunFailed(stderr) => (tr!("verify.run_failed"), stderr.as_str()),
+        Failure::NotPanicked => (tr!("verify.not_panicked"), ""),
+        Failure::TimedOut => (tr!("verify.timed_out"), ""),
+    };
+    println!("\n{} {}:{} {}", style.paint(terminal::RED, "✗"), snippet.path, snippet.line, reason);
+
+    for line in detail.lines().filter(|line| !line.trim().is_empty()).take(12) {
+        println!("    {}", style.paint(terminal::DIM, &readme_lines(line, snippet)));
+    }
+}
+
+// 診断メッセージやpanicの`snippet.rs:行:列`を、READMEのファイルと行番号に直す
+fn readme_lines(text: &str, snippet: &Snippet) -> String {
+    // `wrap`が先頭に加えた行数
+    let header = if snippet.code.contains("fn main(") { 1 } else { 2 };
+    let mut result = String::new();
+    let mut rest = text;
+    while let Some(index) = rest.find("snippet.rs:") {
+        result.push_str(&rest[..index]);
+        rest = &rest[index + "snippet.rs:".len()..];
+        let digits = rest.chars().take_while(char::is_ascii_digit).count();
+        match rest[..digits].parse::<usize>() {
+            Ok(line) if line > header => {
+                result.push_str(&format!("{}:{}", snippet.path, snippet.line + line - header));
+                rest = &rest[digits..];
+            }
+            _ => result.push_str("snippet.rs:"),
+        }
+    }
+    result.push_str(rest);
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_code_block_markers() {
+        assert_eq!(parse_info("rust"), Some(Some(Mode::Run)));
+        assert_eq!(parse_info("rust,no_run"), Some(Some(Mode::NoRun)));
+        assert_eq!(parse_info("rust, compile_fail"), Some(Some(Mode::CompileFail)));
+        assert_eq!(parse_info("rust,should_panic"), Some(Some(Mode::ShouldPanic)));
+        assert_eq!(parse_info("rust,ignore"), Some(None));
+        assert_eq!(parse_info("bash"), None);
+        assert_eq!(parse_info(""), None);
+    }
+
+    #[test]
+    fn wraps_fragments_in_main() {
+        assert_eq!(wrap("let x = 5;"), "#![allow(unused)]\nfn main() {\nlet x = 5;\n}\n");
+        assert_eq!(wrap("fn main() {}"), "#![allow(unused)]\nfn main() {}\n");
+        assert_eq!(wrap("# use std::fs;\n#[derive(Debug)]\nfn main() {}"), "#![allow(unused)]\nuse std::fs;\n#[derive(Debug)]\nfn main() {}\n");
+
+        let (snippets, ignored) = snippets("README.md", "```rust\nlet x = 5;\n```\n\n```rust,ignore\nfoo();\n```\n\n```bash\nls\n```");
+        assert_eq!(snippets.len(), 1);
+        assert_eq!(snippets[0].line, 1);
+        assert_eq!(ignored, 1);
+    }
+
+    #[test]
+    fn maps_diagnostics_to_readme_lines() {
+        let snippet = Snippet { path: String::from("src/basics/README.md"), line: 40, code: String::from("let x = 5;"), mode: Mode::Run };
+        assert_eq!(readme_lines("  --> snippet.rs:3:9", &snippet), "  --> src/basics/README.md:41:9");
+        assert_eq!(readme_lines("panicked at snippet.rs:4:5:", &snippet), "panicked at src/basics/README.md:42:5:");
+    }
+
+    // 手元のrustcで実際にコンパイル・実行する
+    #[test]
+    fn verifies_with_rustc() {
+        let dir = env::temp_dir().join(format!("hello_cargo-test-{}-verify", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let check = |code: &str, mode| {
+            verify(&Snippet { path: String::new(), line: 1, code: code.to_string(), mode }, &dir).unwrap()
+        };
+        assert_eq!(check("let x = 5; assert_eq!(x, 5);", Mode::Run), Ok(()));
+        assert!(matches!(check("let x: i32 = \"a\";", Mode::Run), Err(Failure::CompileError(_))));
+        assert_eq!(check("let s = String::new(); let t = s; s.len();", Mode::CompileFail), Ok(()));
+        assert_eq!(check("let v: Vec<i32> = Vec::new(); v[0];", Mode::ShouldPanic), Ok(()));
+        assert_eq!(check("let x = 1;", Mode::ShouldPanic), Err(Failure::NotPanicked));
+        fs::remove_dir_all(&dir).ok();
+    }
+}
@@ -0,0 +1,35 @@
+// 演習: カスタムエラー型
+//
+// `parse_age`は文字列を年齢として解析します。
+// 数値でなければ`AgeError::NotANumber`、150より大きければ`AgeError::TooOld`を返します。
+// `?`演算子で`ParseIntError`を`AgeError`に変換できるよう、`From`トレイトを実装してください。
+//
+// 確認: cargo run -- exercise check custom_error
+
+use std::num::ParseIntError;
+
+#[derive(Debug, PartialEq)]
+pub enum AgeError {
+    NotANumber,
+    TooOld(u32),
+}
+
+pub fn parse_age(input: &str) -> Result<u32, AgeError> {
+    let age: u32 = input.trim().parse()?;
+    if age > 150 {
+        return Err(AgeError::TooOld(age));
+    }
+    Ok(age)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_ages() {
+        assert_eq!(parse_age(" 42 "), Ok(42));
+        assert_eq!(parse_age("abc"), Err(AgeError::NotANumber));
+        assert_eq!(parse_age("200"), Err(AgeError::TooOld(200)));
+    }
+}
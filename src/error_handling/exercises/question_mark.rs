@@ -0,0 +1,33 @@
+// 演習: ?演算子
+//
+// `sum_numbers`はカンマ区切りの数値を合計する関数です。
+// 数値として解析できない要素があれば、エラーを呼び出し元に返します。
+// `parse`の結果を`?`演算子で伝播するように修正してください。
+//
+// 確認: cargo run -- exercise check question_mark
+
+use std::num::ParseIntError;
+
+pub fn sum_numbers(input: &str) -> Result<i32, ParseIntError> {
+    let mut sum = 0;
+    for part in input.split(',') {
+        let number: i32 = part.trim().parse();
+        sum += number;
+    }
+    Ok(sum)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sums_numbers() {
+        assert_eq!(sum_numbers("1, 2, 3"), Ok(6));
+    }
+
+    #[test]
+    fn returns_parse_errors() {
+        assert!(sum_numbers("1, two, 3").is_err());
+    }
+}
@@ -1,15 +1,29 @@
 // エラーハンドリングの学習
 // Rustの堅牢なエラー処理メカニズムについて学びます
 
+use std::env;
 use std::fs::File;
-use std::io::{self, Read};
+use std::io::{self, Read, Write};
 use std::error::Error;
 use std::fmt;
 use std::num::ParseFloatError;
+use std::path::{Path, PathBuf};
+use std::process;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use crate::section::{self, Demo, DocPage, Exercise, Section, SourceFile};
+
+// デモで読み書きするファイルは、作業ディレクトリを汚さないよう一時ディレクトリに置く
+// （同じデモを同時に実行しても重ならないよう、呼び出しごとに番号を付ける）
+static NEXT_FILE: AtomicUsize = AtomicUsize::new(0);
+
+fn demo_file(name: &str) -> PathBuf {
+    let index = NEXT_FILE.fetch_add(1, Ordering::Relaxed);
+    env::temp_dir().join(format!("hello_cargo-{}-{}-{}", process::id(), index, name))
+}
 
 // 1. panic!マクロ（回復不可能なエラー）
-pub fn panic_example() {
-    println!("panic!の例（コメントアウトを外すとプログラムが終了します）");
+pub fn panic_example(out: &mut dyn Write) -> io::Result<()> {
+    writeln!(out, "panic!の例（コメントアウトを外すとプログラムが終了します）")?;
     
     // panic!("クラッシュしました！");
     
@@ -19,55 +33,62 @@ pub fn panic_example() {
     
     // 明示的なチェック
     if v.len() <= 2 {
-        println!("ベクタが小さすぎます");
+        writeln!(out, "ベクタが小さすぎます")?;
     } else {
-        println!("3番目の要素: {}", v[2]);
+        writeln!(out, "3番目の要素: {}", v[2])?;
     }
+    Ok(())
 }
 
 // 2. Result型（回復可能なエラー）
-pub fn result_basics() {
+pub fn result_basics(out: &mut dyn Write) -> io::Result<()> {
     // ファイルを開く
-    let f = File::open("hello.txt");
+    let path = demo_file("hello.txt");
+    let f = File::open(&path);
     
     let _f = match f {
         Ok(file) => {
-            println!("ファイルを開きました");
+            writeln!(out, "ファイルを開きました")?;
             file
         }
         Err(error) => {
-            println!("ファイルを開く際のエラー: {:?}", error);
-            return;
+            // エラーの詳細（OSのエラーコードやメッセージ）は環境によって異なるので、種類だけを表示する
+            writeln!(out, "ファイルを開く際のエラー: {:?}", error.kind())?;
+            return Ok(());
         }
     };
     
     // 異なるエラーの処理
     use std::io::ErrorKind;
     
-    let f = File::open("hello.txt");
+    let f = File::open(&path);
     let _f = match f {
         Ok(file) => file,
         Err(error) => match error.kind() {
             ErrorKind::NotFound => {
-                println!("ファイルが見つからないので作成します");
-                match File::create("hello.txt") {
+                writeln!(out, "ファイルが見つからないので作成します")?;
+                match File::create(&path) {
                     Ok(fc) => fc,
                     Err(e) => {
-                        println!("ファイル作成エラー: {:?}", e);
-                        return;
+                        writeln!(out, "ファイル作成エラー: {:?}", e)?;
+                        return Ok(());
                     }
                 }
             }
             other_error => {
-                println!("ファイルを開く際の問題: {:?}", other_error);
-                return;
+                writeln!(out, "ファイルを開く際の問題: {:?}", other_error)?;
+                return Ok(());
             }
         },
     };
+    
+    // クリーンアップ
+    let _ = std::fs::remove_file(&path);
+    Ok(())
 }
 
 // 3. unwrapとexpect
-pub fn unwrap_expect_example() {
+pub fn unwrap_expect_example(out: &mut dyn Write) -> io::Result<()> {
     // unwrap: 成功時は値を返し、失敗時はpanic!
     // let f = File::open("hello.txt").unwrap();
     
@@ -79,12 +100,13 @@ pub fn unwrap_expect_example() {
     let content = "42";
     let number: i32 = content.parse()
         .expect("数値の解析に失敗しました");
-    println!("解析された数値: {}", number);
+    writeln!(out, "解析された数値: {}", number)?;
+    Ok(())
 }
 
 // 4. エラーの伝播
 fn read_username_from_file() -> Result<String, io::Error> {
-    let f = File::open("username.txt");
+    let f = File::open(demo_file("username.txt"));
     
     let mut f = match f {
         Ok(file) => file,
@@ -101,7 +123,7 @@ fn read_username_from_file() -> Result<String, io::Error> {
 
 // ?演算子を使った簡潔な書き方
 fn read_username_from_file_short() -> Result<String, io::Error> {
-    let mut f = File::open("username.txt")?;
+    let mut f = File::open(demo_file("username.txt"))?;
     let mut s = String::new();
     f.read_to_string(&mut s)?;
     Ok(s)
@@ -110,20 +132,21 @@ fn read_username_from_file_short() -> Result<String, io::Error> {
 // さらに短く
 fn read_username_from_file_shorter() -> Result<String, io::Error> {
     let mut s = String::new();
-    File::open("username.txt")?.read_to_string(&mut s)?;
+    File::open(demo_file("username.txt"))?.read_to_string(&mut s)?;
     Ok(s)
 }
 
 // 最も短い（標準ライブラリの関数を使用）
 fn read_username_from_file_shortest() -> Result<String, io::Error> {
-    std::fs::read_to_string("username.txt")
+    std::fs::read_to_string(demo_file("username.txt"))
 }
 
-pub fn error_propagation_example() {
+pub fn error_propagation_example(out: &mut dyn Write) -> io::Result<()> {
     match read_username_from_file() {
-        Ok(username) => println!("ユーザー名: {}", username),
-        Err(e) => println!("ユーザー名の読み取りエラー: {}", e),
+        Ok(username) => writeln!(out, "ユーザー名: {}", username)?,
+        Err(e) => writeln!(out, "ユーザー名の読み取りエラー: {:?}", e.kind())?,
     }
+    Ok(())
 }
 
 // 5. カスタムエラー型
@@ -162,26 +185,27 @@ fn sqrt(x: f64) -> Result<f64, MathError> {
     }
 }
 
-pub fn custom_error_example() {
+pub fn custom_error_example(out: &mut dyn Write) -> io::Result<()> {
     match divide(10.0, 2.0) {
-        Ok(result) => println!("10 ÷ 2 = {}", result),
-        Err(e) => println!("エラー: {}", e),
+        Ok(result) => writeln!(out, "10 ÷ 2 = {}", result)?,
+        Err(e) => writeln!(out, "エラー: {}", e)?,
     }
     
     match divide(10.0, 0.0) {
-        Ok(result) => println!("結果: {}", result),
-        Err(e) => println!("エラー: {}", e),
+        Ok(result) => writeln!(out, "結果: {}", result)?,
+        Err(e) => writeln!(out, "エラー: {}", e)?,
     }
     
     match sqrt(16.0) {
-        Ok(result) => println!("√16 = {}", result),
-        Err(e) => println!("エラー: {}", e),
+        Ok(result) => writeln!(out, "√16 = {}", result)?,
+        Err(e) => writeln!(out, "エラー: {}", e)?,
     }
     
     match sqrt(-4.0) {
-        Ok(result) => println!("結果: {}", result),
-        Err(e) => println!("エラー: {}", e),
+        Ok(result) => writeln!(out, "結果: {}", result)?,
+        Err(e) => writeln!(out, "エラー: {}", e)?,
     }
+    Ok(())
 }
 
 // 6. 複数のエラー型の処理
@@ -228,9 +252,9 @@ impl From<MathError> for AppError {
     }
 }
 
-fn complex_operation() -> Result<f64, AppError> {
+fn complex_operation(path: &Path) -> Result<f64, AppError> {
     // ファイルから数値を読み取る
-    let contents = std::fs::read_to_string("number.txt")?;
+    let contents = std::fs::read_to_string(path)?;
     let number: f64 = contents.trim().parse()?;
     
     // 平方根を計算
@@ -242,30 +266,32 @@ fn complex_operation() -> Result<f64, AppError> {
     Ok(result)
 }
 
-pub fn multiple_error_types_example() {
+pub fn multiple_error_types_example(out: &mut dyn Write) -> io::Result<()> {
     // テスト用のファイルを作成
-    let _ = std::fs::write("number.txt", "100");
+    let path = demo_file("number.txt");
+    let _ = std::fs::write(&path, "100");
     
-    match complex_operation() {
-        Ok(result) => println!("複雑な操作の結果: {}", result),
-        Err(e) => println!("エラーが発生しました: {}", e),
+    match complex_operation(&path) {
+        Ok(result) => writeln!(out, "複雑な操作の結果: {}", result)?,
+        Err(e) => writeln!(out, "エラーが発生しました: {}", e)?,
     }
     
     // クリーンアップ
-    let _ = std::fs::remove_file("number.txt");
+    let _ = std::fs::remove_file(&path);
+    Ok(())
 }
 
 // 7. OptionとResultの変換
-pub fn option_result_conversion() {
+pub fn option_result_conversion(out: &mut dyn Write) -> io::Result<()> {
     // OptionをResultに変換
     let opt: Option<i32> = Some(42);
     let res: Result<i32, &str> = opt.ok_or("値がありません");
-    println!("Option -> Result: {:?}", res);
+    writeln!(out, "Option -> Result: {:?}", res)?;
     
     // ResultをOptionに変換
     let res: Result<i32, &str> = Ok(42);
     let opt: Option<i32> = res.ok();
-    println!("Result -> Option: {:?}", opt);
+    writeln!(out, "Result -> Option: {:?}", opt)?;
     
     // unwrap_or_defaultの使用
     let config: Result<String, io::Error> = Err(io::Error::new(
@@ -273,7 +299,7 @@ pub fn option_result_conversion() {
         "設定ファイルが見つかりません"
     ));
     let config_value = config.unwrap_or_default();
-    println!("設定値（デフォルト）: '{}'", config_value);
+    writeln!(out, "設定値（デフォルト）: '{}'", config_value)?;
     
     // unwrap_or_elseの使用
     let backup_config = || String::from("デフォルト設定");
@@ -282,7 +308,8 @@ pub fn option_result_conversion() {
         "設定ファイルが見つかりません"
     ));
     let config_value = config.unwrap_or_else(|_| backup_config());
-    println!("設定値（関数から）: '{}'", config_value);
+    writeln!(out, "設定値（関数から）: '{}'", config_value)?;
+    Ok(())
 }
 
 // 8. 実践的な例：設定ファイルの読み込み
@@ -294,7 +321,7 @@ struct Config {
 }
 
 impl Config {
-    fn from_file(path: &str) -> Result<Config, AppError> {
+    fn from_file(path: &Path) -> Result<Config, AppError> {
         let contents = std::fs::read_to_string(path)?;
         let mut debug = false;
         let mut port = 8080;
@@ -329,50 +356,128 @@ impl Config {
     }
 }
 
-pub fn config_example() {
+pub fn config_example(out: &mut dyn Write) -> io::Result<()> {
     // 設定ファイルを作成
     let config_content = "debug=true\nport=3000\nhost=0.0.0.0";
-    let _ = std::fs::write("config.txt", config_content);
+    let path = demo_file("config.txt");
+    let _ = std::fs::write(&path, config_content);
     
     // 設定を読み込む
-    let config = Config::from_file("config.txt")
-        .unwrap_or_else(|e| {
-            println!("設定ファイルの読み込みエラー: {}。デフォルト設定を使用します。", e);
+    let config = match Config::from_file(&path) {
+        Ok(config) => config,
+        Err(e) => {
+            writeln!(out, "設定ファイルの読み込みエラー: {}。デフォルト設定を使用します。", e)?;
             Config::with_defaults()
-        });
+        }
+    };
     
-    println!("設定: {:?}", config);
+    writeln!(out, "設定: {:?}", config)?;
     
     // クリーンアップ
-    let _ = std::fs::remove_file("config.txt");
+    let _ = std::fs::remove_file(&path);
+    Ok(())
 }
 
+// デモの一覧（実行順）
+pub static DEMOS: &[Demo] = &[
+    Demo { name: "panic_example", title: "Panicの例", title_en: "Panic Example", run: panic_example },
+    Demo { name: "result_basics", title: "Result型の基本", title_en: "Result Basics", run: result_basics },
+    Demo { name: "unwrap_expect_example", title: "unwrapとexpect", title_en: "unwrap and expect", run: unwrap_expect_example },
+    Demo { name: "error_propagation_example", title: "エラーの伝播", title_en: "Propagating Errors", run: error_propagation_example },
+    Demo { name: "custom_error_example", title: "カスタムエラー型", title_en: "Custom Error Types", run: custom_error_example },
+    Demo { name: "multiple_error_types_example", title: "複数のエラー型", title_en: "Multiple Error Types", run: multiple_error_types_example },
+    Demo { name: "option_result_conversion", title: "OptionとResultの変換", title_en: "Converting Between Option and Result", run: option_result_conversion },
+    Demo { name: "config_example", title: "設定ファイルの例", title_en: "Example: Config File", run: config_example },
+];
+
 // メインの実行関数
-pub fn run_all_error_handling() {
-    println!("\n=== Panicの例 ===");
-    panic_example();
-    
-    println!("\n=== Result型の基本 ===");
-    result_basics();
-    
-    println!("\n=== unwrapとexpect ===");
-    unwrap_expect_example();
-    
-    println!("\n=== エラーの伝播 ===");
-    error_propagation_example();
-    
-    println!("\n=== カスタムエラー型 ===");
-    custom_error_example();
-    
-    println!("\n=== 複数のエラー型 ===");
-    multiple_error_types_example();
-    
-    println!("\n=== OptionとResultの変換 ===");
-    option_result_conversion();
-    
-    println!("\n=== 設定ファイルの例 ===");
-    config_example();
-    
-    // クリーンアップ
-    let _ = std::fs::remove_file("hello.txt");
-}
\ No newline at end of file
+pub fn run_all_error_handling(out: &mut dyn Write) -> io::Result<()> {
+    section::run_demos(DEMOS, out)
+}
+
+// セクションの登録
+pub struct ErrorHandlingSection;
+
+impl Section for ErrorHandlingSection {
+    fn id(&self) -> &'static str {
+        "error"
+    }
+
+    fn aliases(&self) -> &'static [&'static str] {
+        &["error_handling", "errors"]
+    }
+
+    fn title_ja(&self) -> &'static str {
+        "エラーハンドリング"
+    }
+
+    fn title_en(&self) -> &'static str {
+        "Error Handling"
+    }
+
+    fn topics(&self) -> &'static str {
+        "panic!、Result、カスタムエラー"
+    }
+
+    fn topics_en(&self) -> &'static str {
+        "panic!, Result, custom errors"
+    }
+
+    fn run(&self, out: &mut dyn Write) -> io::Result<()> {
+        run_all_error_handling(out)
+    }
+
+    fn demos(&self) -> &'static [Demo] {
+        DEMOS
+    }
+
+    fn source(&self) -> SourceFile {
+        SourceFile {
+            path: "error_handling/mod.rs",
+            content: include_str!("mod.rs"),
+        }
+    }
+
+    fn doc_pages(&self) -> &'static [DocPage] {
+        &[DocPage {
+            name: "readme",
+            path: "error_handling/README.md",
+            description: "エラーハンドリングの詳細",
+            content: include_str!("README.md"),
+            path_en: "error_handling/README.en.md",
+            description_en: "Details of error handling",
+            content_en: include_str!("README.en.md"),
+        }]
+    }
+
+    fn quiz(&self) -> SourceFile {
+        SourceFile {
+            path: "error_handling/quiz.md",
+            content: include_str!("quiz.md"),
+        }
+    }
+
+    fn cards(&self) -> SourceFile {
+        SourceFile {
+            path: "error_handling/cards.md",
+            content: include_str!("cards.md"),
+        }
+    }
+
+    fn exercises(&self) -> &'static [Exercise] {
+        &[
+            Exercise {
+                name: "question_mark",
+                path: "error_handling/exercises/question_mark.rs",
+                hint: "`parse()`は`Result`を返します。`parse()?`とすると、エラーの場合は関数からそのまま返ります。",
+                content: include_str!("exercises/question_mark.rs"),
+            },
+            Exercise {
+                name: "custom_error",
+                path: "error_handling/exercises/custom_error.rs",
+                hint: "`impl From<ParseIntError> for AgeError`を書き、`from`で`AgeError::NotANumber`を返します。",
+                content: include_str!("exercises/custom_error.rs"),
+            },
+        ]
+    }
+}
@@ -0,0 +1,379 @@
+// 演習問題
+// 各セクションの`exercises`ディレクトリにある、コンパイルできない・テストが通らないファイルを修正する練習です
+//
+// 修正する作業用のファイルは、カレントディレクトリの`exercises/<セクション>/<演習名>.rs`に作ります。
+// `exercise check`は手元の`rustc`で`--test`付きでコンパイルし、埋め込まれたテストを実行します
+// （ネットワークやCargoの依存関係は使わないので、オフラインでも動きます）
+
+use crate::markdown::{terminal, terminal::Style};
+use crate::progress::{self, Progress};
+use crate::section::{self, Exercise, Section};
+use std::env;
+use std::fs;
+use std::io::{self, BufRead, Write};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+// 作業用のファイルを置くディレクトリ（`--dir`で変更できる）
+const DEFAULT_DIR: &str = "exercises";
+
+// 1. 演習の一覧
+// 全セクションの演習を、セクションの順に並べる
+fn all() -> Vec<(&'static dyn Section, &'static Exercise)> {
+    section::all()
+        .iter()
+        .flat_map(|section| section.exercises().iter().map(move |exercise| (*section, exercise)))
+        .collect()
+}
+
+fn find(name: &str) -> Option<(&'static dyn Section, &'static Exercise)> {
+    all().into_iter().find(|(_, exercise)| exercise.name == name)
+}
+
+fn is_done(progress: &Progress, section: &dyn Section, exercise: &Exercise) -> bool {
+    progress
+        .exercises
+        .contains_key(&(section.id().to_string(), exercise.name.to_string()))
+}
+
+// まだ終わっていない最初の演習
+fn current(progress: &Progress) -> Option<(&'static dyn Section, &'static Exercise)> {
+    all().into_iter().find(|(section, exercise)| !is_done(progress, *section, exercise))
+}
+
+fn load_progress() -> Progress {
+    progress::data_file()
+        .and_then(|path| Progress::load(&path).ok())
+        .unwrap_or_default()
+}
+
+// 2. 作業用のファイル
+fn work_path(dir: &Path, section: &dyn Section, exercise: &Exercise) -> PathBuf {
+    dir.join(section.id()).join(format!("{}.rs", exercise.name))
+}
+
+// なければ埋め込まれた修正前のファイルから作る
+fn prepare(path: &Path, exercise: &Exercise) -> io::Result<bool> {
+    if path.exists() {
+        return Ok(false);
+    }
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(path, exercise.content)?;
+    Ok(true)
+}
+
+// ファイル先頭のコメント（問題文）
+fn instructions(content: &str) -> Vec<&str> {
+    content
+        .lines()
+        .take_while(|line| line.starts_with("//"))
+        .map(|line| line.trim_start_matches("//").strip_prefix(' ').unwrap_or(""))
+        .collect()
+}
+
+// 3. コンパイルとテストの実行
+#[derive(Debug)]
+enum Outcome {
+    Passed(String),        // テストの出力
+    CompileError(String),  // rustcの診断メッセージ
+    TestFailed(String),    // テストの出力
+}
+
+// `RUSTC`が設定されていればそれを使う（Cargoから実行した場合も同じコンパイラになる）
+pub fn rustc() -> PathBuf {
+    env::var_os("RUSTC").map_or_else(|| PathBuf::from("rustc"), PathBuf::from)
+}
+
+// 同じ演習を同時に確認しても重ならないよう、作業用のディレクトリには呼び出しごとの番号を付ける
+static NEXT_DIR: AtomicUsize = AtomicUsize::new(0);
+
+fn scratch_dir(name: &str) -> PathBuf {
+    let index = NEXT_DIR.fetch_add(1, Ordering::Relaxed);
+    env::temp_dir().join(format!("hello_cargo-{}-{}-{}", std::process::id(), index, name))
+}
+
+fn check(source: &Path, color: bool) -> io::Result<Outcome> {
+    let stem = source.file_stem().and_then(|s| s.to_str()).unwrap_or("exercise");
+    let build_dir = scratch_dir(stem);
+    fs::create_dir_all(&build_dir)?;
+    let result = compile_and_test(source, &build_dir, color);
+    fs::remove_dir_all(&build_dir).ok();
+    result
+}
+
+fn compile_and_test(source: &Path, build_dir: &Path, color: bool) -> io::Result<Outcome> {
+    let binary = build_dir.join("exercise");
+    let compiled = Command::new(rustc())
+        .args(["--edition", "2024", "--test", "--crate-name", "exercise"])
+        .args(["--color", if color { "always" } else { "never" }])
+        .arg("-o")
+        .arg(&binary)
+        .arg(source)
+        .output()?;
+    if !compiled.status.success() {
+        return Ok(Outcome::CompileError(String::from_utf8_lossy(&compiled.stderr).into_owned()));
+    }
+
+    // 失敗したテストの出力が読みやすいよう、バックトレースは表示しない
+    let tested = Command::new(&binary)
+        .args(["--color", if color { "always" } else { "never" }])
+        .env_remove("RUST_BACKTRACE")
+        .output()?;
+    let output = combined(&tested);
+    Ok(if tested.status.success() {
+        Outcome::Passed(output)
+    } else {
+        Outcome::TestFailed(output)
+    })
+}
+
+fn combined(output: &Output) -> String {
+    let mut text = String::from_utf8_lossy(&output.stdout).into_owned();
+    text.push_str(&String::from_utf8_lossy(&output.stderr));
+    text
+}
+
+// 4. コマンドの実行
+pub fn command(args: &[String]) {
+    let mut args: Vec<&str> = args.iter().map(String::as_str).collect();
+    let yes = take_flag(&mut args, &["--yes", "-y"]);
+    let dir = match args.iter().position(|arg| *arg == "--dir") {
+        Some(index) if index + 1 < args.len() => {
+            let dir = PathBuf::from(args[index + 1]);
+            args.drain(index..=index + 1);
+            dir
+        }
+        Some(_) => {
+            println!("{}", tr!("main.option_needs_value", "--dir"));
+            return;
+        }
+        None => PathBuf::from(DEFAULT_DIR),
+    };
+
+    let style = Style::detect();
+    match args.as_slice() {
+        [] | ["list"] => list(&dir, style),
+        ["check"] => match current(&load_progress()) {
+            Some((section, exercise)) => check_command(&dir, section, exercise, style),
+            None => println!("{}", tr!("exercise.all_done")),
+        },
+        ["check", name] => with_exercise(name, |section, exercise| check_command(&dir, section, exercise, style)),
+        ["hint", name] => with_exercise(name, |_, exercise| println!("{}", tr!("exercise.hint", exercise.hint))),
+        ["reset", name] => with_exercise(name, |section, exercise| reset(&dir, section, exercise, yes)),
+        _ => print_help(),
+    }
+}
+
+fn take_flag(args: &mut Vec<&str>, names: &[&str]) -> bool {
+    let before = args.len();
+    args.retain(|arg| !names.contains(arg));
+    args.len() != before
+}
+
+fn with_exercise(name: &str, f: impl FnOnce(&'static dyn Section, &'static Exercise)) {
+    match find(name) {
+        Some((section, exercise)) => f(section, exercise),
+        None => {
+            println!("{}", tr!("exercise.unknown", name));
+            println!("{}", tr!("exercise.list_hint"));
+        }
+    }
+}
+
+fn list(dir: &Path, style: Style) {
+    let progress = load_progress();
+    let next = current(&progress);
+
+    println!("{}", tr!("exercise.list_header"));
+    let mut last_section = "";
+    for (section, exercise) in all() {
+        if section.id() != last_section {
+            println!("\n{} - {}", section.id(), section.title());
+            last_section = section.id();
+        }
+        let mark = if is_done(&progress, section, exercise) {
+            style.paint(terminal::GREEN, "✓")
+        } else if next.is_some_and(|(_, e)| e.name == exercise.name) {
+            style.paint(terminal::BOLD, "▶")
+        } else {
+            " ".to_string()
+        };
+        println!("  {} {:<16}{}", mark, exercise.name, work_path(dir, section, exercise).display());
+    }
+
+    match next {
+        Some((section, exercise)) => {
+            println!("\n{}", style.paint(terminal::BOLD, &tr!("exercise.next", exercise.name)));
+            show_exercise(dir, section, exercise);
+        }
+        None => println!("\n{}", tr!("exercise.completed_all")),
+    }
+}
+
+// 問題文を表示し、作業用のファイルを用意する
+fn show_exercise(dir: &Path, section: &dyn Section, exercise: &Exercise) {
+    for line in instructions(exercise.content) {
+        println!("  {}", line);
+    }
+    let path = work_path(dir, section, exercise);
+    match prepare(&path, exercise) {
+        Ok(true) => println!("\n{}", tr!("exercise.created", path.display())),
+        Ok(false) => println!("\n{}", tr!("exercise.edit_file", path.display())),
+        Err(e) => println!("\n{}", tr!("exercise.create_error", e, path.display())),
+    }
+}
+
+fn check_command(dir: &Path, section: &'static dyn Section, exercise: &Exercise, style: Style) {
+    let path = work_path(dir, section, exercise);
+    match prepare(&path, exercise) {
+        Ok(true) => {
+            println!("{}\n", tr!("exercise.created_check", path.display()));
+            for line in instructions(exercise.content) {
+                println!("  {}", line);
+            }
+            return;
+        }
+        Ok(false) => {}
+        Err(e) => {
+            println!("{}", tr!("exercise.create_error", e, path.display()));
+            return;
+        }
+    }
+
+    println!("{}", tr!("exercise.checking", path.display()));
+    match check(&path, style.color) {
+        Ok(Outcome::Passed(output)) => {
+            print!("{}", style.paint(terminal::DIM, &output));
+            progress::record_exercise(section, exercise);
+            println!("{}", style.paint(terminal::GREEN, &tr!("exercise.passed", exercise.name)));
+            let mut progress = load_progress();
+            // 保存できない環境でも、次の演習へ進めるようにする
+            progress.record_exercise(section.id(), exercise.name, 0);
+            match current(&progress) {
+                Some((section, next)) => {
+                    println!("\n{}", style.paint(terminal::BOLD, &tr!("exercise.next", next.name)));
+                    show_exercise(dir, section, next);
+                }
+                None => println!("\n{}", tr!("exercise.completed_all")),
+            }
+        }
+        Ok(Outcome::CompileError(diagnostics)) => {
+            println!("{}\n", style.paint(terminal::RED, tr!("exercise.compile_error")));
+            print!("{}", diagnostics);
+            println!("{}", tr!("exercise.need_hint", exercise.name));
+        }
+        Ok(Outcome::TestFailed(output)) => {
+            println!("{}\n", style.paint(terminal::RED, tr!("exercise.test_failed")));
+            print!("{}", output);
+            println!("{}", tr!("exercise.need_hint", exercise.name));
+        }
+        Err(e) if e.kind() == io::ErrorKind::NotFound => {
+            println!("{}", tr!("exercise.rustc_not_found"));
+        }
+        Err(e) => println!("{}", tr!("exercise.check_error", e)),
+    }
+}
+
+fn reset(dir: &Path, section: &dyn Section, exercise: &Exercise, yes: bool) {
+    let path = work_path(dir, section, exercise);
+    if path.exists() && !yes && !confirm(&tr!("exercise.confirm_reset", path.display())) {
+        println!("{}", tr!("progress.cancelled"));
+        return;
+    }
+    let result = fs::remove_file(&path)
+        .or_else(|e| if e.kind() == io::ErrorKind::NotFound { Ok(()) } else { Err(e) })
+        .and_then(|()| prepare(&path, exercise));
+    match result {
+        Ok(_) => println!("{}", tr!("exercise.reset", path.display(), exercise.path)),
+        Err(e) => println!("{}", tr!("exercise.reset_error", e, path.display())),
+    }
+}
+
+fn confirm(prompt: &str) -> bool {
+    print!("{} ", prompt);
+    io::stdout().flush().ok();
+    let mut answer = String::new();
+    io::stdin().lock().read_line(&mut answer).is_ok() && matches!(answer.trim(), "y" | "Y" | "yes")
+}
+
+pub fn print_help() {
+    println!("{}", tr!("exercise.title"));
+    println!("\n{}", tr!("exercise.usage"));
+    println!("\n{}", tr!("exercise.subcommands"));
+    println!("  {}", tr!("exercise.cmd.list"));
+    println!("  {}", tr!("exercise.cmd.check"));
+    println!("  {}", tr!("exercise.cmd.hint"));
+    println!("  {}", tr!("exercise.cmd.reset"));
+    println!("\n{}", tr!("help.options"));
+    println!("  {}", tr!("exercise.opt.dir", DEFAULT_DIR));
+    println!("  {}", tr!("exercise.opt.yes"));
+    println!("\n{}", tr!("help.examples"));
+    println!("  {}", tr!("exercise.ex.list"));
+    println!("  {}", tr!("exercise.ex.check"));
+    println!("  {}", tr!("exercise.ex.hint"));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    #[test]
+    fn exercise_names_are_unique_and_documented() {
+        let mut names = HashSet::new();
+        for (_, exercise) in all() {
+            assert!(names.insert(exercise.name), "演習名が重複しています: {}", exercise.name);
+            assert!(exercise.path.ends_with(&format!("/exercises/{}.rs", exercise.name)), "{}", exercise.path);
+            let command = format!("exercise check {}", exercise.name);
+            assert!(
+                instructions(exercise.content).iter().any(|line| line.contains(&command)),
+                "{}: 確認方法が書かれていません",
+                exercise.path
+            );
+            assert!(!exercise.hint.is_empty());
+        }
+        assert!(section::all().iter().all(|section| !section.exercises().is_empty()));
+    }
+
+    fn check_content(name: &str, content: &str) -> Outcome {
+        let dir = scratch_dir(&format!("test-{}", name));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join(format!("{}.rs", name));
+        fs::write(&path, content).unwrap();
+        let outcome = check(&path, false).unwrap();
+        fs::remove_dir_all(&dir).ok();
+        outcome
+    }
+
+    // 修正前のファイルは、どれもコンパイルエラーかテストの失敗になること
+    #[test]
+    fn every_original_fails() {
+        for (_, exercise) in all() {
+            let outcome = check_content(exercise.name, exercise.content);
+            assert!(!matches!(outcome, Outcome::Passed(_)), "{}: 修正前なのに合格しています", exercise.path);
+        }
+    }
+
+    #[test]
+    fn fixed_exercise_passes() {
+        let (_, exercise) = find("move_error").unwrap();
+        let fixed = exercise.content.replace("let s2 = s1;", "let s2 = s1.clone();");
+        assert_ne!(fixed, exercise.content);
+        match check_content("move_error_fixed", &fixed) {
+            Outcome::Passed(output) => assert!(output.contains("test result: ok")),
+            other => panic!("{:?}", other),
+        }
+    }
+
+    #[test]
+    fn compile_errors_are_reported() {
+        let (_, exercise) = find("variables").unwrap();
+        match check_content("variables", exercise.content) {
+            Outcome::CompileError(diagnostics) => assert!(diagnostics.contains("E0384"), "{}", diagnostics),
+            other => panic!("{:?}", other),
+        }
+    }
+}
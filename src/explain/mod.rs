@@ -0,0 +1,375 @@
+// コンパイルエラーの解説
+// デモにコメントで書かれた「エラー！」の行（`// let h = s[0];`など）や、よくあるコンパイルエラーを、
+// rustcのエラーコードと対応させた一覧です
+// `explain <エラーコード>`で、再現するコードを手元の`rustc`でコンパイルして実際の診断メッセージを表示し、
+// 続けて日本語の解説と修正後のコードを表示します
+//
+// 一覧は`errors.md`に書かれています:
+//
+//   ## E0382: ムーブされた値の使用                               ← `エラーコード: タイトル`
+//   該当: ownership/ownership_basics writeln!(out, "{}", s1)?;   ← デモでコメントアウトされた行（省略可、複数可）
+//   参照: ownership#所有権の移動                                 ← 関連するREADMEの見出し
+//
+//   （1つ目のコードブロックが再現するコード、2つ目が修正後のコードで、その間の段落が解説）
+
+use crate::doc;
+use crate::exercise;
+use crate::markdown::{self, terminal, terminal::Style, Align, Block};
+use crate::section;
+use crate::source;
+use std::env;
+use std::fs;
+use std::io;
+use std::process::Command;
+
+const CATALOGUE: &str = include_str!("errors.md");
+
+// 1. エラーの一覧
+#[derive(Debug, Clone, PartialEq)]
+pub struct Location {
+    pub section: String,  // セクションID
+    pub demo: String,     // デモの関数名
+    pub code: String,     // コメントアウトされたコード（`// `の後ろ、行末のコメントを除く）
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Entry {
+    pub code: String,           // エラーコード（例: `E0382`）
+    pub title: String,
+    pub line: usize,            // errors.md内の行番号
+    pub locations: Vec<Location>,
+    pub reference: String,      // `セクション#見出し`
+    pub reproducer: String,     // エラーになるコード
+    pub explanation: Vec<Block>,
+    pub fixed: String,          // 修正後のコード
+}
+
+// 2. 一覧の読み込み
+pub fn parse_catalogue(source: &str) -> Result<Vec<Entry>, String> {
+    let mut entries = Vec::new();
+    let mut current: Option<Entry> = None;
+
+    for block in markdown::parse(source) {
+        match block {
+            Block::Heading { level: 1, .. } => {}
+            Block::Heading { level: 2, text, line } => {
+                if let Some(entry) = current.take() {
+                    entries.push(finish(entry)?);
+                }
+                let (code, title) = text
+                    .split_once(':')
+                    .ok_or(format!("{}行目: 見出しは`エラーコード: タイトル`の形式で書いてください", line))?;
+                current = Some(Entry {
+                    code: code.trim().to_string(),
+                    title: title.trim().to_string(),
+                    line,
+                    locations: Vec::new(),
+                    reference: String::new(),
+                    reproducer: String::new(),
+                    explanation: Vec::new(),
+                    fixed: String::new(),
+                });
+            }
+            block => {
+                let Some(entry) = current.as_mut() else {
+                    continue;
+                };
+                match block {
+                    Block::Paragraph { lines } => {
+                        let mut text = Vec::new();
+                        for line in lines {
+                            if let Some(location) = line.strip_prefix("該当:") {
+                                entry.locations.push(parse_location(location.trim(), entry.line)?);
+                            } else if let Some(reference) = line.strip_prefix("参照:") {
+                                entry.reference = reference.trim().to_string();
+                            } else {
+                                text.push(line);
+                            }
+                        }
+                        if !text.is_empty() {
+                            entry.explanation.push(Block::Paragraph { lines: text });
+                        }
+                    }
+                    Block::Code { code, .. } if entry.reproducer.is_empty() => entry.reproducer = code,
+                    Block::Code { code, .. } if entry.fixed.is_empty() => entry.fixed = code,
+                    Block::Code { .. } => {
+                        return Err(format!("{}行目: コードブロックは再現するコードと修正後のコードの2つだけにしてください", entry.line));
+                    }
+                    block => entry.explanation.push(block),
+                }
+            }
+        }
+    }
+
+    if let Some(entry) = current.take() {
+        entries.push(finish(entry)?);
+    }
+    Ok(entries)
+}
+
+// `ownership/ownership_basics writeln!(out, "{}", s1)?;`
+fn parse_location(text: &str, line: usize) -> Result<Location, String> {
+    let error = || format!("{}行目: `該当: セクション/デモ コード`の形式で書いてください", line);
+    let (target, code) = text.split_once(' ').ok_or_else(error)?;
+    let (section, demo) = target.split_once('/').ok_or_else(error)?;
+    Ok(Location { section: section.to_string(), demo: demo.to_string(), code: code.trim().to_string() })
+}
+
+fn finish(entry: Entry) -> Result<Entry, String> {
+    let line = entry.line;
+    if entry.reference.is_empty() {
+        return Err(format!("{}行目: `参照:`で関連する見出しを指定してください", line));
+    }
+    if entry.fixed.is_empty() {
+        return Err(format!("{}行目: 再現するコードと修正後のコードを書いてください", line));
+    }
+    Ok(entry)
+}
+
+pub fn load() -> Result<Vec<Entry>, String> {
+    parse_catalogue(CATALOGUE).map_err(|e| tr!("explain.catalogue_error", e))
+}
+
+// `e0382`や`382`も`E0382`として探す
+fn normalize_code(text: &str) -> String {
+    let digits = text.trim().trim_start_matches(['E', 'e']);
+    match digits.parse::<u32>() {
+        Ok(number) if digits.chars().all(|c| c.is_ascii_digit()) => format!("E{:04}", number),
+        _ => text.trim().to_uppercase(),
+    }
+}
+
+// 3. デモでの該当箇所
+#[derive(Debug, PartialEq)]
+pub struct Found {
+    pub path: &'static str,  // セクションのソースコードのパス（`src/`から）
+    pub line: usize,         // 行番号（1始まり）
+    pub text: &'static str,  // コメントアウトされた行
+}
+
+// デモの関数の中から、`// <コード>`で始まる行を探す
+pub fn locate(location: &Location) -> Option<Found> {
+    let source = section::find(&location.section)?.source();
+    let function = source::find_function(source.content, &location.demo)?;
+    function.code.lines().enumerate().find_map(|(i, line)| {
+        let commented = line.trim_start().strip_prefix("// ")?;
+        commented.starts_with(&location.code).then(|| Found {
+            path: source.path,
+            line: function.first_line + i,
+            text: line.trim(),
+        })
+    })
+}
+
+// 4. rustcでのコンパイル
+// コンパイルに失敗したら診断メッセージを返す（成功したらNone）
+fn compile(code: &str, color: bool) -> io::Result<Option<String>> {
+    let build_dir = env::temp_dir().join(format!("hello_cargo-{}-explain", std::process::id()));
+    fs::create_dir_all(&build_dir)?;
+    let result = compile_in(&build_dir, code, color);
+    fs::remove_dir_all(&build_dir).ok();
+    result
+}
+
+fn compile_in(build_dir: &std::path::Path, code: &str, color: bool) -> io::Result<Option<String>> {
+    // 診断メッセージに`example.rs:行:列`と出るように、作業用のディレクトリで相対パスを渡す
+    fs::write(build_dir.join("example.rs"), code)?;
+    let compiled = Command::new(exercise::rustc())
+        .args(["--edition", "2024", "--crate-type", "bin", "--crate-name", "example"])
+        .args(["--color", if color { "always" } else { "never" }, "--cap-lints", "allow"])
+        .args(["-o", "example", "example.rs"])
+        .current_dir(build_dir)
+        .output()?;
+    Ok((!compiled.status.success()).then(|| String::from_utf8_lossy(&compiled.stderr).into_owned()))
+}
+
+// 5. コマンド
+// `explain`: エラーコードの一覧、`explain <エラーコード>`: 診断メッセージ・解説・修正後のコード
+pub fn command(args: &[String]) {
+    let entries = match load() {
+        Ok(entries) => entries,
+        Err(e) => {
+            println!("{}", e);
+            return;
+        }
+    };
+    let style = Style::detect();
+    let Some(code) = args.first() else {
+        print_list(&entries, style);
+        return;
+    };
+    let code = normalize_code(code);
+    match entries.iter().find(|entry| entry.code == code) {
+        Some(entry) => explain(entry, style),
+        None => {
+            println!("{}", tr!("explain.unknown", code, code));
+            println!("{}", tr!("explain.list_hint"));
+        }
+    }
+}
+
+fn explain(entry: &Entry, style: Style) {
+    println!("{}", style.paint(&format!("{}{}", terminal::BOLD, terminal::RED), &format!("{}: {}", entry.code, entry.title)));
+
+    // デモに該当する行がないエラーは、再現するコードだけを表示する
+    if !entry.locations.is_empty() {
+        println!("\n{}", style.paint(terminal::BOLD, tr!("explain.locations")));
+    }
+    for location in &entry.locations {
+        match locate(location) {
+            Some(found) => println!("  {}  {}", style.paint(terminal::DIM, &format!("src/{}:{}", found.path, found.line)), found.text),
+            None => println!("  {}/{}  // {}", location.section, location.demo, location.code),
+        }
+    }
+
+    println!("\n{}", style.paint(terminal::BOLD, tr!("explain.reproducer")));
+    for line in terminal::render_source(&entry.reproducer, 1, style) {
+        println!("{}", line);
+    }
+
+    // 手元のrustcの実際の診断メッセージ
+    println!("\n{}", style.paint(terminal::BOLD, tr!("explain.diagnostic")));
+    match compile(&entry.reproducer, style.color) {
+        Ok(Some(diagnostic)) => print!("{}", diagnostic),
+        Ok(None) => println!("{}", tr!("explain.compiled")),
+        Err(e) => println!("{} ({})", tr!("exercise.rustc_not_found"), e),
+    }
+
+    println!("\n{}", style.paint(terminal::BOLD, tr!("explain.explanation")));
+    print!("{}", terminal::render_blocks(&entry.explanation, style));
+
+    println!("\n{}", style.paint(terminal::BOLD, tr!("explain.fixed")));
+    for line in terminal::render_source(&entry.fixed, 1, style) {
+        println!("{}", line);
+    }
+
+    println!();
+    if let Some(command) = doc_command(&entry.reference) {
+        println!("{} {}", style.paint(terminal::DIM, tr!("explain.see_doc")), command);
+    }
+    println!("{} rustc --explain {}", style.paint(terminal::DIM, tr!("explain.rustc_explain")), entry.code);
+}
+
+// `参照:`の見出しを開く`doc`コマンド（見出しはアンカーに直す）
+fn doc_command(reference: &str) -> Option<String> {
+    let (id, heading) = reference.split_once('#')?;
+    let section = section::find(id)?;
+    let page = &section.doc_pages()[0];
+    let blocks = markdown::parse(page.localized_content());
+    let index = doc::find_heading(page, &blocks, heading)?;
+    let Block::Heading { text, .. } = &blocks[index] else {
+        return None;
+    };
+    Some(format!("cargo run -- doc {}#{}", section.id(), markdown::anchor(text)))
+}
+
+fn print_list(entries: &[Entry], style: Style) {
+    let rows = entries
+        .iter()
+        .map(|entry| {
+            let places: Vec<String> = entry.locations.iter().map(|l| format!("{}/{}", l.section, l.demo)).collect();
+            let places = if places.is_empty() { "-".to_string() } else { places.join(", ") };
+            vec![entry.code.clone(), entry.title.clone(), places]
+        })
+        .collect();
+    let table = Block::Table {
+        header: vec![
+            tr!("explain.col.code").to_string(),
+            tr!("explain.col.title").to_string(),
+            tr!("explain.col.demos").to_string(),
+        ],
+        aligns: vec![Align::Left; 3],
+        rows,
+    };
+    println!("{}\n", tr!("explain.title"));
+    print!("{}", terminal::render_blocks(&[table], style));
+    println!("\n{}", tr!("explain.usage"));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    const SAMPLE: &str = "# 解説\n\n## E0384: 再代入\n該当: basics/variables_and_mutability x = 6;\n参照: basics#可変変数\n\n```rust\nfn main() { let x = 5; x = 6; }\n```\n\n解説です。\n\n- 箇条書き\n\n```rust\nfn main() { let mut x = 5; x = 6; }\n```\n";
+
+    #[test]
+    fn parses_entries() {
+        let entries = parse_catalogue(SAMPLE).unwrap();
+        assert_eq!(entries.len(), 1);
+        let entry = &entries[0];
+        assert_eq!((entry.code.as_str(), entry.title.as_str()), ("E0384", "再代入"));
+        assert_eq!(
+            entry.locations,
+            vec![Location { section: "basics".into(), demo: "variables_and_mutability".into(), code: "x = 6;".into() }]
+        );
+        assert_eq!(entry.reference, "basics#可変変数");
+        assert!(entry.reproducer.contains("x = 6;"));
+        assert!(entry.fixed.contains("let mut x"));
+        assert_eq!(entry.explanation.len(), 2);
+    }
+
+    #[test]
+    fn rejects_invalid_entries() {
+        assert!(parse_catalogue("## E0384 タイトルなし\n").is_err());
+        assert!(parse_catalogue("## E0384: 修正なし\n該当: basics/x y\n参照: basics#可変変数\n\n```rust\na\n```\n").is_err());
+        assert!(parse_catalogue("## E0384: 形式\n該当: basics\n").is_err());
+    }
+
+    // `該当:`は省略できる（デモにない、よくあるエラー）
+    #[test]
+    fn locations_are_optional() {
+        let entries = parse_catalogue("## E0384: 該当なし\n参照: basics#可変変数\n\n```rust\na\n```\n\n```rust\nb\n```\n").unwrap();
+        assert!(entries[0].locations.is_empty());
+        assert_eq!(entries[0].fixed, "b");
+    }
+
+    #[test]
+    fn normalizes_error_codes() {
+        assert_eq!(normalize_code("E0382"), "E0382");
+        assert_eq!(normalize_code("e0382"), "E0382");
+        assert_eq!(normalize_code("382"), "E0382");
+        assert_eq!(normalize_code("borrow"), "BORROW");
+    }
+
+    #[test]
+    fn locates_commented_lines_in_demos() {
+        let location = Location { section: "collections".into(), demo: "strings".into(), code: "let h = s[0];".into() };
+        let found = locate(&location).unwrap();
+        assert_eq!(found.path, "collections/mod.rs");
+        assert!(found.text.starts_with("// let h = s[0];  // エラー！"));
+        let source = section::find("collections").unwrap().source().content;
+        assert_eq!(source.lines().nth(found.line - 1).unwrap().trim(), found.text);
+    }
+
+    // すべての項目の該当箇所と見出しが見つかること
+    #[test]
+    fn every_entry_is_valid() {
+        let entries = load().unwrap();
+        assert!(entries.len() >= 5);
+        let mut codes = HashSet::new();
+        for entry in &entries {
+            let at = format!("errors.md:{}", entry.line);
+            assert!(codes.insert(&entry.code), "{}: 同じエラーコードがあります", at);
+            assert_eq!(normalize_code(&entry.code), entry.code, "{}", at);
+            for location in &entry.locations {
+                assert!(locate(location).is_some(), "{}: 該当する行がありません {:?}", at, location);
+            }
+            assert!(doc_command(&entry.reference).is_some(), "{}: 見出しが見つかりません {}", at, entry.reference);
+        }
+    }
+
+    // 手元のrustcで、再現するコードがそのエラーコードで失敗し、修正後のコードがコンパイルできること
+    #[test]
+    fn reproducers_fail_with_their_code() {
+        let dir = env::temp_dir().join(format!("hello_cargo-test-{}-explain", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        for entry in load().unwrap() {
+            let diagnostic = compile_in(&dir, &entry.reproducer, false).unwrap();
+            let diagnostic = diagnostic.unwrap_or_else(|| panic!("{}: コンパイルできてしまいます", entry.code));
+            assert!(diagnostic.contains(&format!("error[{}]", entry.code)), "{}: {}", entry.code, diagnostic);
+            assert_eq!(compile_in(&dir, &entry.fixed, false).unwrap(), None, "{}: 修正後のコードがコンパイルできません", entry.code);
+        }
+        fs::remove_dir_all(&dir).ok();
+    }
+}
@@ -0,0 +1,341 @@
+// 静的なHTMLへの書き出し
+// `export html <ディレクトリ>`で、すべてのセクションのドキュメントをリンクでつないだHTMLとして書き出します。
+// スタイル・検索・デモの実行結果もすべてディレクトリ内に含めるため、ネットワークなしで閲覧できます
+
+use crate::i18n;
+use crate::markdown::{self, html, Block};
+use crate::section::{self, DocPage, Section};
+use crate::transcript::{self, Status};
+use std::fs;
+use std::io;
+use std::path::Path;
+
+const STYLE: &str = include_str!("style.css");
+const SEARCH_SCRIPT: &str = include_str!("search.js");
+
+// 1. コマンドの実行
+pub fn command(args: &[String], docs_dir: Option<&Path>) {
+    let (Some("html"), Some(dir), None) = (args.first().map(String::as_str), args.get(1), args.get(2)) else {
+        print_help();
+        return;
+    };
+
+    let dir = Path::new(dir);
+    match export_html(dir, docs_dir) {
+        Ok(count) => {
+            println!("{}", tr!("export.done", count, dir.display()));
+            println!("{}", tr!("export.open", dir.join("index.html").display()));
+        }
+        Err(e) => println!("{}", tr!("export.error", e)),
+    }
+}
+
+fn print_help() {
+    println!("{}", tr!("export.usage"));
+    println!("\n{}", tr!("export.description"));
+    println!("{}", tr!("export.description_contents"));
+    println!("\n{}", tr!("help.examples"));
+    println!("  {}", tr!("export.ex.html"));
+    println!("  {}", tr!("export.ex.lang"));
+}
+
+// 2. 書き出すページ
+struct Page {
+    section: &'static dyn Section,
+    doc: &'static DocPage,
+    file: String,
+    blocks: Vec<Block>,
+    first: bool,  // セクションの先頭のページ（デモの実行結果を載せる）
+}
+
+impl Page {
+    // ページの見出し（先頭の`#`見出し、なければページの説明）
+    fn title(&self) -> String {
+        self.blocks
+            .iter()
+            .find_map(|block| match block {
+                Block::Heading { level: 1, text, .. } => Some(markdown::inline_text(text)),
+                _ => None,
+            })
+            .unwrap_or_else(|| self.doc.localized_description().to_string())
+    }
+}
+
+// セクションの先頭のページは`basics.html`、それ以外は`ownership-stack-heap.html`
+fn page_file(section: &dyn Section, doc: &DocPage, first: bool) -> String {
+    if first {
+        format!("{}.html", section.id())
+    } else {
+        format!("{}-{}.html", section.id(), doc.name)
+    }
+}
+
+fn load_pages(docs_dir: Option<&Path>) -> io::Result<Vec<Page>> {
+    let mut pages = Vec::new();
+    for section in section::all() {
+        for (i, doc) in section.doc_pages().iter().enumerate() {
+            let content = doc.load(docs_dir)?;
+            pages.push(Page {
+                section: *section,
+                doc,
+                file: page_file(*section, doc, i == 0),
+                blocks: markdown::parse(&content),
+                first: i == 0,
+            });
+        }
+    }
+    Ok(pages)
+}
+
+// 3. 書き出し（書き出したページ数を返す）
+pub fn export_html(dir: &Path, docs_dir: Option<&Path>) -> io::Result<usize> {
+    let pages = load_pages(docs_dir)?;
+    fs::create_dir_all(dir)?;
+
+    fs::write(dir.join("style.css"), STYLE)?;
+    fs::write(dir.join("search.js"), SEARCH_SCRIPT)?;
+    fs::write(dir.join("search-index.js"), search_index(&pages))?;
+    fs::write(dir.join("index.html"), layout(tr!("help.title"), &sidebar(&pages, None), &index_body(&pages)))?;
+
+    for (i, page) in pages.iter().enumerate() {
+        let mut body = html::render_blocks(&page.blocks);
+        if page.first {
+            body.push_str(&demo_output(page.section));
+        }
+        let title = format!("{} - {}", page.title(), tr!("help.title"));
+        fs::write(dir.join(&page.file), layout(&title, &sidebar(&pages, Some(i)), &body))?;
+    }
+    Ok(pages.len() + 1)
+}
+
+// 4. ページの組み立て
+fn layout(title: &str, sidebar: &str, body: &str) -> String {
+    format!(
+        "<!DOCTYPE html>
+<html lang=\"{}\">
+<head>
+<meta charset=\"utf-8\">
+<meta name=\"viewport\" content=\"width=device-width, initial-scale=1\">
+<title>{}</title>
+<link rel=\"stylesheet\" href=\"style.css\">
+</head>
+<body>
+{}<main>
+{}</main>
+<script src=\"search-index.js\"></script>
+<script src=\"search.js\"></script>
+</body>
+</html>
+",
+        i18n::pick("ja", "en"),
+        html::escape(title),
+        sidebar,
+        body,
+    )
+}
+
+// セクションとページの一覧（表示中のページには`##`見出しの目次を付ける）
+fn sidebar(pages: &[Page], current: Option<usize>) -> String {
+    let mut nav = String::from("<nav class=\"sidebar\">\n");
+    nav.push_str(&format!("<a class=\"site-title\" href=\"index.html\">{}</a>\n", html::escape(tr!("help.title"))));
+    nav.push_str(&format!(
+        "<input id=\"search\" type=\"search\" placeholder=\"{}\" autocomplete=\"off\">\n",
+        html::escape(tr!("export.search_placeholder"))
+    ));
+    nav.push_str(&format!(
+        "<ul id=\"search-results\" data-empty=\"{}\"></ul>\n<ul>\n",
+        html::escape(tr!("export.no_results"))
+    ));
+
+    for section in section::all() {
+        let section_pages: Vec<(usize, &Page)> = pages
+            .iter()
+            .enumerate()
+            .filter(|(_, page)| page.section.id() == section.id())
+            .collect();
+        let Some((_, first)) = section_pages.first() else {
+            continue;
+        };
+        let in_section = section_pages.iter().any(|(i, _)| Some(*i) == current);
+        let class = if in_section { " class=\"current\"" } else { "" };
+        nav.push_str(&format!("<li{}><a href=\"{}\">{}</a>\n", class, first.file, html::escape(section.title())));
+
+        if section_pages.len() > 1 {
+            nav.push_str("<ul>\n");
+            for (i, page) in &section_pages {
+                let class = if Some(*i) == current { " class=\"current\"" } else { "" };
+                nav.push_str(&format!(
+                    "<li{}><a href=\"{}\">{}</a>",
+                    class,
+                    page.file,
+                    html::escape(page.doc.localized_description())
+                ));
+                if Some(*i) == current {
+                    nav.push_str(&toc(page));
+                }
+                nav.push_str("</li>\n");
+            }
+            nav.push_str("</ul>\n");
+        } else if in_section {
+            nav.push_str(&toc(first));
+        }
+        nav.push_str("</li>\n");
+    }
+    nav.push_str("</ul>\n</nav>\n");
+    nav
+}
+
+fn toc(page: &Page) -> String {
+    let ids = html::heading_ids(&page.blocks);
+    let headings = page.blocks.iter().filter_map(|block| match block {
+        Block::Heading { level, text, .. } => Some((*level, text)),
+        _ => None,
+    });
+
+    let mut toc = String::from("\n<ul class=\"toc\">\n");
+    for ((level, text), id) in headings.zip(ids) {
+        if level == 2 {
+            toc.push_str(&format!(
+                "<li><a href=\"#{}\">{}</a></li>\n",
+                html::escape(&id),
+                html::escape(&markdown::inline_text(text))
+            ));
+        }
+    }
+    if page.first {
+        toc.push_str(&format!("<li><a href=\"#demo-output\">{}</a></li>\n", html::escape(tr!("export.demo_output"))));
+    }
+    toc.push_str("</ul>\n");
+    toc
+}
+
+fn index_body(pages: &[Page]) -> String {
+    let mut body = format!("<h1>{}</h1>\n<p>{}</p>\n", html::escape(tr!("help.title")), html::escape(tr!("export.intro")));
+    body.push_str(&format!("<h2>{}</h2>\n<dl class=\"sections\">\n", html::escape(tr!("export.sections"))));
+    for section in section::all() {
+        let section_pages: Vec<&Page> = pages.iter().filter(|page| page.section.id() == section.id()).collect();
+        let Some(first) = section_pages.first() else {
+            continue;
+        };
+        body.push_str(&format!(
+            "<dt><a href=\"{}\">{}</a> <code>{}</code></dt>\n<dd>{}",
+            first.file,
+            html::escape(section.title()),
+            section.id(),
+            html::escape(section.topic_summary())
+        ));
+        if section_pages.len() > 1 {
+            body.push_str("\n<ul>\n");
+            for page in &section_pages {
+                body.push_str(&format!(
+                    "<li><a href=\"{}\">{}</a></li>\n",
+                    page.file,
+                    html::escape(page.doc.localized_description())
+                ));
+            }
+            body.push_str("</ul>\n");
+        }
+        body.push_str("</dd>\n");
+    }
+    body.push_str("</dl>\n");
+    body
+}
+
+// 5. デモの実行結果
+// 書き出す時点でデモを実行し、出力をそのまま載せる
+fn demo_output(section: &dyn Section) -> String {
+    let mut body = format!("<h2 id=\"demo-output\">{}</h2>\n", html::escape(tr!("export.demo_output")));
+    let command = format!("cargo run -- {}", section.id());
+    body.push_str(&format!("<p>{}</p>\n", html::inline(&tr!("export.demo_output_description", command))));
+
+    for demo in section.demos() {
+        let record = transcript::run_demo(section, demo);
+        let status = match &record.status {
+            Status::Ok => String::new(),
+            Status::Error(_) => format!(" <span class=\"status\">{}</span>", html::escape(tr!("export.status_error"))),
+            Status::Panicked(_) => format!(" <span class=\"status\">{}</span>", html::escape(tr!("export.status_panicked"))),
+        };
+        body.push_str(&format!(
+            "<h3 id=\"demo-{}\">{} <code>{}</code>{}</h3>\n",
+            demo.name,
+            html::escape(record.title),
+            demo.name,
+            status
+        ));
+        body.push_str(&format!("<pre class=\"output\"><code>{}</code></pre>\n", html::escape(&record.lines.join("\n"))));
+        if let Status::Error(message) | Status::Panicked(message) = &record.status {
+            body.push_str(&format!("<p>{}</p>\n", html::escape(message)));
+        }
+    }
+    body
+}
+
+// 6. 検索インデックス
+// `fetch`はfile://で使えないため、JSONを変数に代入するスクリプトとして書き出す
+fn search_index(pages: &[Page]) -> String {
+    let mut entries = Vec::new();
+    for page in pages {
+        let location = format!("{} / {}", page.section.title(), page.doc.localized_description());
+        for chunk in html::chunks(&page.blocks) {
+            entries.push(format!(
+                "{{\"title\":{},\"page\":{},\"url\":{},\"text\":{}}}",
+                transcript::quote(&chunk.title),
+                transcript::quote(&location),
+                transcript::quote(&format!("{}#{}", page.file, chunk.id)),
+                transcript::quote(&chunk.text),
+            ));
+        }
+    }
+    format!("const SEARCH_INDEX = [\n{}\n];\n", entries.join(",\n"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::env;
+
+    #[test]
+    fn page_files_are_unique() {
+        let pages = load_pages(None).unwrap();
+        let mut files: Vec<&str> = pages.iter().map(|page| page.file.as_str()).collect();
+        assert!(files.contains(&"basics.html"));
+        assert!(files.contains(&"ownership-stack-heap.html"));
+        let count = files.len();
+        files.sort();
+        files.dedup();
+        assert_eq!(files.len(), count);
+    }
+
+    #[test]
+    fn exports_linked_pages() {
+        let dir = env::temp_dir().join(format!("hello_cargo-test-{}-export", std::process::id()));
+        let count = export_html(&dir, None).unwrap();
+        let pages = load_pages(None).unwrap();
+        assert_eq!(count, pages.len() + 1);
+
+        for file in ["index.html", "style.css", "search.js", "search-index.js"] {
+            assert!(dir.join(file).exists(), "{}", file);
+        }
+
+        // サイドバーのリンク先がすべて書き出されていること
+        let basics = fs::read_to_string(dir.join("basics.html")).unwrap();
+        for page in &pages {
+            assert!(basics.contains(&format!("href=\"{}\"", page.file)), "{}", page.file);
+            assert!(dir.join(&page.file).exists(), "{}", page.file);
+        }
+        assert!(basics.contains("<span class=\"kw\">let</span>"));
+        assert!(basics.contains("<h2 id=\"demo-output\">"));
+        assert!(basics.contains("xの値: 5"));
+
+        // 先頭以外のページにはデモの実行結果を載せない
+        let stack_heap = fs::read_to_string(dir.join("ownership-stack-heap.html")).unwrap();
+        assert!(!stack_heap.contains("demo-output"));
+        assert!(stack_heap.contains("<li class=\"current\"><a href=\"ownership-stack-heap.html\">"));
+
+        let index = fs::read_to_string(dir.join("search-index.js")).unwrap();
+        assert!(index.starts_with("const SEARCH_INDEX = [\n{\"title\":"));
+        assert!(index.contains("\"url\":\"basics.html#"));
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,70 @@
+// export htmlで書き出すページの検索
+// 検索インデックス（search-index.js）は<script>で読み込むため、file://で開いても動く
+
+(function () {
+  const input = document.getElementById("search");
+  const results = document.getElementById("search-results");
+  if (!input || !results || typeof SEARCH_INDEX === "undefined") {
+    return;
+  }
+
+  const entries = SEARCH_INDEX.map(function (entry) {
+    return {
+      entry: entry,
+      title: entry.title.toLowerCase(),
+      text: entry.text.toLowerCase(),
+    };
+  });
+
+  // すべての語を含む見出しを、見出しに一致した語が多い順に表示する
+  function search(query) {
+    const terms = query.toLowerCase().split(/\s+/).filter(function (term) {
+      return term.length > 0;
+    });
+    if (terms.length === 0) {
+      return [];
+    }
+    return entries
+      .filter(function (item) {
+        return terms.every(function (term) {
+          return item.title.includes(term) || item.text.includes(term);
+        });
+      })
+      .map(function (item) {
+        const score = terms.filter(function (term) {
+          return item.title.includes(term);
+        }).length;
+        return { entry: item.entry, score: score };
+      })
+      .sort(function (a, b) {
+        return b.score - a.score;
+      })
+      .slice(0, 20);
+  }
+
+  function render(query) {
+    results.textContent = "";
+    const found = search(query);
+    if (query.trim() !== "" && found.length === 0) {
+      const empty = document.createElement("li");
+      empty.textContent = results.dataset.empty;
+      results.appendChild(empty);
+    }
+    found.forEach(function (result) {
+      const item = document.createElement("li");
+      const link = document.createElement("a");
+      link.href = result.entry.url;
+      link.textContent = result.entry.title;
+      const where = document.createElement("span");
+      where.className = "where";
+      where.textContent = result.entry.page;
+      item.appendChild(link);
+      item.appendChild(where);
+      results.appendChild(item);
+    });
+  }
+
+  input.addEventListener("input", function () {
+    render(input.value);
+  });
+})();
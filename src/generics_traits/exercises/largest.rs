@@ -0,0 +1,27 @@
+// 演習: トレイト境界
+//
+// `largest`はスライスの中で最大の要素を返すジェネリック関数です。
+// `>`で比較したり値をコピーしたりするには、`T`にトレイト境界が必要です。
+//
+// 確認: cargo run -- exercise check largest
+
+pub fn largest<T>(list: &[T]) -> T {
+    let mut largest = list[0];
+    for &item in list {
+        if item > largest {
+            largest = item;
+        }
+    }
+    largest
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn numbers_and_chars() {
+        assert_eq!(largest(&[34, 50, 25, 100, 65]), 100);
+        assert_eq!(largest(&['y', 'm', 'a', 'q']), 'y');
+    }
+}
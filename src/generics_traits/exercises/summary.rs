@@ -0,0 +1,38 @@
+// 演習: トレイトの実装
+//
+// `Summary`トレイトの`summarize`には、`summarize_author`を使うデフォルト実装があります。
+// `Tweet`に`Summary`を実装して、テストが通るようにしてください。
+// （`summarize_author`だけを実装すれば、`summarize`はデフォルト実装が使われます）
+//
+// 確認: cargo run -- exercise check summary
+
+pub trait Summary {
+    fn summarize_author(&self) -> String;
+
+    fn summarize(&self) -> String {
+        format!("（{}さんの投稿をもっと読む）", self.summarize_author())
+    }
+}
+
+pub struct Tweet {
+    pub username: String,
+    pub content: String,
+}
+
+pub fn notify(item: &impl Summary) -> String {
+    format!("速報！ {}", item.summarize())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn default_summary() {
+        let tweet = Tweet {
+            username: String::from("horse_ebooks"),
+            content: String::from("もちろん、ご存知のように、みなさん"),
+        };
+        assert_eq!(notify(&tweet), "速報！ （@horse_ebooksさんの投稿をもっと読む）");
+    }
+}
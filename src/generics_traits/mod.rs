@@ -2,9 +2,12 @@
 // Rustの型システムの強力な機能について学びます
 
 use std::fmt::{Debug, Display};
+use std::io::{self, Write};
+use crate::layout::{self, Shape, TypeLayout};
+use crate::section::{self, Demo, DocPage, Exercise, Section, SourceFile};
 
 // 1. ジェネリック関数
-pub fn generics_functions() {
+pub fn generics_functions(out: &mut dyn Write) -> io::Result<()> {
     // 非ジェネリックな関数（重複したコード）
     fn largest_i32(list: &[i32]) -> i32 {
         let mut largest = list[0];
@@ -39,11 +42,12 @@ pub fn generics_functions() {
     
     let number_list = vec![34, 50, 25, 100, 65];
     let result = largest(&number_list);
-    println!("最大の数値: {}", result);
+    writeln!(out, "最大の数値: {}", result)?;
     
     let char_list = vec!['y', 'm', 'a', 'q'];
     let result = largest(&char_list);
-    println!("最大の文字: {}", result);
+    writeln!(out, "最大の文字: {}", result)?;
+    Ok(())
 }
 
 // 2. ジェネリック構造体
@@ -85,22 +89,23 @@ impl<T, U> MixedPoint<T, U> {
     }
 }
 
-pub fn generics_structs() {
+pub fn generics_structs(out: &mut dyn Write) -> io::Result<()> {
     let integer_point = Point { x: 5, y: 10 };
     let float_point = Point { x: 1.0, y: 4.0 };
     let mixed = MixedPoint { x: 5, y: 10.5 };
     
-    println!("整数の点: {:?}", integer_point);
-    println!("浮動小数点の点: {:?}", float_point);
-    println!("混合型の点: {:?}", mixed);
+    writeln!(out, "整数の点: {:?}", integer_point)?;
+    writeln!(out, "浮動小数点の点: {:?}", float_point)?;
+    writeln!(out, "混合型の点: {:?}", mixed)?;
     
-    println!("integer_point.x = {}", integer_point.x());
-    println!("原点からの距離: {}", float_point.distance_from_origin());
+    writeln!(out, "integer_point.x = {}", integer_point.x())?;
+    writeln!(out, "原点からの距離: {}", float_point.distance_from_origin())?;
     
     let p1 = MixedPoint { x: 5, y: 10.4 };
     let p2 = MixedPoint { x: "Hello", y: 'c' };
     let p3 = p1.mixup(p2);
-    println!("混ぜた結果: {:?}", p3);
+    writeln!(out, "混ぜた結果: {:?}", p3)?;
+    Ok(())
 }
 
 // 3. ジェネリック列挙型
@@ -141,18 +146,18 @@ impl<T> BinaryTree<T> {
     }
 }
 
-pub fn generics_enums() {
+pub fn generics_enums(out: &mut dyn Write) -> io::Result<()> {
     let some_number = Option::Some(5);
     let some_string = Option::Some("a string");
     let absent_number: Option<i32> = Option::None;
     
-    println!("Option列挙型: {:?}, {:?}, {:?}", 
-             some_number, some_string, absent_number);
+    writeln!(out, "Option列挙型: {:?}, {:?}, {:?}", 
+             some_number, some_string, absent_number)?;
     
     let success: Result<i32, String> = Result::Ok(42);
     let failure: Result<i32, String> = Result::Err(String::from("エラーです"));
     
-    println!("Result列挙型: {:?}, {:?}", success, failure);
+    writeln!(out, "Result列挙型: {:?}, {:?}", success, failure)?;
     
     // 二分木の例
     let tree = BinaryTree::Node {
@@ -160,7 +165,8 @@ pub fn generics_enums() {
         left: Box::new(BinaryTree::leaf(3)),
         right: Box::new(BinaryTree::leaf(7)),
     };
-    println!("二分木: {:?}", tree);
+    writeln!(out, "二分木: {:?}", tree)?;
+    Ok(())
 }
 
 // 4. トレイトの定義と実装
@@ -203,7 +209,7 @@ impl Summary for Tweet {
     }
 }
 
-pub fn traits_basics() {
+pub fn traits_basics(out: &mut dyn Write) -> io::Result<()> {
     let tweet = Tweet {
         username: String::from("horse_ebooks"),
         content: String::from("もちろん、ご存知のように、みなさん"),
@@ -218,31 +224,32 @@ pub fn traits_basics() {
         content: String::from("ピッツバーグ・ペンギンズが再びNHLチャンピオンに。"),
     };
     
-    println!("新しいツイート: {}", tweet.summarize());
-    println!("新しい記事: {}", article.summarize());
-    println!("記事の著者: {}", article.summarize_author());
+    writeln!(out, "新しいツイート: {}", tweet.summarize())?;
+    writeln!(out, "新しい記事: {}", article.summarize())?;
+    writeln!(out, "記事の著者: {}", article.summarize_author())?;
+    Ok(())
 }
 
 // 5. トレイト境界
-pub fn notify<T: Summary>(item: &T) {
-    println!("速報！ {}", item.summarize());
+pub fn notify<T: Summary>(out: &mut dyn Write, item: &T) -> io::Result<()> {
+    writeln!(out, "速報！ {}", item.summarize())
 }
 
 // 複数のトレイト境界
-pub fn notify_multiple<T: Summary + Display>(item: &T) {
-    println!("速報！ {}", item.summarize());
-    println!("表示: {}", item);
+pub fn notify_multiple<T: Summary + Display>(out: &mut dyn Write, item: &T) -> io::Result<()> {
+    writeln!(out, "速報！ {}", item.summarize())?;
+    writeln!(out, "表示: {}", item)
 }
 
 // where句を使った見やすい書き方
-fn some_function<T, U>(t: &T, u: &U) -> i32
+fn some_function<T, U>(out: &mut dyn Write, t: &T, u: &U) -> io::Result<i32>
 where
     T: Display + Clone,
     U: Clone + Debug,
 {
-    println!("t: {}", t);
-    println!("u: {:?}", u);
-    42
+    writeln!(out, "t: {}", t)?;
+    writeln!(out, "u: {:?}", u)?;
+    Ok(42)
 }
 
 // トレイト境界を返り値に使用
@@ -255,7 +262,7 @@ fn returns_summarizable() -> impl Summary {
     }
 }
 
-pub fn trait_bounds_example() {
+pub fn trait_bounds_example(out: &mut dyn Write) -> io::Result<()> {
     let tweet = Tweet {
         username: String::from("horse_ebooks"),
         content: String::from("最新ニュース"),
@@ -263,14 +270,15 @@ pub fn trait_bounds_example() {
         retweet: false,
     };
     
-    notify(&tweet);
+    notify(out, &tweet)?;
     
     let item = returns_summarizable();
-    println!("返されたアイテム: {}", item.summarize());
+    writeln!(out, "返されたアイテム: {}", item.summarize())?;
+    Ok(())
 }
 
 // 6. ライフタイムとジェネリクス
-pub fn lifetimes_example() {
+pub fn lifetimes_example(out: &mut dyn Write) -> io::Result<()> {
     // ライフタイム注釈が必要な関数
     fn longest<'a>(x: &'a str, y: &'a str) -> &'a str {
         if x.len() > y.len() {
@@ -284,7 +292,7 @@ pub fn lifetimes_example() {
     let string2 = "xyz";
     
     let result = longest(string1.as_str(), string2);
-    println!("最も長い文字列: {}", result);
+    writeln!(out, "最も長い文字列: {}", result)?;
     
     // ライフタイムを持つ構造体
     #[derive(Debug)]
@@ -297,9 +305,9 @@ pub fn lifetimes_example() {
             3
         }
         
-        fn announce_and_return_part(&self, announcement: &str) -> &str {
-            println!("注意: {}", announcement);
-            self.part
+        fn announce_and_return_part(&self, out: &mut dyn Write, announcement: &str) -> io::Result<&str> {
+            writeln!(out, "注意: {}", announcement)?;
+            Ok(self.part)
         }
     }
     
@@ -309,8 +317,9 @@ pub fn lifetimes_example() {
         part: first_sentence,
     };
     
-    println!("重要な引用: {:?}", i);
-    println!("レベル: {}", i.level());
+    writeln!(out, "重要な引用: {:?}", i)?;
+    writeln!(out, "レベル: {}", i.level())?;
+    Ok(())
 }
 
 // 7. 高度なトレイト
@@ -364,13 +373,13 @@ impl Add for Point2D {
     }
 }
 
-pub fn advanced_traits() {
+pub fn advanced_traits(out: &mut dyn Write) -> io::Result<()> {
     // カスタムイテレータ
     let mut counter = Counter::new();
     
-    println!("カウンター:");
+    writeln!(out, "カウンター:")?;
     while let Option::Some(value) = counter.next() {
-        println!("  {}", value);
+        writeln!(out, "  {}", value)?;
     }
     
     // 演算子オーバーロード
@@ -378,10 +387,12 @@ pub fn advanced_traits() {
     let p2 = Point2D { x: 3.0, y: 4.0 };
     let p3 = p1 + p2;
     
-    println!("{:?} + {:?} = {:?}", p1, p2, p3);
+    writeln!(out, "{:?} + {:?} = {:?}", p1, p2, p3)?;
+    Ok(())
 }
 
 // 8. 実践的な例：ジェネリックなキャッシュ
+use std::cell::Cell;
 use std::collections::HashMap;
 use std::hash::Hash;
 
@@ -418,41 +429,135 @@ where
     }
 }
 
-pub fn cache_example() {
+pub fn cache_example(out: &mut dyn Write) -> io::Result<()> {
+    // 計算した回数を数える（`Fn`のクロージャから書き換えるため`Cell`を使う）
+    let calculations = Cell::new(0);
     let mut expensive_cache = Cache::new(|num: &u32| {
-        println!("高コストな計算中...");
+        calculations.set(calculations.get() + 1);
         std::thread::sleep(std::time::Duration::from_millis(100));
         num * 2
     });
     
-    println!("最初の呼び出し: {}", expensive_cache.value(5));
-    println!("二回目の呼び出し（キャッシュ済み）: {}", expensive_cache.value(5));
-    println!("別の値: {}", expensive_cache.value(10));
-}
+    let value = expensive_cache.value(5);
+    writeln!(out, "最初の呼び出し: {}（計算回数: {}）", value, calculations.get())?;
+    let value = expensive_cache.value(5);
+    writeln!(out, "二回目の呼び出し（キャッシュ済み）: {}（計算回数: {}）", value, calculations.get())?;
+    let value = expensive_cache.value(10);
+    writeln!(out, "別の値: {}（計算回数: {}）", value, calculations.get())?;
+    Ok(())
+}
+
+// デモの一覧（実行順）
+pub static DEMOS: &[Demo] = &[
+    Demo { name: "generics_functions", title: "ジェネリック関数", title_en: "Generic Functions", run: generics_functions },
+    Demo { name: "generics_structs", title: "ジェネリック構造体", title_en: "Generic Structs", run: generics_structs },
+    Demo { name: "generics_enums", title: "ジェネリック列挙型", title_en: "Generic Enums", run: generics_enums },
+    Demo { name: "traits_basics", title: "トレイトの基本", title_en: "Trait Basics", run: traits_basics },
+    Demo { name: "trait_bounds_example", title: "トレイト境界", title_en: "Trait Bounds", run: trait_bounds_example },
+    Demo { name: "lifetimes_example", title: "ライフタイム", title_en: "Lifetimes", run: lifetimes_example },
+    Demo { name: "advanced_traits", title: "高度なトレイト", title_en: "Advanced Traits", run: advanced_traits },
+    Demo { name: "cache_example", title: "キャッシュの例", title_en: "Example: Cache", run: cache_example },
+];
 
 // メインの実行関数
-pub fn run_all_generics_traits() {
-    println!("\n=== ジェネリック関数 ===");
-    generics_functions();
-    
-    println!("\n=== ジェネリック構造体 ===");
-    generics_structs();
-    
-    println!("\n=== ジェネリック列挙型 ===");
-    generics_enums();
-    
-    println!("\n=== トレイトの基本 ===");
-    traits_basics();
-    
-    println!("\n=== トレイト境界 ===");
-    trait_bounds_example();
-    
-    println!("\n=== ライフタイム ===");
-    lifetimes_example();
-    
-    println!("\n=== 高度なトレイト ===");
-    advanced_traits();
-    
-    println!("\n=== キャッシュの例 ===");
-    cache_example();
-}
\ No newline at end of file
+pub fn run_all_generics_traits(out: &mut dyn Write) -> io::Result<()> {
+    section::run_demos(DEMOS, out)
+}
+
+// セクションの登録
+pub struct GenericsTraitsSection;
+
+impl Section for GenericsTraitsSection {
+    fn id(&self) -> &'static str {
+        "generics"
+    }
+
+    fn aliases(&self) -> &'static [&'static str] {
+        &["generics_traits", "traits"]
+    }
+
+    fn title_ja(&self) -> &'static str {
+        "ジェネリクスとトレイト"
+    }
+
+    fn title_en(&self) -> &'static str {
+        "Generics and Traits"
+    }
+
+    fn topics(&self) -> &'static str {
+        "型パラメータ、トレイト境界"
+    }
+
+    fn topics_en(&self) -> &'static str {
+        "Type parameters, trait bounds"
+    }
+
+    fn run(&self, out: &mut dyn Write) -> io::Result<()> {
+        run_all_generics_traits(out)
+    }
+
+    fn demos(&self) -> &'static [Demo] {
+        DEMOS
+    }
+
+    fn source(&self) -> SourceFile {
+        SourceFile {
+            path: "generics_traits/mod.rs",
+            content: include_str!("mod.rs"),
+        }
+    }
+
+    fn doc_pages(&self) -> &'static [DocPage] {
+        &[DocPage {
+            name: "readme",
+            path: "generics_traits/README.md",
+            description: "ジェネリクスとトレイトの詳細",
+            content: include_str!("README.md"),
+            path_en: "generics_traits/README.en.md",
+            description_en: "Details of generics and traits",
+            content_en: include_str!("README.en.md"),
+        }]
+    }
+
+    fn quiz(&self) -> SourceFile {
+        SourceFile {
+            path: "generics_traits/quiz.md",
+            content: include_str!("quiz.md"),
+        }
+    }
+
+    fn cards(&self) -> SourceFile {
+        SourceFile {
+            path: "generics_traits/cards.md",
+            content: include_str!("cards.md"),
+        }
+    }
+
+    fn exercises(&self) -> &'static [Exercise] {
+        &[
+            Exercise {
+                name: "largest",
+                path: "generics_traits/exercises/largest.rs",
+                hint: "比較には`PartialOrd`、`list[0]`のように値を取り出すには`Copy`が必要です。`<T: PartialOrd + Copy>`と書きます。",
+                content: include_str!("exercises/largest.rs"),
+            },
+            Exercise {
+                name: "summary",
+                path: "generics_traits/exercises/summary.rs",
+                hint: "`impl Summary for Tweet`を書き、`summarize_author`で`format!(\"@{}\", self.username)`を返します。",
+                content: include_str!("exercises/summary.rs"),
+            },
+        ]
+    }
+
+    fn layouts(&self) -> Vec<TypeLayout> {
+        vec![
+            TypeLayout::of::<BinaryTree<i32>>(Shape::Enum(layout::variants!(BinaryTree<i32> {
+                Empty,
+                Node { value: i32, left: Box<BinaryTree<i32>>, right: Box<BinaryTree<i32>> },
+            }))),
+            // Option<Box<T>>はNoneをヌルポインタで表すので、Boxと同じ大きさになる
+            TypeLayout::of::<Box<BinaryTree<i32>>>(Shape::Opaque),
+        ]
+    }
+}
@@ -0,0 +1,402 @@
+// 用語集
+// `glossary.txt`に書いた用語（日本語・読み・英語・説明・出てくるセクション）を、
+// `glossary [用語]`で日本語・読み・英語のどれからでも探せるようにします
+// `doc`でREADMEを表示するときは、本文に出てくる用語を強調し、最後に一覧を表示します
+
+use crate::i18n::{self, Lang};
+use crate::markdown::{self, terminal, terminal::Style, Align, Block, Inline};
+use crate::section::{self, Section};
+use std::ops::Range;
+
+const GLOSSARY: &str = include_str!("glossary.txt");
+
+// `glossary`で最もよく一致した用語と一緒に表示する、他の候補の数
+const OTHER_CANDIDATES: usize = 5;
+
+// 1. 用語
+#[derive(Debug, Clone, PartialEq)]
+pub struct Term {
+    pub ja: &'static str,
+    pub reading: &'static str,        // 日本語の読み（ひらがな）
+    pub en: &'static str,
+    pub definition: &'static str,
+    pub definition_en: &'static str,
+    pub sections: Vec<&'static str>,  // 用語が出てくるセクションのID
+}
+
+impl Term {
+    // 表示言語に合わせた用語と説明
+    pub fn name(&self) -> &'static str {
+        i18n::pick(self.ja, self.en)
+    }
+
+    pub fn localized_definition(&self) -> &'static str {
+        i18n::pick(self.definition, self.definition_en)
+    }
+
+    fn find_in(&self, text: &str, lang: Lang) -> Option<Range<usize>> {
+        match lang {
+            Lang::Ja => text.find(self.ja).map(|start| start..start + self.ja.len()),
+            Lang::En => find_word(text, self.en),
+        }
+    }
+}
+
+// 2. 用語集の読み込み
+// 1行に1語を`日本語 | 読み | 英語 | 説明 | 英語の説明 | セクション...`の形式で書く
+pub fn parse(text: &'static str) -> Result<Vec<Term>, String> {
+    let mut terms = Vec::new();
+    for (i, line) in text.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let fields: Vec<&'static str> = line.split('|').map(str::trim).collect();
+        let [ja, reading, en, definition, definition_en, sections] = fields[..] else {
+            return Err(tr!("glossary.invalid_line", i + 1));
+        };
+        let sections: Vec<&'static str> = sections.split_whitespace().collect();
+        if let Some(unknown) = sections.iter().find(|id| section::find(id).is_none()) {
+            return Err(tr!("glossary.unknown_section", i + 1, unknown));
+        }
+        terms.push(Term { ja, reading, en, definition, definition_en, sections });
+    }
+    Ok(terms)
+}
+
+pub fn load() -> Result<Vec<Term>, String> {
+    parse(GLOSSARY)
+}
+
+// 3. あいまい検索
+// 表記のゆれを吸収するため、小文字にして空白や区切り記号を除き、カタカナをひらがなにそろえる
+fn normalize(text: &str) -> String {
+    text.chars()
+        .filter(|c| !matches!(c, ' ' | '　' | '-' | '_' | '・'))
+        .flat_map(char::to_lowercase)
+        .map(|c| match c {
+            'ァ'..='ヶ' => char::from_u32(c as u32 - 0x60).unwrap_or(c),
+            c => c,
+        })
+        .collect()
+}
+
+// 一致の度合い（小さいほどよく一致している、一致しなければNone）
+fn score(query: &str, key: &str) -> Option<usize> {
+    if key == query {
+        return Some(0);
+    }
+    if key.starts_with(query) {
+        return Some(1);
+    }
+    if key.contains(query) || (query.contains(key) && key.chars().count() >= 2) {
+        return Some(2);
+    }
+    // 入力の誤りは、4文字あたり1文字まで許す
+    let distance = edit_distance(query, key);
+    (distance <= (query.chars().count() / 4).max(1)).then_some(3 + distance)
+}
+
+// レーベンシュタイン距離（文字単位）
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut current = vec![i + 1];
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(ca != *cb);
+            current.push(substitution.min(previous[j + 1] + 1).min(current[j] + 1));
+        }
+        previous = current;
+    }
+    previous[b.len()]
+}
+
+// 日本語・読み・英語のいずれかに一致する用語を、よく一致する順に返す
+pub fn lookup<'a>(terms: &'a [Term], query: &str) -> Vec<&'a Term> {
+    let query = normalize(query);
+    if query.is_empty() {
+        return Vec::new();
+    }
+    let mut found: Vec<(usize, usize, &Term)> = terms
+        .iter()
+        .enumerate()
+        .filter_map(|(i, term)| {
+            let best = [term.ja, term.reading, term.en]
+                .iter()
+                .filter_map(|key| score(&query, &normalize(key)))
+                .min()?;
+            Some((best, i, term))
+        })
+        .collect();
+    found.sort_by_key(|(score, i, _)| (*score, *i));
+    found.into_iter().map(|(_, _, term)| term).collect()
+}
+
+// 4. ドキュメントの中の用語
+// 英語は大文字・小文字を区別せず、単語の途中では一致させない（複数形の`s`は含める）
+fn find_word(text: &str, word: &str) -> Option<Range<usize>> {
+    let lower = text.to_ascii_lowercase();
+    let word = word.to_ascii_lowercase();
+    let is_word = |c: char| c.is_ascii_alphanumeric();
+    let mut from = 0;
+    while let Some(found) = lower[from..].find(&word) {
+        let start = from + found;
+        let mut end = start + word.len();
+        if lower[end..].starts_with('s') && !lower[end + 1..].starts_with(is_word) {
+            end += 1;
+        }
+        if !lower[..start].ends_with(is_word) && !lower[end..].starts_with(is_word) {
+            return Some(start..end);
+        }
+        from = start + 1;
+        while !lower.is_char_boundary(from) {
+            from += 1;
+        }
+    }
+    None
+}
+
+// 本文（段落・リスト・引用）に出てくる用語の最初の1か所を`**用語**`で強調する
+// 見出しはアンカーが変わらないように、表とコードは崩れないようにそのままにする
+// 長い用語から探すので、「可変参照」の中の「参照」は別に強調しない
+pub fn annotate<'a>(blocks: &[Block], terms: &'a [Term], lang: Lang) -> (Vec<Block>, Vec<&'a Term>) {
+    let mut order: Vec<&Term> = terms.iter().collect();
+    order.sort_by_key(|term| match lang {
+        Lang::Ja => std::cmp::Reverse(term.ja.chars().count()),
+        Lang::En => std::cmp::Reverse(term.en.len()),
+    });
+
+    let mut found: Vec<&Term> = Vec::new();
+    let mut mark = |line: &str| -> String {
+        let mut inlines = markdown::parse_inline(line);
+        for term in &order {
+            if found.contains(term) {
+                continue;
+            }
+            let Some((i, range)) = inlines.iter().enumerate().find_map(|(i, inline)| match inline {
+                Inline::Text(text) => Some((i, term.find_in(text, lang)?)),
+                _ => None,
+            }) else {
+                continue;
+            };
+            let Inline::Text(text) = inlines.remove(i) else {
+                continue;
+            };
+            let parts = [
+                Inline::Text(text[..range.start].to_string()),
+                Inline::Strong(text[range.clone()].to_string()),
+                Inline::Text(text[range.end..].to_string()),
+            ];
+            let parts = parts.into_iter().filter(|part| !matches!(part, Inline::Text(text) if text.is_empty()));
+            inlines.splice(i..i, parts);
+            found.push(term);
+        }
+        inlines.iter().map(to_markdown).collect()
+    };
+
+    let annotated = blocks
+        .iter()
+        .map(|block| match block {
+            Block::Paragraph { lines } => Block::Paragraph { lines: lines.iter().map(|line| mark(line)).collect() },
+            Block::Quote { lines } => Block::Quote { lines: lines.iter().map(|line| mark(line)).collect() },
+            Block::List { items } => Block::List {
+                items: items
+                    .iter()
+                    .map(|item| markdown::ListItem { text: mark(&item.text), ..item.clone() })
+                    .collect(),
+            },
+            block => block.clone(),
+        })
+        .collect();
+    (annotated, found)
+}
+
+fn to_markdown(inline: &Inline) -> String {
+    match inline {
+        Inline::Text(text) => text.clone(),
+        Inline::Code(code) => format!("`{}`", code),
+        Inline::Strong(text) => format!("**{}**", text),
+        Inline::Link { text, url } => format!("[{}]({})", text, url),
+    }
+}
+
+// 用語を並べるときの区切り
+fn separator() -> &'static str {
+    i18n::pick("、", ", ")
+}
+
+// `doc`の最後に表示する、本文に出てきた用語の一覧
+pub fn print_terms(terms: &[&Term], style: Style) {
+    if terms.is_empty() {
+        return;
+    }
+    let names: Vec<&str> = terms.iter().map(|term| term.name()).collect();
+    println!("\n{}", style.paint(terminal::DIM, tr!("glossary.doc_terms")));
+    println!("  {}", names.join(separator()));
+}
+
+// 用語が最初に出てくる見出しのアンカー（最初の見出しより前なら空文字列、出てこなければNone）
+fn first_mention(section: &dyn Section, term: &Term) -> Option<String> {
+    let page = &section.doc_pages()[0];
+    let lang = i18n::lang();
+    let mut heading = None;
+    for block in markdown::parse(page.localized_content()) {
+        let text = match &block {
+            // ページの題名（`#`）は除く
+            Block::Heading { level: 1, .. } => continue,
+            Block::Heading { text, .. } => {
+                heading = Some(markdown::anchor(text));
+                text.clone()
+            }
+            Block::Paragraph { lines } | Block::Quote { lines } => lines.join("\n"),
+            Block::List { items } => items.iter().map(|item| item.text.as_str()).collect::<Vec<_>>().join("\n"),
+            _ => continue,
+        };
+        if term.find_in(&markdown::inline_text(&text), lang).is_some() {
+            return Some(heading.unwrap_or_default());
+        }
+    }
+    None
+}
+
+// 5. コマンド
+// `glossary`: 用語の一覧、`glossary <用語>`: 用語の説明と出てくるセクション
+pub fn command(args: &[String]) {
+    let terms = match load() {
+        Ok(terms) => terms,
+        Err(e) => {
+            println!("{}", e);
+            return;
+        }
+    };
+    let style = Style::detect();
+    if args.is_empty() {
+        print_list(&terms, style);
+        return;
+    }
+
+    // `trait bound`のように空白を含む用語は、引用符で囲まなくても探せる
+    let query = args.join(" ");
+    let found = lookup(&terms, &query);
+    let Some(term) = found.first() else {
+        println!("{}", tr!("glossary.not_found", query));
+        println!("{}", tr!("glossary.list_hint"));
+        return;
+    };
+    print_term(term, style);
+
+    let others: Vec<&str> = found[1..].iter().take(OTHER_CANDIDATES).map(|term| term.name()).collect();
+    if !others.is_empty() {
+        println!("\n{} {}", style.paint(terminal::DIM, tr!("glossary.others")), others.join(separator()));
+    }
+}
+
+fn print_term(term: &Term, style: Style) {
+    println!(
+        "{}（{}） / {}",
+        style.paint(terminal::BOLD, term.ja),
+        term.reading,
+        style.paint(terminal::BOLD, term.en)
+    );
+    println!("  {}", terminal::inline(term.localized_definition(), style));
+
+    println!("\n{}", tr!("glossary.sections"));
+    for id in &term.sections {
+        let Some(section) = section::find(id) else { continue };
+        let target = match first_mention(section, term) {
+            Some(anchor) if !anchor.is_empty() => format!("{}#{}", id, anchor),
+            _ => id.to_string(),
+        };
+        println!("  {:<13}cargo run -- doc {}", id, target);
+    }
+}
+
+fn print_list(terms: &[Term], style: Style) {
+    let rows = terms
+        .iter()
+        .map(|term| vec![term.ja.to_string(), term.reading.to_string(), term.en.to_string(), term.sections.join(" ")])
+        .collect();
+    let table = Block::Table {
+        header: vec![
+            tr!("glossary.col.ja").to_string(),
+            tr!("glossary.col.reading").to_string(),
+            tr!("glossary.col.en").to_string(),
+            tr!("glossary.col.sections").to_string(),
+        ],
+        aligns: vec![Align::Left; 4],
+        rows,
+    };
+    println!("{}\n", tr!("glossary.title", terms.len()));
+    print!("{}", terminal::render_blocks(&[table], style));
+    println!("\n{}", tr!("glossary.usage"));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn names(found: &[&Term]) -> Vec<&'static str> {
+        found.iter().map(|term| term.ja).collect()
+    }
+
+    #[test]
+    fn rejects_invalid_lines() {
+        assert!(parse("所有権 | しょゆうけん | ownership").is_err());
+        assert!(parse("所有権 | しょゆうけん | ownership | a | b | no_such_section").is_err());
+        assert_eq!(parse("# コメント\n\n所有権 | しょゆうけん | ownership | a | b | ownership").unwrap().len(), 1);
+    }
+
+    #[test]
+    fn looks_up_in_either_language() {
+        let terms = load().unwrap();
+        assert_eq!(names(&lookup(&terms, "借用"))[0], "借用");
+        assert_eq!(names(&lookup(&terms, "Borrowing"))[0], "借用");
+        assert_eq!(names(&lookup(&terms, "trait bound"))[0], "トレイト境界");
+        // 読み（カタカナでもよい）と、入力の誤り
+        assert_eq!(names(&lookup(&terms, "シャクヨウ"))[0], "借用");
+        assert_eq!(names(&lookup(&terms, "owenrship"))[0], "所有権");
+        assert_eq!(names(&lookup(&terms, "しゃどういんぐ"))[0], "シャドーイング");
+        // 前方一致は完全一致の後に並ぶ
+        let found = names(&lookup(&terms, "トレイト"));
+        assert_eq!(found[0], "トレイト");
+        assert!(found.contains(&"トレイト境界"));
+        assert!(lookup(&terms, "xyzzy").is_empty());
+    }
+
+    #[test]
+    fn finds_whole_english_words() {
+        assert_eq!(find_word("The Borrowing rules", "borrowing"), Some(4..13));
+        assert_eq!(find_word("two structs here", "struct"), Some(4..11));
+        assert_eq!(find_word("destructure", "struct"), None);
+        assert_eq!(find_word("所有権 ownership", "ownership"), Some(10..19));
+    }
+
+    #[test]
+    fn highlights_first_mention_outside_code() {
+        let terms = load().unwrap();
+        let blocks = markdown::parse("## 参照\n\n`参照`ではなく可変参照と参照\n\n- 参照とスライス\n");
+        let (annotated, found) = annotate(&blocks, &terms, Lang::Ja);
+
+        assert_eq!(annotated[0], blocks[0]);
+        assert_eq!(annotated[1], Block::Paragraph { lines: vec!["`参照`ではなく**可変参照**と**参照**".to_string()] });
+        let Block::List { items } = &annotated[2] else { panic!() };
+        assert_eq!(items[0].text, "参照と**スライス**");
+        assert_eq!(names(&found), ["可変参照", "参照", "スライス"]);
+    }
+
+    // すべての用語がREADMEに出てきて、一覧のセクションに実際に含まれていること
+    #[test]
+    fn every_term_appears_in_its_sections() {
+        let terms = load().unwrap();
+        for term in &terms {
+            assert!(!term.sections.is_empty(), "{}", term.ja);
+            assert_eq!(lookup(&terms, term.en)[0], term, "{}", term.en);
+            for id in &term.sections {
+                let section = section::find(id).unwrap();
+                assert!(section.doc_pages()[0].content.contains(term.ja), "{}: {}", id, term.ja);
+                assert!(first_mention(section, term).is_some(), "{}: {}", id, term.ja);
+            }
+        }
+    }
+}
@@ -0,0 +1,234 @@
+// デモの出力の英訳
+// デモのコードは学習用の例なので、日本語の文字列をそのまま`writeln!`で出力します。
+// 英語で表示するときは出力を1行ずつ`demo.en.txt`のパターンと照合して置き換えます
+//
+// パターンの書き方（`{}`はどんな文字列にも一致し、英語側の`{}`に順番に入る）:
+//
+//   xの値: {} => value of x: {}
+
+use crate::section;
+use std::io::{self, Write};
+use std::sync::OnceLock;
+
+const CATALOG: &str = include_str!("demo.en.txt");
+
+// 1. パターン
+#[derive(Debug)]
+pub(crate) struct Pattern {
+    pub(crate) ja: String,
+    pub(crate) en: String,
+}
+
+pub(crate) fn parse_patterns(text: &str) -> Result<Vec<Pattern>, String> {
+    let mut patterns = Vec::new();
+    for (i, line) in text.lines().enumerate() {
+        if line.trim().is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let Some((ja, en)) = line.split_once(" => ") else {
+            return Err(format!("{}行目: `日本語 => 英語`の形式ではありません", i + 1));
+        };
+        patterns.push(Pattern { ja: ja.to_string(), en: en.to_string() });
+    }
+    Ok(patterns)
+}
+
+// カタログのパターンと、各デモの見出し（`=== 変数と可変性 ===`）
+fn patterns() -> &'static [Pattern] {
+    static PATTERNS: OnceLock<Vec<Pattern>> = OnceLock::new();
+    PATTERNS.get_or_init(|| {
+        let mut patterns = parse_patterns(CATALOG).unwrap_or_default();
+        for section in section::all() {
+            for demo in section.demos() {
+                patterns.push(Pattern {
+                    ja: format!("=== {} ===", demo.title),
+                    en: format!("=== {} ===", demo.title_en),
+                });
+            }
+        }
+        patterns
+    })
+}
+
+// `{}`を含むパターンに行全体が一致すれば、`{}`に当たる部分を返す
+fn match_pattern<'a>(pattern: &str, line: &'a str) -> Option<Vec<&'a str>> {
+    let mut parts = pattern.split("{}");
+    let first = parts.next().unwrap_or("");
+    let rest = line.strip_prefix(first)?;
+    let literals: Vec<&str> = parts.collect();
+    let mut captures = Vec::new();
+    capture(&literals, rest, &mut captures).then_some(captures)
+}
+
+// 残りのリテラルを順に探す（一致しなければ、より後ろの位置で探し直す）
+fn capture<'a>(literals: &[&str], text: &'a str, captures: &mut Vec<&'a str>) -> bool {
+    let Some((literal, rest)) = literals.split_first() else {
+        return text.is_empty();
+    };
+    if rest.is_empty() && literal.is_empty() {
+        captures.push(text);
+        return true;
+    }
+    for (index, _) in text.match_indices(literal) {
+        captures.push(&text[..index]);
+        if capture(rest, &text[index + literal.len()..], captures) {
+            return true;
+        }
+        captures.pop();
+    }
+    false
+}
+
+// 一致するパターンのうち、固定部分が最も長いものを使う
+pub fn translate_line(line: &str) -> String {
+    let best = patterns()
+        .iter()
+        .filter_map(|pattern| match_pattern(&pattern.ja, line).map(|captures| (pattern, captures)))
+        .max_by_key(|(pattern, _)| pattern.ja.len() - 2 * pattern.ja.matches("{}").count());
+
+    match best {
+        Some((pattern, captures)) => {
+            let args: Vec<&dyn std::fmt::Display> = captures.iter().map(|c| c as &dyn std::fmt::Display).collect();
+            super::format(&pattern.en, &args)
+        }
+        None => line.to_string(),
+    }
+}
+
+// 2. 出力の置き換え
+// 改行までをためておき、1行ずつ英訳して書き込む
+pub struct Translator<'a> {
+    inner: &'a mut dyn Write,
+    line: Vec<u8>,
+}
+
+impl<'a> Translator<'a> {
+    pub fn new(inner: &'a mut dyn Write) -> Translator<'a> {
+        Translator { inner, line: Vec::new() }
+    }
+
+    fn write_line(&mut self) -> io::Result<()> {
+        let text = String::from_utf8_lossy(&self.line).into_owned();
+        self.line.clear();
+        self.inner.write_all(translate_line(&text).as_bytes())
+    }
+
+    // 改行で終わっていない最後の行も書き出す
+    pub fn finish(mut self) -> io::Result<()> {
+        if !self.line.is_empty() {
+            self.write_line()?;
+        }
+        self.inner.flush()
+    }
+}
+
+impl Write for Translator<'_> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        for &byte in buf {
+            if byte == b'\n' {
+                self.write_line()?;
+                self.inner.write_all(b"\n")?;
+            } else {
+                self.line.push(byte);
+            }
+        }
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+// 表示する言語に合わせてデモを実行する（英語なら出力を英訳する）
+pub fn run(out: &mut dyn Write, f: impl FnOnce(&mut dyn Write) -> io::Result<()>) -> io::Result<()> {
+    match super::lang() {
+        super::Lang::Ja => f(out),
+        super::Lang::En => {
+            let mut translator = Translator::new(out);
+            f(&mut translator)?;
+            translator.finish()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    #[test]
+    fn matches_placeholders() {
+        assert_eq!(match_pattern("xの値: {}", "xの値: 5"), Some(vec!["5"]));
+        assert_eq!(match_pattern("{} の平均点: {}", "田中 の平均点: 87.67"), Some(vec!["田中", "87.67"]));
+        assert_eq!(match_pattern("({}, {})へ移動", "(10, 20)へ移動"), Some(vec!["10", "20"]));
+        assert_eq!(match_pattern("xの値: {}", "yの値: 5"), None);
+        assert_eq!(match_pattern("発射！", "発射！"), Some(vec![]));
+    }
+
+    #[test]
+    fn translates_whole_lines() {
+        let mut output = Vec::new();
+        let mut translator = Translator::new(&mut output);
+        write!(translator, "\n=== 変数と可変性 ===\nxの値: 5\nno match").unwrap();
+        translator.finish().unwrap();
+        assert_eq!(String::from_utf8(output).unwrap(), "\n=== Variables and Mutability ===\nValue of x: 5\nno match");
+    }
+
+    // デモのソースコードで`writeln!`に渡している日本語の文字列が、すべて英訳されていること
+    #[test]
+    fn every_demo_message_is_translated() {
+        let patterns = parse_patterns(CATALOG).unwrap();
+        let known: HashSet<&str> = patterns.iter().map(|p| p.ja.as_str()).collect();
+        let mut untranslated = Vec::new();
+
+        for section in section::all() {
+            let source = section.source();
+            for (i, line) in source.content.lines().enumerate() {
+                for message in format_strings(line) {
+                    for piece in message.split('\n') {
+                        if !piece.is_ascii() && !known.contains(piece) {
+                            untranslated.push(format!("{}:{}: {}", source.path, i + 1, piece));
+                        }
+                    }
+                }
+            }
+        }
+        assert!(untranslated.is_empty(), "英訳されていないデモの出力:\n{}", untranslated.join("\n"));
+
+        for pattern in &patterns {
+            assert_eq!(pattern.ja.matches("{}").count(), pattern.en.matches("{}").count(), "{}", pattern.ja);
+        }
+    }
+
+    // `writeln!(out, "...")`の文字列を、`{:?}`などを`{}`にそろえて取り出す
+    fn format_strings(line: &str) -> Vec<String> {
+        let mut messages = Vec::new();
+        let parts = line.split("write!(out, \"").skip(1).chain(line.split("writeln!(out, \"").skip(1));
+        for part in parts {
+            let mut message = String::new();
+            let mut chars = part.chars();
+            while let Some(c) = chars.next() {
+                match c {
+                    '"' => break,
+                    '\\' => match chars.next() {
+                        Some('n') => message.push('\n'),
+                        Some(other) => message.push(other),
+                        None => {}
+                    },
+                    '{' => {
+                        for c in chars.by_ref() {
+                            if c == '}' {
+                                break;
+                            }
+                        }
+                        message.push_str("{}");
+                    }
+                    other => message.push(other),
+                }
+            }
+            messages.push(message);
+        }
+        messages
+    }
+}
@@ -0,0 +1,237 @@
+// 表示言語の切り替え（日本語・英語）
+// CLIのメッセージは`ja.txt`と`en.txt`のカタログにIDをキーとして書かれています
+//
+// カタログの書き方（1行に1件、`#`で始まる行はコメント）:
+//
+//   doc.unknown_section = 不明なセクション: {}    ← `{}`には引数が順番に入る
+//
+// 値の前後の空白は取り除かれます。改行は`\n`と書きます。
+
+pub mod demo;
+
+use std::collections::HashMap;
+use std::env;
+use std::fmt::Display;
+use std::sync::OnceLock;
+
+// 1. 言語
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Lang {
+    Ja,
+    En,
+}
+
+impl Lang {
+    pub fn parse(name: &str) -> Option<Lang> {
+        match name {
+            "ja" => Some(Lang::Ja),
+            "en" => Some(Lang::En),
+            _ => None,
+        }
+    }
+
+    // `--lang`に指定する名前
+    pub fn code(self) -> &'static str {
+        match self {
+            Lang::Ja => "ja",
+            Lang::En => "en",
+        }
+    }
+
+    // `LANG=en_US.UTF-8`のようなロケール名から判定する（日本語・英語以外や未設定は日本語）
+    fn from_locale(locale: &str) -> Lang {
+        if locale.starts_with("en") {
+            Lang::En
+        } else {
+            Lang::Ja
+        }
+    }
+
+    // POSIXと同じく、LC_ALL、LC_MESSAGES、LANGの順に見る
+    pub fn detect() -> Lang {
+        ["LC_ALL", "LC_MESSAGES", "LANG"]
+            .iter()
+            .filter_map(|name| env::var(name).ok())
+            .find(|value| !value.is_empty())
+            .map_or(Lang::Ja, |locale| Lang::from_locale(&locale))
+    }
+}
+
+static CURRENT: OnceLock<Lang> = OnceLock::new();
+
+// 起動時に一度だけ設定する（設定されていなければ日本語）
+pub fn set_lang(lang: Lang) {
+    CURRENT.set(lang).ok();
+}
+
+pub fn lang() -> Lang {
+    CURRENT.get().copied().unwrap_or(Lang::Ja)
+}
+
+// 日本語と英語の組から、表示する言語の方を選ぶ
+pub fn pick<T>(ja: T, en: T) -> T {
+    match lang() {
+        Lang::Ja => ja,
+        Lang::En => en,
+    }
+}
+
+// 2. メッセージカタログ
+const JA: &str = include_str!("ja.txt");
+const EN: &str = include_str!("en.txt");
+
+fn parse_catalog(text: &'static str) -> Result<Vec<(&'static str, String)>, String> {
+    let mut entries = Vec::new();
+    for (i, line) in text.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let Some((id, value)) = line.split_once(" = ") else {
+            return Err(format!("{}行目: `ID = メッセージ`の形式ではありません", i + 1));
+        };
+        entries.push((id.trim(), value.trim().replace("\\n", "\n")));
+    }
+    Ok(entries)
+}
+
+fn catalog(lang: Lang) -> &'static HashMap<&'static str, String> {
+    static CATALOGS: OnceLock<[HashMap<&'static str, String>; 2]> = OnceLock::new();
+    let catalogs = CATALOGS.get_or_init(|| {
+        // カタログの形式はテストで確認しているので、ここでは読めた行だけを使う
+        let load = |text| parse_catalog(text).unwrap_or_default().into_iter().collect();
+        [load(JA), load(EN)]
+    });
+    &catalogs[lang as usize]
+}
+
+// IDに対応するメッセージ（英語になければ日本語、どちらにもなければIDをそのまま返す）
+pub fn text(id: &'static str) -> &'static str {
+    catalog(lang())
+        .get(id)
+        .or_else(|| catalog(Lang::Ja).get(id))
+        .map_or(id, String::as_str)
+}
+
+// `{}`を引数で順番に置き換える
+pub fn format(template: &str, args: &[&dyn Display]) -> String {
+    let mut result = String::with_capacity(template.len());
+    let mut args = args.iter();
+    let mut rest = template;
+    while let Some(index) = rest.find("{}") {
+        result.push_str(&rest[..index]);
+        match args.next() {
+            Some(arg) => result.push_str(&arg.to_string()),
+            None => result.push_str("{}"),
+        }
+        rest = &rest[index + 2..];
+    }
+    result.push_str(rest);
+    result
+}
+
+/// カタログのメッセージを取り出す（引数があれば`{}`に埋め込んだ`String`を返す）
+///
+/// ```ignore
+/// println!("{}", tr!("help.title"));
+/// println!("{}", tr!("common.unknown_section", name));
+/// ```
+#[macro_export]
+macro_rules! tr {
+    ($id:literal) => {
+        $crate::i18n::text($id)
+    };
+    ($id:literal, $($arg:expr),+ $(,)?) => {
+        $crate::i18n::format($crate::i18n::text($id), &[$(&$arg as &dyn std::fmt::Display),+])
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeSet;
+    use std::fs;
+    use std::path::Path;
+
+    #[test]
+    fn detects_language_from_locale() {
+        assert_eq!(Lang::from_locale("en_US.UTF-8"), Lang::En);
+        assert_eq!(Lang::from_locale("ja_JP.UTF-8"), Lang::Ja);
+        assert_eq!(Lang::from_locale("C.UTF-8"), Lang::Ja);
+        assert_eq!(Lang::parse("en"), Some(Lang::En));
+        assert_eq!(Lang::parse("fr"), None);
+    }
+
+    #[test]
+    fn formats_arguments_in_order() {
+        assert_eq!(format("{}と{}", &[&1, &"b"]), "1とb");
+        assert_eq!(format("{}: {}", &[&"x"]), "x: {}");
+    }
+
+    fn placeholders(text: &str) -> usize {
+        text.matches("{}").count()
+    }
+
+    // 英語のカタログに、日本語のすべてのメッセージが同じ数の引数で翻訳されていること
+    #[test]
+    fn every_message_is_translated() {
+        let ja = parse_catalog(JA).unwrap();
+        let en = parse_catalog(EN).unwrap();
+
+        let mut ids = BTreeSet::new();
+        for (id, _) in &ja {
+            assert!(ids.insert(*id), "ja.txt: IDが重複しています: {}", id);
+        }
+        let en_ids: BTreeSet<&str> = en.iter().map(|(id, _)| *id).collect();
+        assert_eq!(en_ids.len(), en.len(), "en.txt: IDが重複しています");
+
+        let untranslated: Vec<&&str> = ids.difference(&en_ids).collect();
+        let unknown: Vec<&&str> = en_ids.difference(&ids).collect();
+        assert!(untranslated.is_empty(), "英語に翻訳されていないメッセージ: {:?}", untranslated);
+        assert!(unknown.is_empty(), "日本語のカタログにないメッセージ: {:?}", unknown);
+
+        let en: HashMap<&str, &String> = en.iter().map(|(id, text)| (*id, text)).collect();
+        for (id, text) in &ja {
+            assert_eq!(placeholders(text), placeholders(en[id]), "{}: 引数の数が違います", id);
+        }
+    }
+
+    // ソースコードの`tr!`で使っているIDが、すべてカタログにあること
+    #[test]
+    fn every_used_id_exists() {
+        let ja: HashMap<&str, String> = parse_catalog(JA).unwrap().into_iter().collect();
+        let src = Path::new(env!("CARGO_MANIFEST_DIR")).join("src");
+        let mut missing = Vec::new();
+        for path in rust_files(&src) {
+            let content = fs::read_to_string(&path).unwrap();
+            for (i, line) in content.lines().enumerate() {
+                for (start, _) in line.match_indices("tr!(\"") {
+                    // include_str!(...)などは除く
+                    let before = line[..start].chars().next_back();
+                    if before.is_some_and(|c| c.is_alphanumeric() || c == '_') {
+                        continue;
+                    }
+                    let part = &line[start + 5..];
+                    let id = &part[..part.find('"').unwrap()];
+                    if !ja.contains_key(id) {
+                        missing.push(format!("{}:{}: {}", path.display(), i + 1, id));
+                    }
+                }
+            }
+        }
+        assert!(missing.is_empty(), "カタログにないID:\n{}", missing.join("\n"));
+    }
+
+    fn rust_files(dir: &Path) -> Vec<std::path::PathBuf> {
+        let mut files = Vec::new();
+        for entry in fs::read_dir(dir).unwrap() {
+            let path = entry.unwrap().path();
+            if path.is_dir() {
+                files.extend(rust_files(&path));
+            } else if path.extension().is_some_and(|ext| ext == "rs") {
+                files.push(path);
+            }
+        }
+        files
+    }
+}
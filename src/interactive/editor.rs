@@ -0,0 +1,371 @@
+// 対話モードの行編集
+// 端末では入力を1キーずつ読み（rawモード）、カーソルの移動・文字の削除・履歴の呼び出しができるようにします
+// 端末でない場合（パイプやファイルからの入力）や、`stty`で端末を設定できない場合は1行ずつそのまま読みます
+
+use crate::markdown;
+use std::io::{self, BufRead, IsTerminal, Read, Write};
+use std::process::{Command, Stdio};
+
+// 1. キー入力
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Key {
+    Char(char),
+    Enter,
+    Backspace,
+    Delete,
+    Left,
+    Right,
+    Home,        // ←の端（Homeキー、Ctrl-A）
+    End,         // →の端（Endキー、Ctrl-E）
+    Up,          // 1つ前の履歴
+    Down,        // 1つ後の履歴
+    ClearBefore, // Ctrl-U: カーソルより前を削除
+    Cancel,      // Ctrl-C: 入力中の行を取り消す
+    Eof,         // Ctrl-D: 空の行なら終了
+    Unknown,
+}
+
+// 1キー分のバイト列を読む（入力の終わりならNone）
+pub fn read_key(input: &mut impl Read) -> io::Result<Option<Key>> {
+    let Some(byte) = read_byte(input)? else {
+        return Ok(None);
+    };
+    let key = match byte {
+        b'\r' | b'\n' => Key::Enter,
+        0x7f | 0x08 => Key::Backspace,
+        0x01 => Key::Home,
+        0x05 => Key::End,
+        0x02 => Key::Left,
+        0x06 => Key::Right,
+        0x10 => Key::Up,
+        0x0e => Key::Down,
+        0x15 => Key::ClearBefore,
+        0x03 => Key::Cancel,
+        0x04 => Key::Eof,
+        0x1b => read_escape(input)?,
+        byte if byte < 0x20 => Key::Unknown,
+        byte => read_char(input, byte)?,
+    };
+    Ok(Some(key))
+}
+
+fn read_byte(input: &mut impl Read) -> io::Result<Option<u8>> {
+    let mut buf = [0u8; 1];
+    loop {
+        match input.read(&mut buf) {
+            Ok(0) => return Ok(None),
+            Ok(_) => return Ok(Some(buf[0])),
+            Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+            Err(e) => return Err(e),
+        }
+    }
+}
+
+// `ESC [ A`（矢印キー）や`ESC [ 3 ~`（Delete）などのエスケープシーケンス
+fn read_escape(input: &mut impl Read) -> io::Result<Key> {
+    if !matches!(read_byte(input)?, Some(b'[' | b'O')) {
+        return Ok(Key::Unknown);
+    }
+    let mut number = String::new();
+    while let Some(byte) = read_byte(input)? {
+        let key = match byte {
+            b'0'..=b'9' | b';' => {
+                number.push(byte as char);
+                continue;
+            }
+            b'A' => Key::Up,
+            b'B' => Key::Down,
+            b'C' => Key::Right,
+            b'D' => Key::Left,
+            b'H' => Key::Home,
+            b'F' => Key::End,
+            b'~' => match number.as_str() {
+                "1" | "7" => Key::Home,
+                "4" | "8" => Key::End,
+                "3" => Key::Delete,
+                _ => Key::Unknown,
+            },
+            _ => Key::Unknown,
+        };
+        return Ok(key);
+    }
+    Ok(Key::Unknown)
+}
+
+// UTF-8の先頭のバイトから、残りのバイトを読んで1文字にする
+fn read_char(input: &mut impl Read, first: u8) -> io::Result<Key> {
+    let len = match first {
+        0xc0..=0xdf => 2,
+        0xe0..=0xef => 3,
+        0xf0..=0xf7 => 4,
+        _ => 1,
+    };
+    let mut bytes = vec![first];
+    while bytes.len() < len {
+        match read_byte(input)? {
+            Some(byte) => bytes.push(byte),
+            None => break,
+        }
+    }
+    Ok(std::str::from_utf8(&bytes)
+        .ok()
+        .and_then(|text| text.chars().next())
+        .map_or(Key::Unknown, Key::Char))
+}
+
+// 2. 編集中の行
+#[derive(Debug, PartialEq)]
+pub enum Action {
+    Edit,
+    Submit(String),
+    Cancel,
+    Eof,
+}
+
+#[derive(Debug, Default)]
+pub struct Line {
+    chars: Vec<char>,
+    cursor: usize,         // 文字単位の位置
+    recall: Option<usize>, // 呼び出している履歴の番号（Noneなら入力中の行）
+    draft: Vec<char>,      // 履歴を呼び出す前に入力していた行
+}
+
+impl Line {
+    pub fn apply(&mut self, key: Key, history: &[String]) -> Action {
+        match key {
+            Key::Char(c) => {
+                self.chars.insert(self.cursor, c);
+                self.cursor += 1;
+            }
+            Key::Backspace if self.cursor > 0 => {
+                self.cursor -= 1;
+                self.chars.remove(self.cursor);
+            }
+            Key::Delete if self.cursor < self.chars.len() => {
+                self.chars.remove(self.cursor);
+            }
+            Key::Left => self.cursor = self.cursor.saturating_sub(1),
+            Key::Right => self.cursor = (self.cursor + 1).min(self.chars.len()),
+            Key::Home => self.cursor = 0,
+            Key::End => self.cursor = self.chars.len(),
+            Key::ClearBefore => {
+                self.chars.drain(..self.cursor);
+                self.cursor = 0;
+            }
+            Key::Up => {
+                let index = match self.recall {
+                    Some(i) => Some(i.saturating_sub(1)),
+                    None => history.len().checked_sub(1),
+                };
+                if let Some(i) = index {
+                    if self.recall.is_none() {
+                        self.draft = self.chars.clone();
+                    }
+                    self.show(history[i].chars().collect(), Some(i));
+                }
+            }
+            Key::Down => match self.recall {
+                Some(i) if i + 1 < history.len() => {
+                    self.show(history[i + 1].chars().collect(), Some(i + 1))
+                }
+                Some(_) => self.show(self.draft.clone(), None),
+                None => {}
+            },
+            Key::Enter => return Action::Submit(self.text()),
+            Key::Cancel => return Action::Cancel,
+            // 入力がなければ終了、あればDeleteと同じ
+            Key::Eof if self.chars.is_empty() => return Action::Eof,
+            Key::Eof => return self.apply(Key::Delete, history),
+            Key::Backspace | Key::Delete | Key::Unknown => {}
+        }
+        Action::Edit
+    }
+
+    fn show(&mut self, chars: Vec<char>, recall: Option<usize>) {
+        self.cursor = chars.len();
+        self.chars = chars;
+        self.recall = recall;
+    }
+
+    pub fn text(&self) -> String {
+        self.chars.iter().collect()
+    }
+
+    // 行頭に戻ってプロンプトと入力を書き直し、カーソルの後ろの文字の幅だけ左に戻る
+    pub fn render(&self, prompt: &str) -> String {
+        let after: String = self.chars[self.cursor..].iter().collect();
+        let back = match markdown::display_width(&after) {
+            0 => String::new(),
+            width => format!("\x1b[{}D", width),
+        };
+        format!("\r{}{}\x1b[K{}", prompt, self.text(), back)
+    }
+}
+
+// 3. 1行の読み込み
+// プロンプトを表示して1行読む（入力の終わりならNone）
+pub fn read_line(prompt: &str, history: &[String]) -> io::Result<Option<String>> {
+    let stdin = io::stdin();
+    if stdin.is_terminal()
+        && let Some(_raw) = RawMode::enable()
+    {
+        return edit_line(&mut stdin.lock(), &mut io::stdout(), prompt, history);
+    }
+
+    print!("{}", prompt);
+    io::stdout().flush()?;
+    let mut line = String::new();
+    if stdin.lock().read_line(&mut line)? == 0 {
+        return Ok(None);
+    }
+    Ok(Some(line.trim_end_matches(['\r', '\n']).to_string()))
+}
+
+// rawモードでは改行で行頭に戻らないので、`\r\n`を出力する
+fn edit_line(
+    input: &mut impl Read,
+    out: &mut impl Write,
+    prompt: &str,
+    history: &[String],
+) -> io::Result<Option<String>> {
+    let mut line = Line::default();
+    write!(out, "{}", line.render(prompt))?;
+    out.flush()?;
+    while let Some(key) = read_key(input)? {
+        match line.apply(key, history) {
+            Action::Edit => {}
+            Action::Submit(text) => {
+                write!(out, "\r\n")?;
+                return Ok(Some(text));
+            }
+            Action::Cancel => {
+                write!(out, "^C\r\n")?;
+                line = Line::default();
+            }
+            Action::Eof => return Ok(None),
+        }
+        write!(out, "{}", line.render(prompt))?;
+        out.flush()?;
+    }
+    Ok(None)
+}
+
+// `stty`で端末をrawモード（エコーなし、1キーずつ読む）にし、終わったら元の設定に戻す
+struct RawMode {
+    saved: String,
+}
+
+impl RawMode {
+    fn enable() -> Option<RawMode> {
+        let saved = stty(&["-g"])?.trim().to_string();
+        stty(&["raw", "-echo"])?;
+        Some(RawMode { saved })
+    }
+}
+
+impl Drop for RawMode {
+    fn drop(&mut self) {
+        stty(&[&self.saved]);
+    }
+}
+
+// `stty`は標準入力の端末を設定するので、標準入力を引き継いで実行する
+fn stty(args: &[&str]) -> Option<String> {
+    let output = Command::new("stty")
+        .args(args)
+        .stdin(Stdio::inherit())
+        .stderr(Stdio::null())
+        .output()
+        .ok()?;
+    output
+        .status
+        .success()
+        .then(|| String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn keys(bytes: &[u8]) -> Vec<Key> {
+        let mut input = bytes;
+        std::iter::from_fn(|| read_key(&mut input).unwrap()).collect()
+    }
+
+    #[test]
+    fn reads_keys_and_escape_sequences() {
+        assert_eq!(
+            keys("aあ\x1b[A\x1b[D\x1b[3~\x1bOH\x7f\r".as_bytes()),
+            [
+                Key::Char('a'),
+                Key::Char('あ'),
+                Key::Up,
+                Key::Left,
+                Key::Delete,
+                Key::Home,
+                Key::Backspace,
+                Key::Enter
+            ]
+        );
+        assert_eq!(
+            keys(b"\x03\x04\x15"),
+            [Key::Cancel, Key::Eof, Key::ClearBefore]
+        );
+    }
+
+    #[test]
+    fn edits_in_the_middle_of_the_line() {
+        let mut line = Line::default();
+        for key in [
+            Key::Char('d'),
+            Key::Char('c'),
+            Key::Home,
+            Key::Char('x'),
+            Key::Delete,
+            Key::Char('o'),
+            Key::End,
+        ] {
+            assert_eq!(line.apply(key, &[]), Action::Edit);
+        }
+        assert_eq!(line.text(), "xoc");
+        line.apply(Key::Left, &[]);
+        line.apply(Key::Backspace, &[]);
+        assert_eq!(line.text(), "xc");
+        // カーソルの後ろの「c」の幅だけ左に戻る
+        assert_eq!(line.render("> "), "\r> xc\x1b[K\x1b[1D");
+        assert_eq!(
+            line.apply(Key::Enter, &[]),
+            Action::Submit("xc".to_string())
+        );
+    }
+
+    #[test]
+    fn recalls_history_and_restores_the_draft() {
+        let history = ["basics".to_string(), "doc #if式".to_string()];
+        let mut line = Line::default();
+        line.apply(Key::Char('b'), &history);
+        line.apply(Key::Up, &history);
+        assert_eq!(line.text(), "doc #if式");
+        line.apply(Key::Up, &history);
+        line.apply(Key::Up, &history);
+        assert_eq!(line.text(), "basics");
+        line.apply(Key::Down, &history);
+        line.apply(Key::Down, &history);
+        assert_eq!(line.text(), "b");
+        assert_eq!(line.apply(Key::Eof, &history), Action::Edit);
+        line.apply(Key::ClearBefore, &history);
+        assert_eq!(line.apply(Key::Eof, &history), Action::Eof);
+    }
+
+    #[test]
+    fn edit_line_cancels_and_submits() {
+        let mut out = Vec::new();
+        let text = edit_line(&mut "ab\x03cd\x1b[D\x7f\r".as_bytes(), &mut out, "> ", &[]).unwrap();
+        assert_eq!(text.as_deref(), Some("d"));
+        assert!(String::from_utf8(out).unwrap().contains("^C\r\n"));
+        assert_eq!(
+            edit_line(&mut "".as_bytes(), &mut Vec::new(), "> ", &[]).unwrap(),
+            None
+        );
+    }
+}
@@ -0,0 +1,305 @@
+// 対話モード
+// セクションを選んでデモの実行やドキュメントの表示を繰り返せるようにします
+// 不正な入力があってもメッセージを表示して続行し、`quit`かCtrl-Dでのみ終了します
+// 端末では、←→でのカーソルの移動や↑↓での履歴の呼び出しができます（`editor.rs`）
+
+mod editor;
+
+use crate::section::{self, Section};
+use crate::transcript::Format;
+use std::panic::{self, AssertUnwindSafe};
+use std::path::Path;
+
+// 1. 入力の処理結果
+#[derive(Debug, PartialEq)]
+enum Flow {
+    Continue,
+    Quit,
+}
+
+pub struct Repl<'a> {
+    current: Option<&'static dyn Section>,  // 選択中のセクション
+    history: Vec<String>,
+    docs_dir: Option<&'a Path>,
+}
+
+impl<'a> Repl<'a> {
+    pub fn new(docs_dir: Option<&'a Path>) -> Self {
+        Repl { current: None, history: Vec::new(), docs_dir }
+    }
+
+    // 2. 入力ループ
+    pub fn run(&mut self) {
+        println!("{}", tr!("interactive.title"));
+        print_sections();
+        println!("\n{}", tr!("interactive.welcome"));
+
+        loop {
+            println!();
+            let line = match editor::read_line(&format!("{}> ", self.prompt()), &self.history) {
+                Ok(Some(line)) => line,
+                Err(e) => {
+                    println!("{}", tr!("interactive.read_error", e));
+                    continue;
+                }
+                Ok(None) => {
+                    println!();
+                    break;
+                }
+            };
+            if self.execute(&line) == Flow::Quit {
+                break;
+            }
+        }
+    }
+
+    fn prompt(&self) -> String {
+        match self.current {
+            Some(section) => format!("rust-study/{}", section.id()),
+            None => "rust-study".to_string(),
+        }
+    }
+
+    // 1行分の入力を実行する（履歴の展開もここで行う）
+    fn execute(&mut self, line: &str) -> Flow {
+        let line = line.trim();
+        if line.is_empty() {
+            return Flow::Continue;
+        }
+
+        let line = match self.expand_history(line) {
+            Ok(line) => line,
+            Err(message) => {
+                println!("{}", message);
+                return Flow::Continue;
+            }
+        };
+        self.history.push(line.clone());
+
+        let words = split_words(&line);
+        let words: Vec<&str> = words.iter().map(String::as_str).collect();
+        self.dispatch(&words)
+    }
+
+    // `!!`は直前のコマンド、`!N`は履歴のN番目のコマンドに置き換える
+    fn expand_history(&self, line: &str) -> Result<String, String> {
+        let Some(rest) = line.strip_prefix('!') else {
+            return Ok(line.to_string());
+        };
+        let entry = if rest == "!" {
+            self.history.last()
+        } else {
+            rest.parse::<usize>()
+                .ok()
+                .and_then(|n| self.history.get(n.wrapping_sub(1)))
+        };
+        match entry {
+            Some(command) => {
+                println!("{}", command);
+                Ok(command.clone())
+            }
+            None => Err(tr!("interactive.not_in_history", line)),
+        }
+    }
+
+    // 3. コマンドの振り分け
+    fn dispatch(&mut self, words: &[&str]) -> Flow {
+        let (command, args) = (words[0], &words[1..]);
+        match command {
+            "quit" | "exit" | "q" => return Flow::Quit,
+            "help" | "?" => self.print_help(),
+            "history" => {
+                for (i, command) in self.history.iter().enumerate() {
+                    println!("  {:>3}  {}", i + 1, command);
+                }
+            }
+            "sections" => print_sections(),
+            "back" | ".." => {
+                if self.current.take().is_none() {
+                    println!("{}", tr!("interactive.already_top"));
+                }
+            }
+            "list" | "ls" => match (args.first(), self.current) {
+                (Some(name), _) => match section::find(name) {
+                    Some(section) => crate::cli::print_demos(section),
+                    None => println!("{}", tr!("common.unknown_section", name)),
+                },
+                (None, Some(section)) => crate::cli::print_demos(section),
+                (None, None) => print_sections(),
+            },
+            "doc" => self.show_doc(args),
+            "search" => crate::search::command(&to_strings(args)),
+            "run" => match self.current {
+                Some(section) => self.run_demos(section, args),
+                None => println!("{}", tr!("interactive.select_first")),
+            },
+            "all" => match self.current {
+                Some(section) => self.run_demos(section, &[]),
+                None => guard(|| crate::cli::run_all_sections(Format::Text, false)),
+            },
+            _ => self.select(words),
+        }
+        Flow::Continue
+    }
+
+    // コマンド以外の入力は、セクション一覧ではセクションの選択、
+    // セクション内ではデモの選択として扱う
+    fn select(&mut self, words: &[&str]) {
+        match self.current {
+            Some(section) => match section::find(words[0]) {
+                Some(other) if words.len() == 1 => self.enter(other),
+                _ => self.run_demos(section, words),
+            },
+            None => {
+                let found = match words[0].parse::<usize>() {
+                    Ok(n) => section::all().get(n.wrapping_sub(1)).copied(),
+                    Err(_) => section::find(words[0]),
+                };
+                match found {
+                    Some(section) if words.len() == 1 => self.enter(section),
+                    Some(section) => self.run_demos(section, &words[1..]),
+                    None => println!("{}", tr!("interactive.unknown_command", words[0])),
+                }
+            }
+        }
+    }
+
+    fn enter(&mut self, section: &'static dyn Section) {
+        self.current = Some(section);
+        println!();
+        crate::cli::print_demos(section);
+        println!("\n{}", tr!("interactive.section_hint"));
+    }
+
+    fn run_demos(&self, section: &dyn Section, patterns: &[&str]) {
+        let patterns = to_strings(patterns);
+        guard(|| crate::cli::run_section(section, &patterns, Format::Text, false));
+    }
+
+    fn show_doc(&self, args: &[&str]) {
+        let mut args = to_strings(args);
+        // セクション内では、セクション名を省略できる（`doc #見出し`や`doc stack-heap`）
+        if let Some(section) = self.current {
+            match args.first() {
+                Some(first) if first.starts_with('#') => args[0] = format!("{}{}", section.id(), first),
+                Some(first) if section::find(first.split('#').next().unwrap_or("")).is_some() => {}
+                _ => args.insert(0, section.id().to_string()),
+            }
+        }
+        crate::doc::command(&args, self.docs_dir);
+    }
+
+    fn print_help(&self) {
+        println!("{}", tr!("help.commands"));
+        if self.current.is_some() {
+            println!("  {}", tr!("interactive.cmd.demos"));
+            println!("  {}", tr!("interactive.cmd.section"));
+            println!("  {}", tr!("interactive.cmd.run"));
+            println!("  {}", tr!("interactive.cmd.doc_page"));
+            println!("  {}", tr!("interactive.cmd.back"));
+        } else {
+            println!("  {}", tr!("interactive.cmd.select"));
+            println!("  {}", tr!("interactive.cmd.all"));
+            println!("  {}", tr!("interactive.cmd.doc"));
+        }
+        println!("  {}", tr!("interactive.cmd.list"));
+        println!("  {}", tr!("interactive.cmd.sections"));
+        println!("  {}", tr!("interactive.cmd.search"));
+        println!("  {}", tr!("interactive.cmd.history"));
+        println!("  {}", tr!("interactive.cmd.rerun"));
+        println!("  {}", tr!("interactive.cmd.quit"));
+        println!("\n{}", tr!("interactive.keys"));
+    }
+}
+
+fn print_sections() {
+    println!("\n{}", tr!("help.sections"));
+    for (i, section) in section::all().iter().enumerate() {
+        let entry = tr!("help.section_entry", section.title(), section.topic_summary());
+        println!("  {}. {:<13}- {}", i + 1, section.id(), entry);
+    }
+}
+
+// デモがpanicしても対話モードは終了しない
+fn guard(f: impl FnOnce()) {
+    if panic::catch_unwind(AssertUnwindSafe(f)).is_err() {
+        println!("\n{}", tr!("interactive.panicked"));
+    }
+}
+
+fn to_strings(words: &[&str]) -> Vec<String> {
+    words.iter().map(|word| word.to_string()).collect()
+}
+
+// 空白で区切る（引用符で囲んだ部分は1語として扱う）
+fn split_words(line: &str) -> Vec<String> {
+    let mut words = Vec::new();
+    let mut word = String::new();
+    let mut quote: Option<char> = None;
+    let mut in_word = false;
+
+    for c in line.chars() {
+        match (quote, c) {
+            (Some(q), c) if c == q => quote = None,
+            (Some(_), c) => word.push(c),
+            (None, '\'' | '"') => {
+                quote = Some(c);
+                in_word = true;
+            }
+            (None, c) if c.is_whitespace() => {
+                if in_word {
+                    words.push(std::mem::take(&mut word));
+                    in_word = false;
+                }
+            }
+            (None, c) => {
+                word.push(c);
+                in_word = true;
+            }
+        }
+    }
+    if in_word {
+        words.push(word);
+    }
+    words
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn splits_words_with_quotes() {
+        assert_eq!(split_words("  search 'entry().or_insert'  x"), vec!["search", "entry().or_insert", "x"]);
+        assert_eq!(split_words("search \"所有権 借用\""), vec!["search", "所有権 借用"]);
+        assert_eq!(split_words("a ''"), vec!["a", ""]);
+    }
+
+    #[test]
+    fn navigates_sections_and_survives_bad_input() {
+        let mut repl = Repl::new(None);
+        assert_eq!(repl.execute("nonexistent command"), Flow::Continue);
+        assert_eq!(repl.execute("2"), Flow::Continue);
+        assert_eq!(repl.current.map(|s| s.id()), Some("ownership"));
+        assert_eq!(repl.execute("no_such_demo"), Flow::Continue);
+        assert_eq!(repl.execute("collections"), Flow::Continue);
+        assert_eq!(repl.current.map(|s| s.id()), Some("collections"));
+        assert_eq!(repl.execute("back"), Flow::Continue);
+        assert!(repl.current.is_none());
+        assert_eq!(repl.execute("quit"), Flow::Quit);
+    }
+
+    #[test]
+    fn expands_history() {
+        let mut repl = Repl::new(None);
+        repl.execute("basics");
+        repl.execute("back");
+        assert_eq!(repl.expand_history("!1"), Ok("basics".to_string()));
+        assert_eq!(repl.expand_history("!!"), Ok("back".to_string()));
+        assert!(repl.expand_history("!9").is_err());
+
+        repl.execute("!1");
+        assert_eq!(repl.history.last().map(String::as_str), Some("basics"));
+        assert_eq!(repl.current.map(|s| s.id()), Some("basics"));
+    }
+}
@@ -0,0 +1,500 @@
+// 型のメモリレイアウト（`layout [型]`）
+// プロジェクトの型について、`size_of`・`align_of`・フィールドのオフセット（`offset_of!`）と、
+// `Option<T>`にしても大きさが変わらないか（ニッチ最適化）を、実際にコンパイルされた値で表示します
+// `layout string`では、スタックにある`String`のポインタ・長さ・容量と、それが指すヒープのバッファを表示します
+//
+// 型は各セクションの`Section::layouts`で登録します（非公開の型やフィールドも、定義したモジュールの中なら参照できるため）
+
+use crate::markdown::{terminal, terminal::Style, Align, Block};
+use crate::section;
+use std::any;
+use std::mem;
+
+// 1. レイアウトの情報
+#[derive(Debug)]
+pub struct TypeLayout {
+    pub name: String,        // 型の名前（例: `ownership::Book`）
+    pub size: usize,
+    pub align: usize,
+    pub option_size: usize,  // `Option<T>`の大きさ（`size`と同じならニッチ最適化が効いている）
+    pub shape: Shape,
+}
+
+#[derive(Debug)]
+pub enum Shape {
+    Struct(Vec<Field>),   // 構造体のフィールド（宣言の順）
+    Enum(Vec<Variant>),   // 列挙型のバリアント
+    Opaque,               // 標準ライブラリの型など、中身を表示しないもの
+}
+
+#[derive(Debug)]
+pub struct Field {
+    pub name: &'static str,
+    pub ty: String,
+    pub offset: usize,
+    pub size: usize,
+}
+
+// 列挙型のバリアントと、持っているデータ（タプルとしての大きさ）
+#[derive(Debug)]
+pub struct Variant {
+    pub name: &'static str,
+    pub data: String,
+    pub size: usize,
+}
+
+impl TypeLayout {
+    pub fn of<T>(shape: Shape) -> TypeLayout {
+        TypeLayout {
+            name: type_name::<T>(),
+            size: mem::size_of::<T>(),
+            align: mem::align_of::<T>(),
+            option_size: mem::size_of::<Option<T>>(),
+            shape,
+        }
+    }
+
+    pub fn niche(&self) -> bool {
+        self.option_size == self.size
+    }
+}
+
+impl Field {
+    // フィールドの型と大きさは、フィールドを取り出す関数から求める（`field!`マクロから使う）
+    pub fn of<T, F>(name: &'static str, offset: usize, _get: fn(&T) -> &F) -> Field {
+        Field { name, ty: type_name::<F>(), offset, size: mem::size_of::<F>() }
+    }
+}
+
+impl Variant {
+    // `D`はバリアントのデータをタプルにした型（データがなければ`()`）
+    pub fn of<D>(name: &'static str) -> Variant {
+        Variant { name, data: type_name::<D>(), size: mem::size_of::<D>() }
+    }
+}
+
+// `field!(Book, title)`: フィールドのオフセット・型・大きさ
+macro_rules! field {
+    ($type:ty, $field:ident) => {
+        $crate::layout::Field::of(stringify!($field), std::mem::offset_of!($type, $field), |value: &$type| &value.$field)
+    };
+}
+pub(crate) use field;
+
+// `variants!(Message { Quit, Move { x: i32, y: i32 }, Write(String) })`: 列挙型の宣言と同じ形で書いたバリアント
+// 実際の列挙型とバリアントの過不足やフィールドの名前・型が食い違えば、コンパイルエラーになる
+macro_rules! variants {
+    ($type:ty { $($variant:ident $(($($tuple:tt)*))? $({$($named:tt)*})?),* $(,)? }) => {{
+        type Enum = $type;
+        // バリアントの過不足は、網羅的なmatchで確かめる
+        let _ = |value: &Enum| match value {
+            $(Enum::$variant { .. } => {})*
+        };
+        vec![$($crate::layout::variants!(@variant Enum, $variant $(($($tuple)*))? $({$($named)*})?)),*]
+    }};
+    (@variant $enum:ident, $variant:ident) => {{
+        let _: $enum = $enum::$variant;
+        $crate::layout::Variant::of::<()>(stringify!($variant))
+    }};
+    // タプルのバリアントは、フィールドの型を引数に取る関数として確かめる
+    (@variant $enum:ident, $variant:ident ($($ty:ty),* $(,)?)) => {{
+        let _: fn($($ty),*) -> $enum = $enum::$variant;
+        $crate::layout::Variant::of::<($($ty,)*)>(stringify!($variant))
+    }};
+    // 名前付きのフィールドは、すべてのフィールドを指定して値を作れることで確かめる
+    (@variant $enum:ident, $variant:ident { $($field:ident: $ty:ty),* $(,)? }) => {{
+        let _ = |$($field: $ty),*| $enum::$variant { $($field),* };
+        $crate::layout::Variant::of::<($($ty,)*)>(stringify!($variant))
+    }};
+}
+pub(crate) use variants;
+
+// `alloc::string::String`を`String`に、`hello_cargo::ownership::Book`を`ownership::Book`に短くする
+pub fn type_name<T>() -> String {
+    short_type_name(any::type_name::<T>())
+}
+
+fn short_type_name(full: &str) -> String {
+    let mut name = String::new();
+    let mut path = String::new();
+    for c in full.chars().chain(std::iter::once(' ')) {
+        if c.is_alphanumeric() || c == '_' || c == ':' {
+            path.push(c);
+            continue;
+        }
+        let mut segments: Vec<&str> = path.split("::").collect();
+        match segments[0] {
+            "core" | "alloc" | "std" => segments.drain(..segments.len() - 1),
+            first if first == env!("CARGO_CRATE_NAME") && segments.len() > 1 => segments.drain(..1),
+            _ => segments.drain(..0),
+        };
+        name.push_str(&segments.join("::"));
+        path.clear();
+        name.push(c);
+    }
+    name.pop();
+    name
+}
+
+// 標準ライブラリの型（比較のため）
+fn std_layouts() -> Vec<TypeLayout> {
+    vec![
+        TypeLayout::of::<String>(Shape::Opaque),
+        TypeLayout::of::<&str>(Shape::Opaque),
+        TypeLayout::of::<Vec<i32>>(Shape::Opaque),
+        TypeLayout::of::<&i32>(Shape::Opaque),
+        TypeLayout::of::<u32>(Shape::Opaque),
+    ]
+}
+
+// セクションの順に、最後に標準ライブラリの型
+pub fn all() -> Vec<TypeLayout> {
+    let mut layouts: Vec<TypeLayout> = section::all().iter().flat_map(|section| section.layouts()).collect();
+    layouts.extend(std_layouts());
+    layouts
+}
+
+// 型の名前で探す（`Book`・`ownership::Book`・`binarytree`のどれでもよい）
+fn find<'a>(layouts: &'a [TypeLayout], query: &str) -> Vec<&'a TypeLayout> {
+    let query = query.to_lowercase();
+    let exact: Vec<&TypeLayout> = layouts
+        .iter()
+        .filter(|layout| {
+            let name = layout.name.to_lowercase();
+            // ジェネリクスの引数を除いた、最後のパスの名前（`Box<a::B>`なら`box`）
+            let outer = name.split('<').next().unwrap_or(&name);
+            let base = outer.rsplit("::").next().unwrap_or(outer);
+            name == query || base == query
+        })
+        .collect();
+    if !exact.is_empty() {
+        return exact;
+    }
+    layouts.iter().filter(|layout| layout.name.to_lowercase().contains(&query)).collect()
+}
+
+// 2. 表示
+// 1バイトを1文字で表した図（フィールドごとにA、B、C…、パディングは`.`）
+const MAX_BAR_BYTES: usize = 64;
+
+fn byte_bar(size: usize, fields: &[Field]) -> Option<String> {
+    if size == 0 || size > MAX_BAR_BYTES {
+        return None;
+    }
+    let mut bar = vec!['.'; size];
+    for (i, field) in fields.iter().enumerate() {
+        let letter = char::from(b'A' + (i % 26) as u8);
+        for cell in bar.iter_mut().skip(field.offset).take(field.size) {
+            *cell = letter;
+        }
+    }
+    Some(bar.into_iter().collect())
+}
+
+// フィールドの間と末尾のパディング（オフセット, 大きさ）
+fn padding(size: usize, fields: &[&Field]) -> Vec<(usize, usize)> {
+    let mut gaps = Vec::new();
+    let mut end = 0;
+    for field in fields {
+        if field.offset > end {
+            gaps.push((end, field.offset - end));
+        }
+        end = end.max(field.offset + field.size);
+    }
+    if size > end {
+        gaps.push((end, size - end));
+    }
+    gaps
+}
+
+fn bytes(n: usize) -> String {
+    tr!("layout.bytes", n)
+}
+
+fn print_summary(layouts: &[TypeLayout], style: Style) {
+    let rows = layouts
+        .iter()
+        .map(|layout| {
+            vec![
+                layout.name.clone(),
+                layout.size.to_string(),
+                layout.align.to_string(),
+                layout.option_size.to_string(),
+                if layout.niche() { tr!("layout.yes") } else { tr!("layout.no") }.to_string(),
+            ]
+        })
+        .collect();
+    let table = Block::Table {
+        header: vec![
+            tr!("layout.col.type").to_string(),
+            tr!("layout.col.size").to_string(),
+            tr!("layout.col.align").to_string(),
+            tr!("layout.col.option").to_string(),
+            tr!("layout.col.niche").to_string(),
+        ],
+        aligns: vec![Align::Left, Align::Right, Align::Right, Align::Right, Align::Left],
+        rows,
+    };
+    println!("{}\n", tr!("layout.title"));
+    print!("{}", terminal::render_blocks(&[table], style));
+    println!("\n{}", tr!("layout.detail_hint"));
+    println!("{}", tr!("layout.string_hint"));
+}
+
+fn print_layout(layout: &TypeLayout, style: Style) {
+    println!("{}", style.paint(terminal::BOLD, &layout.name));
+    println!("  {}", tr!("layout.size_align", bytes(layout.size), bytes(layout.align)));
+    let niche = if layout.niche() { tr!("layout.niche_yes") } else { tr!("layout.niche_no") };
+    println!("  Option<{}>: {}  {}", layout.name, bytes(layout.option_size), style.paint(terminal::DIM, niche));
+
+    match &layout.shape {
+        Shape::Struct(fields) => print_fields(layout.size, fields, style),
+        Shape::Enum(variants) => print_variants(layout.size, variants, style),
+        Shape::Opaque => {}
+    }
+}
+
+fn print_fields(size: usize, fields: &[Field], style: Style) {
+    let mut sorted: Vec<&Field> = fields.iter().collect();
+    sorted.sort_by_key(|field| field.offset);
+    let letters: Vec<char> = (0..fields.len()).map(|i| char::from(b'A' + (i % 26) as u8)).collect();
+    let letter = |field: &Field| letters[fields.iter().position(|f| std::ptr::eq(f, field)).unwrap_or(0)];
+
+    let mut rows: Vec<(usize, Vec<String>)> = sorted
+        .iter()
+        .map(|field| {
+            let name = format!("{}  {}: {}", letter(field), field.name, field.ty);
+            (field.offset, vec![field.offset.to_string(), field.size.to_string(), name])
+        })
+        .collect();
+    for (offset, size) in padding(size, &sorted) {
+        rows.push((offset, vec![offset.to_string(), size.to_string(), format!(".  {}", tr!("layout.padding"))]));
+    }
+    rows.sort_by_key(|(offset, _)| *offset);
+
+    let table = Block::Table {
+        header: vec![
+            tr!("layout.col.offset").to_string(),
+            tr!("layout.col.size").to_string(),
+            tr!("layout.col.field").to_string(),
+        ],
+        aligns: vec![Align::Right, Align::Right, Align::Left],
+        rows: rows.into_iter().map(|(_, row)| row).collect(),
+    };
+    println!();
+    print!("{}", terminal::render_blocks(&[table], style));
+    if let Some(bar) = byte_bar(size, fields) {
+        println!("  [{}]", bar);
+    }
+    // 宣言と違う順に並べ替えられていれば、そのことを示す
+    if sorted.iter().zip(fields).any(|(a, b)| !std::ptr::eq(*a, b)) {
+        println!("  {}", style.paint(terminal::DIM, tr!("layout.reordered")));
+    }
+}
+
+fn print_variants(size: usize, variants: &[Variant], style: Style) {
+    let rows = variants
+        .iter()
+        .map(|variant| vec![variant.name.to_string(), variant.data.clone(), variant.size.to_string()])
+        .collect();
+    let table = Block::Table {
+        header: vec![
+            tr!("layout.col.variant").to_string(),
+            tr!("layout.col.data").to_string(),
+            tr!("layout.col.size").to_string(),
+        ],
+        aligns: vec![Align::Left, Align::Left, Align::Right],
+        rows,
+    };
+    println!();
+    print!("{}", terminal::render_blocks(&[table], style));
+
+    // 最大のデータより大きい分が、どのバリアントかを表すタグのための領域
+    let largest = variants.iter().map(|variant| variant.size).max().unwrap_or(0);
+    let tag = size.saturating_sub(largest);
+    println!("  {}", tr!("layout.largest", bytes(largest)));
+    if tag == 0 {
+        println!("  {}", style.paint(terminal::DIM, tr!("layout.tag_hidden")));
+    } else {
+        println!("  {}", style.paint(terminal::DIM, &tr!("layout.tag_extra", bytes(tag))));
+    }
+}
+
+// 3. Stringのスタックとヒープ
+// `String`がスタックに持つ3つの値（`as_ptr`・`len`・`capacity`で取り出せるもの）と、変数自体のアドレス
+// （3つの値が`String`の中でどの順に並ぶかは決められていないので、オフセットは表示しない）
+#[derive(Debug, PartialEq)]
+pub struct StringParts {
+    pub stack_address: usize,
+    pub heap_address: usize,
+    pub len: usize,
+    pub capacity: usize,
+}
+
+pub fn string_parts(s: &String) -> StringParts {
+    StringParts {
+        stack_address: s as *const String as usize,
+        heap_address: s.as_ptr() as usize,
+        len: s.len(),
+        capacity: s.capacity(),
+    }
+}
+
+// ヒープのバイトを1マスずつ表示する数
+const MAX_CELLS: usize = 16;
+
+fn print_string(text: &str, style: Style) {
+    // 長さと容量の違いが分かるよう、少し余裕を持って確保する
+    let mut s = String::with_capacity(text.len() + 3);
+    s.push_str(text);
+    let parts = string_parts(&s);
+
+    println!("{}", style.paint(terminal::DIM, &format!("let mut s = String::with_capacity({});  s.push_str({:?});", s.capacity(), text)));
+    println!("\n{}", style.paint(terminal::BOLD, &tr!("layout.stack", bytes(mem::size_of::<String>()), format!("{:#x}", parts.stack_address))));
+    let arrow = style.paint(terminal::CYAN, &format!("──▶ {}", tr!("layout.heap_label")));
+    println!("  ptr = {:#x}  {}", parts.heap_address, arrow);
+    println!("  len = {}", parts.len);
+    println!("  cap = {}", parts.capacity);
+    println!("  {}", style.paint(terminal::DIM, tr!("layout.stack_order")));
+
+    println!("\n{}", style.paint(terminal::BOLD, &tr!("layout.heap", bytes(parts.capacity), format!("{:#x}", parts.heap_address))));
+    let cells = parts.capacity.min(MAX_CELLS);
+    let mut top = String::from("  ┌");
+    let mut middle = String::from("  │");
+    let mut bottom = String::from("  └");
+    for i in 0..cells {
+        let cell = match s.as_bytes().get(i) {
+            Some(&b) if b.is_ascii_graphic() || b == b' ' => format!(" {} ", b as char),
+            Some(&b) => format!("{:02x} ", b),
+            None => style.paint(terminal::DIM, " · "),
+        };
+        let last = i + 1 == cells;
+        top.push_str(if last { "───┐" } else { "───┬" });
+        middle.push_str(&cell);
+        middle.push('│');
+        bottom.push_str(if last { "───┘" } else { "───┴" });
+    }
+    if parts.capacity > MAX_CELLS {
+        middle.push_str(" …");
+    }
+    println!("{}\n{}\n{}", top, middle, bottom);
+    println!("  {}", tr!("layout.len_cap", parts.len, parts.capacity));
+    println!("\n{}", style.paint(terminal::DIM, tr!("layout.string_note")));
+}
+
+// 4. コマンド
+// `layout`: 一覧、`layout <型>`: フィールドとバリアント、`layout string [テキスト]`: Stringの図
+pub fn command(args: &[String]) {
+    let style = Style::detect();
+    match args.first().map(String::as_str) {
+        None => print_summary(&all(), style),
+        Some("string") => print_string(args.get(1).map_or("hello", String::as_str), style),
+        Some(query) => {
+            let layouts = all();
+            let found = find(&layouts, query);
+            if found.is_empty() {
+                println!("{}", tr!("layout.unknown", query));
+                println!("{}", tr!("layout.list_hint"));
+                return;
+            }
+            for (i, layout) in found.iter().enumerate() {
+                if i > 0 {
+                    println!();
+                }
+                print_layout(layout, style);
+            }
+            println!("\n{}", tr!("layout.doc_hint"));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn shortens_type_names() {
+        assert_eq!(short_type_name("alloc::string::String"), "String");
+        assert_eq!(short_type_name("hello_cargo::ownership::Book"), "ownership::Book");
+        assert_eq!(
+            short_type_name("core::option::Option<alloc::boxed::Box<hello_cargo::generics_traits::BinaryTree<i32>>>"),
+            "Option<Box<generics_traits::BinaryTree<i32>>>"
+        );
+        assert_eq!(short_type_name("(i32, &str)"), "(i32, &str)");
+        assert_eq!(type_name::<Vec<String>>(), "Vec<String>");
+    }
+
+    // 登録されたすべての型で、フィールドが重ならず型の大きさに収まること
+    #[test]
+    fn every_layout_is_consistent() {
+        let layouts = all();
+        for name in ["ownership::Book", "structs_enums::User", "structs_enums::Message", "structs_enums::GameState", "structs_enums::IpAddr", "generics_traits::BinaryTree<i32>"] {
+            assert!(layouts.iter().any(|layout| layout.name == name), "{}", name);
+        }
+        // Option<Box<T>>はニッチ最適化でBoxと同じ大きさになる
+        let boxed = layouts.iter().find(|layout| layout.name == "Box<generics_traits::BinaryTree<i32>>").unwrap();
+        assert!(boxed.niche());
+        for layout in &layouts {
+            assert!(layout.size % layout.align == 0, "{}", layout.name);
+            match &layout.shape {
+                Shape::Struct(fields) => {
+                    let mut sorted: Vec<&Field> = fields.iter().collect();
+                    sorted.sort_by_key(|field| field.offset);
+                    let used: usize = fields.iter().map(|field| field.size).sum();
+                    let padded: usize = padding(layout.size, &sorted).iter().map(|(_, size)| size).sum();
+                    assert_eq!(used + padded, layout.size, "{}", layout.name);
+                }
+                Shape::Enum(variants) => assert!(variants.iter().all(|variant| variant.size <= layout.size), "{}", layout.name),
+                Shape::Opaque => {}
+            }
+        }
+    }
+
+    #[test]
+    fn draws_fields_and_padding() {
+        #[allow(dead_code)]
+        struct Sample {
+            a: u8,
+            b: u32,
+        }
+        let fields = vec![field!(Sample, a), field!(Sample, b)];
+        assert_eq!((fields[0].ty.as_str(), fields[1].size), ("u8", 4));
+        let bar = byte_bar(mem::size_of::<Sample>(), &fields).unwrap();
+        assert_eq!(bar.chars().filter(|&c| c == 'A').count(), 1);
+        assert_eq!(bar.chars().filter(|&c| c == 'B').count(), 4);
+        assert_eq!(bar.chars().filter(|&c| c == '.').count(), 3);
+    }
+
+    #[test]
+    fn lists_variants_like_the_declaration() {
+        #[allow(dead_code)]
+        enum Token {
+            End,
+            Number(u8, u32),
+            Name { len: u16 },
+        }
+        let variants = variants!(Token { End, Number(u8, u32), Name { len: u16 } });
+        let listed: Vec<(&str, &str, usize)> = variants.iter().map(|v| (v.name, v.data.as_str(), v.size)).collect();
+        assert_eq!(listed, [("End", "()", 0), ("Number", "(u8, u32)", 8), ("Name", "(u16,)", 2)]);
+    }
+
+    #[test]
+    fn finds_types_by_short_names() {
+        let layouts = all();
+        let names = |query: &str| find(&layouts, query).iter().map(|layout| layout.name.clone()).collect::<Vec<_>>();
+        assert_eq!(names("Book"), ["ownership::Book"]);
+        assert_eq!(names("binarytree"), ["generics_traits::BinaryTree<i32>"]);
+        assert_eq!(names("structs_enums::message"), ["structs_enums::Message"]);
+        assert!(names("no_such_type").is_empty());
+    }
+
+    #[test]
+    fn reads_string_parts() {
+        let mut s = String::with_capacity(10);
+        s.push_str("hello");
+        let parts = string_parts(&s);
+        assert_eq!((parts.len, parts.capacity), (5, 10));
+        assert_eq!(parts.heap_address, s.as_ptr() as usize);
+        assert_ne!(parts.stack_address, parts.heap_address);
+    }
+}
@@ -0,0 +1,49 @@
+// Rustの学習用プロジェクト
+// 各モジュールには文法の学習用コード例が含まれています
+//
+// 学習セクション（`basics`〜`testing`）は公開APIとして、統合テスト（`tests/`）から
+// `hello_cargo::collections::GradeBook`のように使えます。CLIの処理は`cli`モジュールにあります
+
+// 学習用のコード例には、説明のためだけに定義した型や関数、
+// あえて冗長に書いた箇所が含まれるため、それらへの警告は抑制する
+#[allow(dead_code, private_interfaces, clippy::all)]
+pub mod basics;
+#[allow(dead_code, private_interfaces, clippy::all)]
+pub mod ownership;
+#[allow(dead_code, private_interfaces, clippy::all)]
+pub mod structs_enums;
+#[allow(dead_code, private_interfaces, clippy::all)]
+pub mod error_handling;
+#[allow(dead_code, private_interfaces, clippy::all)]
+pub mod generics_traits;
+#[allow(dead_code, private_interfaces, clippy::all)]
+pub mod collections;
+#[allow(dead_code, private_interfaces, clippy::all)]
+pub mod testing;
+
+// `tr!`マクロを他のモジュールで使えるように、最初に宣言する
+#[macro_use]
+pub mod i18n;
+
+pub mod cli;
+mod cards;
+mod curriculum;
+mod doc;
+mod exercise;
+mod explain;
+mod export;
+mod glossary;
+mod interactive;
+mod layout;
+mod markdown;
+mod play;
+mod progress;
+mod quiz;
+mod search;
+pub mod section;
+mod source;
+mod step;
+mod transcript;
+mod watch;
+#[cfg(test)]
+mod snapshot;
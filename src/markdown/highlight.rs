@@ -0,0 +1,207 @@
+// コードブロックの字句ハイライト
+// 色分けのための簡易的な字句解析で、Rustの構文を厳密には解析しません
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Token {
+    Plain,
+    Keyword,
+    Type,
+    Str,
+    Number,
+    Comment,
+    Macro,
+    Attribute,
+    Lifetime,
+}
+
+const KEYWORDS: &[&str] = &[
+    "as", "async", "await", "break", "const", "continue", "crate", "dyn", "else", "enum",
+    "extern", "false", "fn", "for", "if", "impl", "in", "let", "loop", "match", "mod", "move",
+    "mut", "pub", "ref", "return", "self", "Self", "static", "struct", "super", "trait", "true",
+    "type", "unsafe", "use", "where", "while",
+];
+
+// 言語名に応じてコードを字句に分ける（連結すると元のコードに戻る）
+pub fn highlight<'a>(lang: &str, code: &'a str) -> Vec<(Token, &'a str)> {
+    match lang {
+        "rust" | "rs" => rust(code),
+        "bash" | "sh" | "shell" | "toml" => line_comments(code, "#"),
+        _ => vec![(Token::Plain, code)],
+    }
+}
+
+pub fn rust(code: &str) -> Vec<(Token, &str)> {
+    let mut spans = Vec::new();
+    let bytes = code.as_bytes();
+    let mut i = 0;
+
+    while i < code.len() {
+        let rest = &code[i..];
+        let c = rest.chars().next().unwrap();
+
+        let (token, len) = if rest.starts_with("//") {
+            (Token::Comment, rest.find('\n').unwrap_or(rest.len()))
+        } else if rest.starts_with("/*") {
+            (Token::Comment, rest.find("*/").map_or(rest.len(), |end| end + 2))
+        } else if rest.starts_with("#[") || rest.starts_with("#![") {
+            (Token::Attribute, rest.find(']').map_or(rest.len(), |end| end + 1))
+        } else if c == '"' || rest.starts_with("b\"") {
+            (Token::Str, string_len(rest))
+        } else if rest.starts_with("r\"") || rest.starts_with("r#\"") {
+            (Token::Str, raw_string_len(rest))
+        } else if c == '\'' {
+            quote_token(rest)
+        } else if c.is_ascii_digit() {
+            let len = rest
+                .find(|ch: char| !(ch.is_ascii_alphanumeric() || ch == '_' || ch == '.'))
+                .unwrap_or(rest.len());
+            // `1..4`の`..`は数値に含めない
+            let len = rest[..len].find("..").unwrap_or(len);
+            (Token::Number, len)
+        } else if c.is_alphabetic() || c == '_' {
+            let len = rest
+                .find(|ch: char| !(ch.is_alphanumeric() || ch == '_'))
+                .unwrap_or(rest.len());
+            let word = &rest[..len];
+            if bytes.get(i + len) == Some(&b'!') && !KEYWORDS.contains(&word) {
+                (Token::Macro, len + 1)
+            } else if KEYWORDS.contains(&word) {
+                (Token::Keyword, len)
+            } else if word.starts_with(|ch: char| ch.is_ascii_uppercase()) {
+                (Token::Type, len)
+            } else {
+                (Token::Plain, len)
+            }
+        } else {
+            (Token::Plain, c.len_utf8())
+        };
+
+        push(&mut spans, token, i, i + len);
+        i += len;
+    }
+
+    slices(code, spans)
+}
+
+// 文字リテラル（'a'）とライフタイム（'a）を見分ける
+fn quote_token(rest: &str) -> (Token, usize) {
+    let mut chars = rest.char_indices().skip(1);
+    match chars.next() {
+        Some((_, '\\')) => {
+            let end = rest[2..].find('\'').map_or(rest.len(), |end| end + 3);
+            (Token::Str, end)
+        }
+        Some((i, c)) => {
+            let after = i + c.len_utf8();
+            if rest[after..].starts_with('\'') {
+                (Token::Str, after + 1)
+            } else if c.is_alphabetic() || c == '_' {
+                let len = rest[after..]
+                    .find(|ch: char| !(ch.is_alphanumeric() || ch == '_'))
+                    .map_or(rest.len(), |end| after + end);
+                (Token::Lifetime, len)
+            } else {
+                (Token::Plain, 1)
+            }
+        }
+        None => (Token::Plain, 1),
+    }
+}
+
+fn string_len(rest: &str) -> usize {
+    let start = rest.find('"').unwrap() + 1;
+    let mut escaped = false;
+    for (i, c) in rest[start..].char_indices() {
+        match c {
+            '\\' if !escaped => escaped = true,
+            '"' if !escaped => return start + i + 1,
+            _ => escaped = false,
+        }
+    }
+    rest.len()
+}
+
+fn raw_string_len(rest: &str) -> usize {
+    let hashes = rest[1..].chars().take_while(|&c| c == '#').count();
+    let open = 1 + hashes + 1;
+    let close = format!("\"{}", "#".repeat(hashes));
+    rest[open..].find(&close).map_or(rest.len(), |end| open + end + close.len())
+}
+
+// 行コメントだけを色分けする（シェルやTOML向け）
+pub fn line_comments<'a>(code: &'a str, marker: &str) -> Vec<(Token, &'a str)> {
+    let mut spans = Vec::new();
+    let mut offset = 0;
+    for line in code.split_inclusive('\n') {
+        let end = offset + line.len();
+        match line.find(marker) {
+            Some(pos) if line[..pos].trim().is_empty() || line[..pos].ends_with(' ') => {
+                push(&mut spans, Token::Plain, offset, offset + pos);
+                push(&mut spans, Token::Comment, offset + pos, end);
+            }
+            _ => push(&mut spans, Token::Plain, offset, end),
+        }
+        offset = end;
+    }
+    slices(code, spans)
+}
+
+// 字句を追加する（同じ種類の字句が続く場合は1つにまとめる）
+fn push(spans: &mut Vec<(Token, usize, usize)>, token: Token, start: usize, end: usize) {
+    if start == end {
+        return;
+    }
+    match spans.last_mut() {
+        Some((last, _, last_end)) if *last == token && *last_end == start => *last_end = end,
+        _ => spans.push((token, start, end)),
+    }
+}
+
+fn slices(code: &str, spans: Vec<(Token, usize, usize)>) -> Vec<(Token, &str)> {
+    spans
+        .into_iter()
+        .map(|(token, start, end)| (token, &code[start..end]))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn kinds(code: &str) -> Vec<(Token, &str)> {
+        rust(code).into_iter().filter(|(_, text)| !text.trim().is_empty()).collect()
+    }
+
+    #[test]
+    fn tokens_concatenate_to_source() {
+        let code = "let s = String::from(\"こんにちは\"); // コメント\nfn f<'a>(x: &'a str) -> char { 'x' }";
+        let joined: String = rust(code).iter().map(|(_, text)| *text).collect();
+        assert_eq!(joined, code);
+    }
+
+    #[test]
+    fn classifies_rust_tokens() {
+        let tokens = kinds("#[derive(Debug)]\nlet x: i32 = 5; println!(\"{}\", x); // 値");
+        assert_eq!(tokens[0], (Token::Attribute, "#[derive(Debug)]"));
+        assert!(tokens.contains(&(Token::Keyword, "let")));
+        assert!(tokens.contains(&(Token::Number, "5")));
+        assert!(tokens.contains(&(Token::Macro, "println!")));
+        assert!(tokens.contains(&(Token::Str, "\"{}\"")));
+        assert!(tokens.contains(&(Token::Comment, "// 値")));
+    }
+
+    #[test]
+    fn lifetimes_and_chars() {
+        let tokens = kinds("fn f<'a>(c: char) { let _ = 'z'; for i in 1..4 {} }");
+        assert!(tokens.contains(&(Token::Lifetime, "'a")));
+        assert!(tokens.contains(&(Token::Str, "'z'")));
+        assert!(tokens.contains(&(Token::Number, "1")));
+        assert!(tokens.contains(&(Token::Number, "4")));
+    }
+
+    #[test]
+    fn shell_comments() {
+        let tokens = line_comments("cargo test  # 全て\n# 見出し\n", "#");
+        assert_eq!(tokens[1], (Token::Comment, "# 全て\n# 見出し\n"));
+    }
+}
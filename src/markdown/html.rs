@@ -0,0 +1,267 @@
+// MarkdownのHTMLレンダラー
+// `export html`で使います。コードブロックは字句ごとに`<span class="...">`で囲み、色はCSSで付けます
+
+use super::highlight::{self, Token};
+use super::{anchor, inline_text, parse_inline, Align, Block, Inline, ListItem};
+use std::collections::HashMap;
+
+// 1. エスケープ
+pub fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+// 2. 見出しのid
+// 見出しの順に、アンカー名をidとして返す（同じ名前が続く場合は`-1`、`-2`を付ける）
+pub fn heading_ids(blocks: &[Block]) -> Vec<String> {
+    let mut used: HashMap<String, usize> = HashMap::new();
+    blocks
+        .iter()
+        .filter_map(|block| match block {
+            Block::Heading { text, .. } => Some(text),
+            _ => None,
+        })
+        .map(|text| {
+            let mut id = anchor(text);
+            if id.is_empty() {
+                id = String::from("section");
+            }
+            let count = used.entry(id.clone()).or_insert(0);
+            *count += 1;
+            if *count > 1 {
+                id = format!("{}-{}", id, *count - 1);
+            }
+            id
+        })
+        .collect()
+}
+
+// 3. 文書全体の描画
+pub fn render_blocks(blocks: &[Block]) -> String {
+    let mut ids = heading_ids(blocks).into_iter();
+    let mut out = String::new();
+
+    for block in blocks {
+        match block {
+            Block::Heading { level, text, .. } => {
+                let id = ids.next().unwrap_or_default();
+                out.push_str(&format!("<h{0} id=\"{1}\">{2}</h{0}>\n", level, escape(&id), inline(text)));
+            }
+            Block::Paragraph { lines } => {
+                let lines: Vec<String> = lines.iter().map(|line| inline(line)).collect();
+                out.push_str(&format!("<p>{}</p>\n", lines.join("\n")));
+            }
+            Block::List { items } => list(&mut out, items),
+            Block::Code { info, code, .. } => {
+                out.push_str(&code_block(Block::code_lang(info), &Block::shown_code(info, code)));
+            }
+            Block::Table { header, aligns, rows } => table(&mut out, header, aligns, rows),
+            Block::Quote { lines } => {
+                let lines: Vec<String> = lines.iter().map(|line| inline(line)).collect();
+                out.push_str(&format!("<blockquote><p>{}</p></blockquote>\n", lines.join("<br>\n")));
+            }
+            Block::Rule => out.push_str("<hr>\n"),
+        }
+    }
+    out
+}
+
+// インライン要素
+pub fn inline(text: &str) -> String {
+    parse_inline(text)
+        .into_iter()
+        .map(|inline| match inline {
+            Inline::Text(s) => escape(&s),
+            Inline::Code(s) => format!("<code>{}</code>", escape(&s)),
+            Inline::Strong(s) => format!("<strong>{}</strong>", escape(&s)),
+            Inline::Link { text, url } => format!("<a href=\"{}\">{}</a>", escape(&url), escape(&text)),
+        })
+        .collect()
+}
+
+// 入れ子のリストは、深さが変わるたびに`<ul>`/`<ol>`を開閉する
+fn list(out: &mut String, items: &[ListItem]) {
+    let mut open: Vec<&str> = Vec::new();
+
+    for item in items {
+        while open.len() > item.depth + 1 {
+            out.push_str(&format!("</li></{}>\n", open.pop().unwrap()));
+        }
+        if open.len() == item.depth + 1 {
+            out.push_str("</li>\n");
+        }
+        while open.len() < item.depth + 1 {
+            match &item.marker {
+                Some(marker) => {
+                    let start = marker.trim_end_matches('.');
+                    if start == "1" {
+                        out.push_str("<ol>\n");
+                    } else {
+                        out.push_str(&format!("<ol start=\"{}\">\n", escape(start)));
+                    }
+                    open.push("ol");
+                }
+                None => {
+                    out.push_str("<ul>\n");
+                    open.push("ul");
+                }
+            }
+        }
+        out.push_str(&format!("<li>{}", inline(&item.text)));
+    }
+
+    while let Some(tag) = open.pop() {
+        out.push_str(&format!("</li></{}>\n", tag));
+    }
+}
+
+pub fn code_block(lang: &str, code: &str) -> String {
+    let mut html = String::new();
+    for (token, text) in highlight::highlight(lang, code) {
+        match token_class(token) {
+            Some(class) => html.push_str(&format!("<span class=\"{}\">{}</span>", class, escape(text))),
+            None => html.push_str(&escape(text)),
+        }
+    }
+    if lang.is_empty() {
+        format!("<pre><code>{}</code></pre>\n", html)
+    } else {
+        format!("<pre><code class=\"language-{}\">{}</code></pre>\n", escape(lang), html)
+    }
+}
+
+fn token_class(token: Token) -> Option<&'static str> {
+    match token {
+        Token::Plain => None,
+        Token::Keyword => Some("kw"),
+        Token::Type => Some("ty"),
+        Token::Str => Some("str"),
+        Token::Number => Some("num"),
+        Token::Comment => Some("com"),
+        Token::Macro => Some("mac"),
+        Token::Attribute => Some("attr"),
+        Token::Lifetime => Some("life"),
+    }
+}
+
+fn table(out: &mut String, header: &[String], aligns: &[Align], rows: &[Vec<String>]) {
+    let cell = |tag: &str, i: usize, text: &str| -> String {
+        let style = match aligns.get(i) {
+            Some(Align::Center) => " style=\"text-align: center\"",
+            Some(Align::Right) => " style=\"text-align: right\"",
+            _ => "",
+        };
+        format!("<{0}{1}>{2}</{0}>", tag, style, inline(text))
+    };
+
+    out.push_str("<table>\n<thead><tr>");
+    for (i, text) in header.iter().enumerate() {
+        out.push_str(&cell("th", i, text));
+    }
+    out.push_str("</tr></thead>\n<tbody>\n");
+    for row in rows {
+        out.push_str("<tr>");
+        for (i, text) in row.iter().enumerate() {
+            out.push_str(&cell("td", i, text));
+        }
+        out.push_str("</tr>\n");
+    }
+    out.push_str("</tbody>\n</table>\n");
+}
+
+// 4. 検索用のテキスト
+// 見出しごとに区切り、本文（コードを含む）を記号を除いた素のテキストにする
+#[derive(Debug, Clone, PartialEq)]
+pub struct Chunk {
+    pub id: String,
+    pub title: String,
+    pub text: String,
+}
+
+pub fn chunks(blocks: &[Block]) -> Vec<Chunk> {
+    let mut ids = heading_ids(blocks).into_iter();
+    let mut chunks: Vec<Chunk> = Vec::new();
+
+    for block in blocks {
+        let text = match block {
+            Block::Heading { text, .. } => {
+                chunks.push(Chunk {
+                    id: ids.next().unwrap_or_default(),
+                    title: inline_text(text),
+                    text: String::new(),
+                });
+                continue;
+            }
+            Block::Paragraph { lines } | Block::Quote { lines } => inline_text(&lines.join(" ")),
+            Block::List { items } => {
+                let items: Vec<String> = items.iter().map(|item| inline_text(&item.text)).collect();
+                items.join(" ")
+            }
+            Block::Code { info, code, .. } => Block::shown_code(info, code),
+            Block::Table { header, rows, .. } => {
+                let cells: Vec<String> = header.iter().chain(rows.iter().flatten()).map(|cell| inline_text(cell)).collect();
+                cells.join(" ")
+            }
+            Block::Rule => continue,
+        };
+        if let Some(chunk) = chunks.last_mut() {
+            if !chunk.text.is_empty() {
+                chunk.text.push(' ');
+            }
+            chunk.text.push_str(&text);
+        }
+    }
+    chunks
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::markdown::parse;
+
+    #[test]
+    fn renders_blocks_as_html() {
+        let html = render_blocks(&parse("# 所有権\n\n`String`は**ヒープ**に<置く>\n\n| 型 | サイズ |\n|----|---:|\n| i32 | 4 |"));
+        assert!(html.contains("<h1 id=\"所有権\">所有権</h1>"));
+        assert!(html.contains("<p><code>String</code>は<strong>ヒープ</strong>に&lt;置く&gt;</p>"));
+        assert!(html.contains("<th style=\"text-align: right\">サイズ</th>"));
+        assert!(html.contains("<td style=\"text-align: right\">4</td>"));
+    }
+
+    #[test]
+    fn nested_lists_are_closed() {
+        let html = render_blocks(&parse("- a\n  - b\n  - c\n- d\n\n3. x\n4. y"));
+        assert_eq!(
+            html,
+            "<ul>\n<li>a<ul>\n<li>b</li>\n<li>c</li></ul>\n</li>\n<li>d</li></ul>\n<ol start=\"3\">\n<li>x</li>\n<li>y</li></ol>\n"
+        );
+    }
+
+    #[test]
+    fn highlights_code_with_classes() {
+        let html = code_block("rust", "let s = \"<a>\"; // 値");
+        assert!(html.starts_with("<pre><code class=\"language-rust\"><span class=\"kw\">let</span>"));
+        assert!(html.contains("<span class=\"str\">&quot;&lt;a&gt;&quot;</span>"));
+        assert!(html.contains("<span class=\"com\">// 値</span>"));
+    }
+
+    #[test]
+    fn duplicate_headings_get_unique_ids() {
+        let blocks = parse("## 使い方\n\n本文\n\n## 使い方\n\n```rust\nlet x = 1;\n```");
+        assert_eq!(heading_ids(&blocks), vec!["使い方", "使い方-1"]);
+        let chunks = chunks(&blocks);
+        assert_eq!(chunks[0].text, "本文");
+        assert_eq!(chunks[1].id, "使い方-1");
+        assert_eq!(chunks[1].text, "let x = 1;");
+    }
+}
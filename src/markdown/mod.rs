@@ -0,0 +1,612 @@
+// Markdownの簡易パーサー
+// セクションのREADMEで使っている書式（見出し、リスト、表、コードブロックなど）だけを扱います
+
+pub mod highlight;
+pub mod html;
+pub mod terminal;
+
+// 1. ブロック要素
+#[derive(Debug, Clone, PartialEq)]
+pub enum Block {
+    Heading { level: usize, text: String, line: usize },
+    Paragraph { lines: Vec<String> },
+    List { items: Vec<ListItem> },
+    Code { info: String, code: String, line: usize },  // infoは```の後ろの文字列（例: `rust`）
+    Table { header: Vec<String>, aligns: Vec<Align>, rows: Vec<Vec<String>> },
+    Quote { lines: Vec<String> },
+    Rule,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ListItem {
+    pub depth: usize,            // 入れ子の深さ（0始まり）
+    pub marker: Option<String>,  // 番号付きリストなら`1.`など、箇条書きならNone
+    pub text: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Align {
+    Left,
+    Center,
+    Right,
+}
+
+impl Block {
+    // コードブロックの言語名（`rust,ignore`なら`rust`）
+    pub fn code_lang(info: &str) -> &str {
+        info.split([',', ' ']).next().unwrap_or("").trim()
+    }
+
+    // 表示するコード（Rustのコードブロックなら隠し行を除く）
+    pub fn shown_code(info: &str, code: &str) -> String {
+        if Block::code_lang(info) != "rust" {
+            return code.to_string();
+        }
+        let lines: Vec<&str> = code.lines().filter(|line| hidden_line(line).is_none()).collect();
+        lines.join("\n")
+    }
+}
+
+// rustdocと同じく、`# `で始まる行（`#`だけの行も）は表示しない隠し行
+// `doc verify`でコンパイルするときだけ使う、`use`や前のコード例の定義などを書きます
+pub fn hidden_line(line: &str) -> Option<&str> {
+    let trimmed = line.trim_start();
+    if trimmed == "#" {
+        return Some("");
+    }
+    trimmed.strip_prefix("# ")
+}
+
+// 2. ブロック要素の解析
+pub fn parse(source: &str) -> Vec<Block> {
+    let mut parser = Parser::default();
+    for (i, line) in source.lines().enumerate() {
+        parser.line(i + 1, line);
+    }
+    parser.finish()
+}
+
+#[derive(Default)]
+struct Parser {
+    blocks: Vec<Block>,
+    paragraph: Vec<String>,
+    list: Vec<ListItem>,
+    quote: Vec<String>,
+    table: Vec<String>,
+    fence: Option<Fence>,
+}
+
+struct Fence {
+    info: String,
+    indent: usize,
+    line: usize,
+    code: Vec<String>,
+}
+
+impl Parser {
+    fn line(&mut self, number: usize, line: &str) {
+        let trimmed = line.trim_start();
+        let indent = line.len() - trimmed.len();
+
+        // コードブロックの中は終わりの```まで何も解釈しない
+        if let Some(fence) = &mut self.fence {
+            if trimmed.starts_with("```") {
+                let fence = self.fence.take().unwrap();
+                self.blocks.push(Block::Code {
+                    info: fence.info,
+                    code: fence.code.join("\n"),
+                    line: fence.line,
+                });
+            } else {
+                // 開始行と同じだけのインデントを取り除く
+                let strip = line.len() - line.trim_start_matches(' ').len();
+                fence.code.push(line[strip.min(fence.indent)..].to_string());
+            }
+            return;
+        }
+
+        if let Some(info) = trimmed.strip_prefix("```") {
+            self.flush();
+            self.fence = Some(Fence {
+                info: info.trim().to_string(),
+                indent,
+                line: number,
+                code: Vec::new(),
+            });
+            return;
+        }
+
+        if trimmed.is_empty() {
+            self.flush();
+            return;
+        }
+
+        if let Some((level, text)) = heading(trimmed) {
+            self.flush();
+            self.blocks.push(Block::Heading { level, text: text.to_string(), line: number });
+            return;
+        }
+
+        if trimmed.starts_with('|') {
+            if self.table.is_empty() {
+                self.flush();
+            }
+            self.table.push(trimmed.to_string());
+            return;
+        }
+        self.flush_table();
+
+        if let Some((marker, text)) = list_marker(trimmed) {
+            self.flush_paragraph();
+            self.flush_quote();
+            self.list.push(ListItem { depth: indent / 2, marker, text: text.to_string() });
+            return;
+        }
+
+        if let Some(text) = trimmed.strip_prefix('>') {
+            self.flush_paragraph();
+            self.flush_list();
+            self.quote.push(text.trim_start().to_string());
+            return;
+        }
+
+        if is_rule(trimmed) {
+            self.flush();
+            self.blocks.push(Block::Rule);
+            return;
+        }
+
+        // リスト項目の続きの行
+        if indent > 0 && let Some(item) = self.list.last_mut() {
+            item.text.push(' ');
+            item.text.push_str(trimmed);
+            return;
+        }
+
+        self.flush_list();
+        self.flush_quote();
+        self.paragraph.push(trimmed.to_string());
+    }
+
+    fn flush(&mut self) {
+        self.flush_paragraph();
+        self.flush_list();
+        self.flush_quote();
+        self.flush_table();
+    }
+
+    fn flush_paragraph(&mut self) {
+        if !self.paragraph.is_empty() {
+            let lines = std::mem::take(&mut self.paragraph);
+            self.blocks.push(Block::Paragraph { lines });
+        }
+    }
+
+    fn flush_list(&mut self) {
+        if !self.list.is_empty() {
+            let items = std::mem::take(&mut self.list);
+            self.blocks.push(Block::List { items });
+        }
+    }
+
+    fn flush_quote(&mut self) {
+        if !self.quote.is_empty() {
+            let lines = std::mem::take(&mut self.quote);
+            self.blocks.push(Block::Quote { lines });
+        }
+    }
+
+    fn flush_table(&mut self) {
+        if self.table.is_empty() {
+            return;
+        }
+        let lines = std::mem::take(&mut self.table);
+        let mut rows: Vec<Vec<String>> = lines.iter().map(|line| split_row(line)).collect();
+
+        // 2行目が区切り行（|---|:---:|）なら1行目を見出しとして扱う
+        let aligns = match rows.get(1) {
+            Some(row) if row.iter().all(|cell| is_separator_cell(cell)) => {
+                let aligns = row.iter().map(|cell| cell_align(cell)).collect();
+                rows.remove(1);
+                aligns
+            }
+            _ => Vec::new(),
+        };
+        let header = rows.remove(0);
+        let aligns = (0..header.len())
+            .map(|i| aligns.get(i).copied().unwrap_or(Align::Left))
+            .collect();
+        self.blocks.push(Block::Table { header, aligns, rows });
+    }
+
+    fn finish(mut self) -> Vec<Block> {
+        // 閉じられていないコードブロックも最後まで含める
+        if let Some(fence) = self.fence.take() {
+            self.blocks.push(Block::Code {
+                info: fence.info,
+                code: fence.code.join("\n"),
+                line: fence.line,
+            });
+        }
+        self.flush();
+        self.blocks
+    }
+}
+
+// `## 見出し`の形式なら見出しのレベルと本文を返す
+pub fn heading(line: &str) -> Option<(usize, &str)> {
+    let level = line.chars().take_while(|&c| c == '#').count();
+    if !(1..=6).contains(&level) {
+        return None;
+    }
+    let rest = &line[level..];
+    if rest.is_empty() {
+        return Some((level, ""));
+    }
+    rest.strip_prefix(' ').map(|text| (level, text.trim()))
+}
+
+fn list_marker(line: &str) -> Option<(Option<String>, &str)> {
+    for bullet in ["- ", "* ", "+ "] {
+        if let Some(text) = line.strip_prefix(bullet) {
+            return Some((None, text));
+        }
+    }
+
+    let digits = line.chars().take_while(|c| c.is_ascii_digit()).count();
+    if digits > 0 && let Some(text) = line[digits..].strip_prefix(". ") {
+        return Some((Some(line[..digits + 1].to_string()), text));
+    }
+    None
+}
+
+fn is_rule(line: &str) -> bool {
+    let chars: Vec<char> = line.chars().filter(|c| !c.is_whitespace()).collect();
+    chars.len() >= 3 && ['-', '*', '_'].iter().any(|&m| chars.iter().all(|&c| c == m))
+}
+
+// 表の行をセルに分割する（インラインコード内の`|`や`\|`では分割しない）
+fn split_row(line: &str) -> Vec<String> {
+    let line = line.trim();
+    let line = line.strip_prefix('|').unwrap_or(line);
+    let line = line.strip_suffix('|').unwrap_or(line);
+
+    let mut cells = Vec::new();
+    let mut cell = String::new();
+    let mut in_code = false;
+    let mut chars = line.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' if chars.peek() == Some(&'|') => {
+                cell.push('|');
+                chars.next();
+            }
+            '`' => {
+                in_code = !in_code;
+                cell.push(c);
+            }
+            '|' if !in_code => cells.push(std::mem::take(&mut cell).trim().to_string()),
+            _ => cell.push(c),
+        }
+    }
+    cells.push(cell.trim().to_string());
+    cells
+}
+
+fn is_separator_cell(cell: &str) -> bool {
+    let cell = cell.trim();
+    !cell.is_empty() && cell.contains('-') && cell.chars().all(|c| c == '-' || c == ':')
+}
+
+fn cell_align(cell: &str) -> Align {
+    let cell = cell.trim();
+    match (cell.starts_with(':'), cell.ends_with(':')) {
+        (true, true) => Align::Center,
+        (false, true) => Align::Right,
+        _ => Align::Left,
+    }
+}
+
+// 3. インライン要素
+#[derive(Debug, Clone, PartialEq)]
+pub enum Inline {
+    Text(String),
+    Code(String),
+    Strong(String),
+    Link { text: String, url: String },
+}
+
+pub fn parse_inline(text: &str) -> Vec<Inline> {
+    let mut result = Vec::new();
+    let mut plain = String::new();
+    let mut rest = text;
+
+    while let Some(c) = rest.chars().next() {
+        let parsed = match c {
+            '`' => delimited(rest, "`", "`").map(|(inner, len)| (Inline::Code(inner.to_string()), len)),
+            '*' if rest.starts_with("**") => delimited(rest, "**", "**")
+                .filter(|(inner, _)| !inner.is_empty())
+                .map(|(inner, len)| (Inline::Strong(inner.to_string()), len)),
+            '[' => link(rest),
+            _ => None,
+        };
+
+        match parsed {
+            Some((inline, len)) => {
+                if !plain.is_empty() {
+                    result.push(Inline::Text(std::mem::take(&mut plain)));
+                }
+                result.push(inline);
+                rest = &rest[len..];
+            }
+            None => {
+                plain.push(c);
+                rest = &rest[c.len_utf8()..];
+            }
+        }
+    }
+
+    if !plain.is_empty() {
+        result.push(Inline::Text(plain));
+    }
+    result
+}
+
+fn delimited<'a>(text: &'a str, open: &str, close: &str) -> Option<(&'a str, usize)> {
+    let inner = text.strip_prefix(open)?;
+    let end = inner.find(close)?;
+    Some((&inner[..end], open.len() + end + close.len()))
+}
+
+fn link(text: &str) -> Option<(Inline, usize)> {
+    let (label, label_len) = delimited(text, "[", "]")?;
+    let (url, url_len) = delimited(&text[label_len..], "(", ")")?;
+    Some((
+        Inline::Link { text: label.to_string(), url: url.to_string() },
+        label_len + url_len,
+    ))
+}
+
+// インライン要素の記号を取り除いた素のテキスト
+pub fn inline_text(text: &str) -> String {
+    parse_inline(text)
+        .into_iter()
+        .map(|inline| match inline {
+            Inline::Text(s) | Inline::Code(s) | Inline::Strong(s) => s,
+            Inline::Link { text, .. } => text,
+        })
+        .collect()
+}
+
+// 4. 見出しによるナビゲーション
+// 目次の項目（`#`と`##`の見出し）
+#[derive(Debug, Clone, PartialEq)]
+pub struct TocEntry {
+    pub level: usize,
+    pub text: String,
+    pub line: usize,
+}
+
+pub fn toc(blocks: &[Block]) -> Vec<TocEntry> {
+    blocks
+        .iter()
+        .filter_map(|block| match block {
+            Block::Heading { level, text, line } if *level <= 2 => Some(TocEntry {
+                level: *level,
+                text: inline_text(text),
+                line: *line,
+            }),
+            _ => None,
+        })
+        .collect()
+}
+
+// 見出しのアンカー名（GitHubと同じく小文字にし、記号を除いて空白を`-`にする）
+pub fn anchor(text: &str) -> String {
+    inline_text(text)
+        .trim()
+        .to_lowercase()
+        .chars()
+        .filter_map(|c| match c {
+            ' ' => Some('-'),
+            c if c.is_alphanumeric() || c == '-' || c == '_' => Some(c),
+            _ => None,
+        })
+        .collect()
+}
+
+// 見出しの主要部分（先頭の番号と括弧内の英語表記を除く）
+// 例: `4. 制御フロー (Control Flow)` → `制御フロー`
+fn heading_title(text: &str) -> String {
+    let text = inline_text(text);
+    let text = text.trim_start_matches(|c: char| c.is_ascii_digit() || c == '.' || c == ' ');
+    let end = text.find([' ', '(', '（']).filter(|&i| i > 0).unwrap_or(text.len());
+    anchor(&text[..end])
+}
+
+// 名前に対応する見出しのブロック番号を探す
+// アンカー名の完全一致、見出しの主要部分との一致、部分一致の順に試す
+pub fn find_heading(blocks: &[Block], name: &str) -> Option<usize> {
+    let name = anchor(name);
+    if name.is_empty() {
+        return None;
+    }
+    let headings: Vec<(usize, &str)> = blocks
+        .iter()
+        .enumerate()
+        .filter_map(|(i, block)| match block {
+            Block::Heading { text, .. } => Some((i, text.as_str())),
+            _ => None,
+        })
+        .collect();
+
+    let find = |matches: &dyn Fn(&str) -> bool| {
+        headings.iter().find(|(_, text)| matches(text)).map(|(i, _)| *i)
+    };
+    find(&|text| anchor(text) == name)
+        .or_else(|| find(&|text| heading_title(text) == name))
+        .or_else(|| find(&|text| anchor(text).contains(&name)))
+}
+
+// 見出しから、同じかより上位の次の見出しの直前までのブロック
+pub fn heading_section(blocks: &[Block], index: usize) -> &[Block] {
+    let Some(Block::Heading { level, .. }) = blocks.get(index) else {
+        return &[];
+    };
+    let end = blocks[index + 1..]
+        .iter()
+        .position(|block| matches!(block, Block::Heading { level: l, .. } if l <= level))
+        .map_or(blocks.len(), |offset| index + 1 + offset);
+    &blocks[index..end]
+}
+
+// 5. 端末上の表示幅
+// 全角文字（日本語など）は2、結合文字は0として数える。ANSIエスケープシーケンスは無視する
+pub fn display_width(text: &str) -> usize {
+    let mut width = 0;
+    let mut chars = text.chars();
+
+    while let Some(c) = chars.next() {
+        if c == '\x1b' {
+            // ESC [ ... 英字 までを読み飛ばす
+            for c in chars.by_ref() {
+                if c.is_ascii_alphabetic() {
+                    break;
+                }
+            }
+            continue;
+        }
+        width += char_width(c);
+    }
+    width
+}
+
+pub fn char_width(c: char) -> usize {
+    let code = c as u32;
+    match code {
+        0 => 0,
+        _ if c.is_control() => 0,
+        // 結合文字・ゼロ幅文字・異体字セレクタ
+        0x0300..=0x036F | 0x200B..=0x200F | 0x2060..=0x2064 | 0xFE00..=0xFE0F | 0x0900..=0x0903 => 0,
+        0x093A..=0x094F | 0x0951..=0x0957 | 0x0962..=0x0963 => 0,
+        // 全角文字
+        0x1100..=0x115F
+        | 0x2E80..=0x303E
+        | 0x3041..=0x33FF
+        | 0x3400..=0x4DBF
+        | 0x4E00..=0x9FFF
+        | 0xA000..=0xA4CF
+        | 0xAC00..=0xD7A3
+        | 0xF900..=0xFAFF
+        | 0xFE30..=0xFE4F
+        | 0xFF00..=0xFF60
+        | 0xFFE0..=0xFFE6
+        | 0x1F300..=0x1F64F
+        | 0x1F900..=0x1F9FF
+        | 0x20000..=0x3FFFD => 2,
+        _ => 1,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_headings_and_paragraphs() {
+        let blocks = parse("# タイトル\n\n本文1行目\n本文2行目\n\n## 1. 見出し");
+        assert_eq!(
+            blocks,
+            vec![
+                Block::Heading { level: 1, text: "タイトル".to_string(), line: 1 },
+                Block::Paragraph { lines: vec!["本文1行目".to_string(), "本文2行目".to_string()] },
+                Block::Heading { level: 2, text: "1. 見出し".to_string(), line: 6 },
+            ]
+        );
+    }
+
+    #[test]
+    fn code_blocks_are_not_interpreted() {
+        let blocks = parse("```rust\n#[derive(Debug)]\n- not a list\n```\n");
+        assert_eq!(
+            blocks,
+            vec![Block::Code {
+                info: "rust".to_string(),
+                code: "#[derive(Debug)]\n- not a list".to_string(),
+                line: 1,
+            }]
+        );
+        assert_eq!(Block::code_lang("rust,ignore"), "rust");
+        assert_eq!(Block::shown_code("rust", "# struct User;\n#[derive(Debug)]\nlet x = 5;\n    #"), "#[derive(Debug)]\nlet x = 5;");
+        assert_eq!(Block::shown_code("bash", "# コメント"), "# コメント");
+    }
+
+    #[test]
+    fn parses_nested_lists() {
+        let blocks = parse("- 親\n  - 子\n1. 番号");
+        let Block::List { items } = &blocks[0] else { panic!("{:?}", blocks) };
+        assert_eq!(items.len(), 3);
+        assert_eq!((items[1].depth, items[1].text.as_str()), (1, "子"));
+        assert_eq!(items[2].marker.as_deref(), Some("1."));
+    }
+
+    #[test]
+    fn parses_tables() {
+        let blocks = parse("| 操作 | `a|b` |\n|:----|---:|\n| 追加 | O(1)* |");
+        assert_eq!(
+            blocks,
+            vec![Block::Table {
+                header: vec!["操作".to_string(), "`a|b`".to_string()],
+                aligns: vec![Align::Left, Align::Right],
+                rows: vec![vec!["追加".to_string(), "O(1)*".to_string()]],
+            }]
+        );
+    }
+
+    #[test]
+    fn parses_inline_elements() {
+        assert_eq!(
+            parse_inline("**不変**な`let`と[本](https://example.com) O(1)*"),
+            vec![
+                Inline::Strong("不変".to_string()),
+                Inline::Text("な".to_string()),
+                Inline::Code("let".to_string()),
+                Inline::Text("と".to_string()),
+                Inline::Link { text: "本".to_string(), url: "https://example.com".to_string() },
+                Inline::Text(" O(1)*".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn finds_headings_by_anchor_and_title() {
+        let blocks = parse(
+            "# Rustの基本文法\n\n## 3. 関数 (Functions)\n\n本文\n\n### 戻り値\n\n## 4. 制御フロー (Control Flow)\n\n### ループ",
+        );
+        assert_eq!(anchor("4. 制御フロー (Control Flow)"), "4-制御フロー-control-flow");
+        assert_eq!(find_heading(&blocks, "4-制御フロー-control-flow"), Some(4));
+        assert_eq!(find_heading(&blocks, "制御フロー"), Some(4));
+        assert_eq!(find_heading(&blocks, "Functions"), Some(1));
+        assert_eq!(find_heading(&blocks, "戻り値"), Some(3));
+        assert_eq!(find_heading(&blocks, "存在しない"), None);
+    }
+
+    #[test]
+    fn heading_section_stops_at_same_level() {
+        let blocks = parse("## A\n\n本文\n\n### A-1\n\n## B\n\n本文");
+        assert_eq!(heading_section(&blocks, 0).len(), 3);
+        assert_eq!(heading_section(&blocks, 2).len(), 1);
+        assert_eq!(heading_section(&blocks, 3).len(), 2);
+        let entries: Vec<String> = toc(&blocks).into_iter().map(|e| e.text).collect();
+        assert_eq!(entries, vec!["A", "B"]);
+    }
+
+    #[test]
+    fn width_of_full_width_characters() {
+        assert_eq!(display_width("abc"), 3);
+        assert_eq!(display_width("所有権"), 6);
+        assert_eq!(display_width("Vec（ベクタ）"), 13);
+        assert_eq!(display_width("\x1b[1m太字\x1b[0m"), 4);
+    }
+}
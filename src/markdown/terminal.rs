@@ -0,0 +1,398 @@
+// Markdownの端末向けレンダラー
+// 見出し・リスト・表・コードブロックを整形し、色が使える場合はANSIエスケープで装飾します
+
+use super::highlight::{self, Token};
+use super::{char_width, display_width, parse_inline, Align, Block, Inline, ListItem, TocEntry};
+use std::env;
+use std::io::{self, IsTerminal};
+
+pub const RESET: &str = "\x1b[0m";
+pub const BOLD: &str = "\x1b[1m";
+pub const DIM: &str = "\x1b[2m";
+pub const UNDERLINE: &str = "\x1b[4m";
+pub const RED: &str = "\x1b[31m";
+pub const GREEN: &str = "\x1b[32m";
+pub const YELLOW: &str = "\x1b[33m";
+pub const BLUE: &str = "\x1b[34m";
+pub const MAGENTA: &str = "\x1b[35m";
+pub const CYAN: &str = "\x1b[36m";
+
+// 1. 出力スタイル
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Style {
+    pub color: bool,
+}
+
+impl Style {
+    // 標準出力が端末で、NO_COLORが設定されていなければ色を使う
+    pub fn detect() -> Style {
+        let no_color = env::var_os("NO_COLOR").is_some_and(|value| !value.is_empty());
+        Style { color: io::stdout().is_terminal() && !no_color }
+    }
+
+    // 色が有効なときだけエスケープシーケンスで囲む
+    pub fn paint(&self, code: &str, text: &str) -> String {
+        if self.color && !code.is_empty() && !text.is_empty() {
+            format!("{}{}{}", code, text, RESET)
+        } else {
+            text.to_string()
+        }
+    }
+}
+
+// 2. 文書全体の描画
+pub fn render_blocks(blocks: &[Block], style: Style) -> String {
+    let mut out: Vec<String> = Vec::new();
+
+    for block in blocks {
+        if !out.is_empty() {
+            out.push(String::new());
+        }
+        match block {
+            Block::Heading { level, text, .. } => heading(&mut out, *level, text, style),
+            Block::Paragraph { lines } => {
+                out.extend(lines.iter().map(|line| inline(line, style)));
+            }
+            Block::List { items } => list(&mut out, items, style),
+            Block::Code { info, code, .. } => code_block(&mut out, info, code, style),
+            Block::Table { header, aligns, rows } => table(&mut out, header, aligns, rows, style),
+            Block::Quote { lines } => {
+                let bar = style.paint(DIM, "┃");
+                out.extend(lines.iter().map(|line| format!("  {} {}", bar, inline(line, style))));
+            }
+            Block::Rule => out.push(style.paint(DIM, &"─".repeat(40))),
+        }
+    }
+
+    let mut text = out.join("\n");
+    text.push('\n');
+    text
+}
+
+fn heading(out: &mut Vec<String>, level: usize, text: &str, style: Style) {
+    let rendered = inline(text, style);
+    let width = display_width(&rendered);
+    match level {
+        1 => {
+            out.push(style.paint(&format!("{}{}", BOLD, MAGENTA), &rendered));
+            out.push(style.paint(MAGENTA, &"═".repeat(width)));
+        }
+        2 => {
+            out.push(style.paint(&format!("{}{}", BOLD, CYAN), &rendered));
+            out.push(style.paint(CYAN, &"─".repeat(width)));
+        }
+        3 => out.push(style.paint(&format!("{}{}", BOLD, YELLOW), &format!("■ {}", rendered))),
+        _ => out.push(style.paint(BOLD, &format!("□ {}", rendered))),
+    }
+}
+
+fn list(out: &mut Vec<String>, items: &[ListItem], style: Style) {
+    for item in items {
+        let indent = "  ".repeat(item.depth + 1);
+        let marker = match &item.marker {
+            Some(number) => number.clone(),
+            None if item.depth == 0 => "•".to_string(),
+            None => "◦".to_string(),
+        };
+        out.push(format!("{}{} {}", indent, style.paint(CYAN, &marker), inline(&item.text, style)));
+    }
+}
+
+fn code_block(out: &mut Vec<String>, info: &str, code: &str, style: Style) {
+    let lang = Block::code_lang(info);
+    let gutter = style.paint(DIM, "│");
+
+    if !lang.is_empty() {
+        out.push(format!("  {} {}", style.paint(DIM, "┌"), style.paint(DIM, lang)));
+    }
+
+    for line in highlight_lines(lang, &Block::shown_code(info, code), style) {
+        out.push(format!("  {} {}", gutter, line).trim_end().to_string());
+    }
+}
+
+// 色付けした字句を行ごとに分け、行末で必ず色をリセットする
+pub fn highlight_lines(lang: &str, code: &str, style: Style) -> Vec<String> {
+    let mut lines = Vec::new();
+    let mut line = String::new();
+    for (token, text) in highlight::highlight(lang, code) {
+        let mut pieces = text.split('\n').peekable();
+        while let Some(piece) = pieces.next() {
+            line.push_str(&style.paint(token_color(token), piece));
+            if pieces.peek().is_some() {
+                lines.push(std::mem::take(&mut line));
+            }
+        }
+    }
+    lines.push(line);
+    lines
+}
+
+// 行番号付きのソースコード（`first_line`は1行目の行番号）
+pub fn render_source(code: &str, first_line: usize, style: Style) -> Vec<String> {
+    let code = code.strip_suffix('\n').unwrap_or(code);
+    let last = first_line + code.lines().count().saturating_sub(1);
+    let digits = last.to_string().len();
+    highlight_lines("rust", code, style)
+        .into_iter()
+        .enumerate()
+        .map(|(i, line)| {
+            let number = style.paint(DIM, &format!("{:>digits$} │", first_line + i));
+            format!("{} {}", number, line).trim_end().to_string()
+        })
+        .collect()
+}
+
+fn token_color(token: Token) -> &'static str {
+    match token {
+        Token::Plain => "",
+        Token::Keyword => MAGENTA,
+        Token::Type => YELLOW,
+        Token::Str => GREEN,
+        Token::Number => RED,
+        Token::Comment => DIM,
+        Token::Macro => BLUE,
+        Token::Attribute => DIM,
+        Token::Lifetime => CYAN,
+    }
+}
+
+fn table(out: &mut Vec<String>, header: &[String], aligns: &[Align], rows: &[Vec<String>], style: Style) {
+    let columns = header.len().max(rows.iter().map(Vec::len).max().unwrap_or(0));
+    let render_row = |row: &[String]| -> Vec<String> {
+        (0..columns)
+            .map(|i| row.get(i).map(|cell| inline(cell, style)).unwrap_or_default())
+            .collect()
+    };
+    let header = render_row(header);
+    let rows: Vec<Vec<String>> = rows.iter().map(|row| render_row(row)).collect();
+
+    // 各列の幅は表示幅（全角文字は2）の最大値
+    let widths: Vec<usize> = (0..columns)
+        .map(|i| {
+            std::iter::once(&header)
+                .chain(rows.iter())
+                .map(|row| display_width(&row[i]))
+                .max()
+                .unwrap_or(0)
+        })
+        .collect();
+
+    let border = |left: &str, middle: &str, right: &str| {
+        let line: Vec<String> = widths.iter().map(|w| "─".repeat(w + 2)).collect();
+        style.paint(DIM, &format!("{}{}{}", left, line.join(middle), right))
+    };
+    let bar = style.paint(DIM, "│");
+    let format_row = |row: &[String], bold: bool| {
+        let cells: Vec<String> = row
+            .iter()
+            .enumerate()
+            .map(|(i, cell)| {
+                let align = aligns.get(i).copied().unwrap_or(Align::Left);
+                let cell = if bold { style.paint(BOLD, cell) } else { cell.clone() };
+                format!(" {} ", pad(&cell, widths[i], align))
+            })
+            .collect();
+        format!("{}{}{}", bar, cells.join(&bar), bar)
+    };
+
+    out.push(border("┌", "┬", "┐"));
+    out.push(format_row(&header, true));
+    out.push(border("├", "┼", "┤"));
+    for row in &rows {
+        out.push(format_row(row, false));
+    }
+    out.push(border("└", "┴", "┘"));
+}
+
+// 表示幅に合わせて空白で埋める
+pub fn pad(text: &str, width: usize, align: Align) -> String {
+    let space = width.saturating_sub(display_width(text));
+    match align {
+        Align::Left => format!("{}{}", text, " ".repeat(space)),
+        Align::Right => format!("{}{}", " ".repeat(space), text),
+        Align::Center => {
+            let left = space / 2;
+            format!("{}{}{}", " ".repeat(left), text, " ".repeat(space - left))
+        }
+    }
+}
+
+// 目次（`#`の見出しを太字、`##`の見出しを箇条書きにする）
+pub fn render_toc(entries: &[TocEntry], style: Style) -> String {
+    let mut out = String::new();
+    for entry in entries {
+        if entry.level == 1 {
+            out.push_str(&style.paint(BOLD, &entry.text));
+        } else {
+            out.push_str(&format!("  {} {}", style.paint(CYAN, "•"), entry.text));
+        }
+        out.push('\n');
+    }
+    out
+}
+
+// 3. 左右に並べた表示
+// 端末の幅（環境変数COLUMNS、なければ120桁）
+pub fn width() -> usize {
+    env::var("COLUMNS")
+        .ok()
+        .and_then(|columns| columns.trim().parse().ok())
+        .filter(|&columns: &usize| columns >= 40)
+        .unwrap_or(120)
+}
+
+// 2つの列を`│`で区切って並べる（列の幅を超える行は折り返す）
+pub fn side_by_side(left: &[String], right: &[String], width: usize, style: Style) -> Vec<String> {
+    let left_width = width.saturating_sub(3) / 2;
+    let right_width = width.saturating_sub(3 + left_width);
+    let wrap_all = |lines: &[String], width| -> Vec<String> {
+        lines.iter().flat_map(|line| wrap(line, width)).collect()
+    };
+    let left = wrap_all(left, left_width);
+    let right = wrap_all(right, right_width);
+    let bar = style.paint(DIM, "│");
+
+    (0..left.len().max(right.len()))
+        .map(|i| {
+            let cell = left.get(i).map_or("", String::as_str);
+            let line = format!("{} {} {}", pad(cell, left_width, Align::Left), bar, right.get(i).map_or("", String::as_str));
+            line.trim_end().to_string()
+        })
+        .collect()
+}
+
+// 表示幅が`width`を超える行を分割する
+// 色の途中で分割したときは、行末でリセットして次の行の先頭で同じ色を付け直す
+pub fn wrap(line: &str, width: usize) -> Vec<String> {
+    let mut lines = Vec::new();
+    let mut current = String::new();
+    let mut current_width = 0;
+    let mut active = String::new();
+    let mut chars = line.chars();
+
+    while let Some(c) = chars.next() {
+        if c == '\x1b' {
+            let mut sequence = String::from(c);
+            for c in chars.by_ref() {
+                sequence.push(c);
+                if c.is_ascii_alphabetic() {
+                    break;
+                }
+            }
+            if sequence == RESET {
+                active.clear();
+            } else {
+                active.push_str(&sequence);
+            }
+            current.push_str(&sequence);
+            continue;
+        }
+
+        let c_width = char_width(c);
+        if width > 0 && current_width + c_width > width {
+            if !active.is_empty() {
+                current.push_str(RESET);
+            }
+            lines.push(std::mem::replace(&mut current, active.clone()));
+            current_width = 0;
+        }
+        current.push(c);
+        current_width += c_width;
+    }
+    lines.push(current);
+    lines
+}
+
+// 4. インライン要素の描画
+pub fn inline(text: &str, style: Style) -> String {
+    parse_inline(text)
+        .into_iter()
+        .map(|element| match element {
+            Inline::Text(text) => text,
+            Inline::Code(code) if style.color => style.paint(CYAN, &code),
+            Inline::Code(code) => format!("`{}`", code),
+            Inline::Strong(text) => style.paint(BOLD, &text),
+            Inline::Link { text, url } => {
+                format!("{} ({})", style.paint(UNDERLINE, &text), style.paint(DIM, &url))
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::markdown::{parse, toc};
+
+    const PLAIN: Style = Style { color: false };
+
+    fn render(source: &str, style: Style) -> String {
+        render_blocks(&parse(source), style)
+    }
+
+    #[test]
+    fn plain_output_has_no_escape_sequences() {
+        let source = "# タイトル\n\n**太字**と`code`\n\n```rust\nlet x = 5;\n```\n";
+        let output = render(source, PLAIN);
+        assert!(!output.contains('\x1b'));
+        assert_eq!(
+            output,
+            "タイトル\n════════\n\n太字と`code`\n\n  ┌ rust\n  │ let x = 5;\n"
+        );
+    }
+
+    #[test]
+    fn tables_align_full_width_text() {
+        let output = render("| 型 | 説明 |\n|---|---|\n| i32 | 整数 |\n", PLAIN);
+        let lines: Vec<&str> = output.lines().collect();
+        assert_eq!(lines[1], "│ 型  │ 説明 │");
+        assert_eq!(lines[3], "│ i32 │ 整数 │");
+        let widths: Vec<usize> = lines.iter().map(|line| display_width(line)).collect();
+        assert!(widths.iter().all(|&w| w == widths[0]), "{:?}", widths);
+    }
+
+    #[test]
+    fn colored_code_resets_on_every_line() {
+        let output = render("```rust\n/* 複数行\nコメント */\n```\n", Style { color: true });
+        for line in output.lines().filter(|line| line.contains("│")) {
+            assert!(line.ends_with(RESET), "{:?}", line);
+        }
+    }
+
+    #[test]
+    fn source_has_line_numbers() {
+        let lines = render_source("fn main() {\n    let x = 5;\n}\n", 9, PLAIN);
+        assert_eq!(lines, vec![" 9 │ fn main() {", "10 │     let x = 5;", "11 │ }"]);
+    }
+
+    #[test]
+    fn wraps_by_display_width_and_keeps_color() {
+        assert_eq!(wrap("所有権と借用", 5), vec!["所有", "権と", "借用"]);
+        assert_eq!(wrap("", 5), vec![""]);
+
+        let colored = Style { color: true }.paint(RED, "abcdef");
+        let lines = wrap(&colored, 4);
+        assert_eq!(lines, vec![format!("{}abcd{}", RED, RESET), format!("{}ef{}", RED, RESET)]);
+    }
+
+    #[test]
+    fn side_by_side_pads_left_column() {
+        let left = vec!["見出し".to_string(), "本文が長い行".to_string()];
+        let right = vec!["fn a() {}".to_string()];
+        let lines = side_by_side(&left, &right, 21, PLAIN);
+        assert_eq!(lines, vec!["見出し    │ fn a() {}", "本文が長  │", "い行      │"]);
+    }
+
+    #[test]
+    fn toc_lists_second_level_headings() {
+        let entries = toc(&parse("# 題名\n\n## 1. `let`\n\n### 細目\n\n## 2. 次"));
+        assert_eq!(render_toc(&entries, PLAIN), "題名\n  • 1. let\n  • 2. 次\n");
+    }
+
+    #[test]
+    fn lists_use_bullets_and_indentation() {
+        let output = render("- 親\n  - 子\n1. 一番", PLAIN);
+        assert_eq!(output, "  • 親\n    ◦ 子\n  1. 一番\n");
+    }
+}
@@ -0,0 +1,27 @@
+// 演習: 参照と借用
+//
+// `total_length`は2つの文字列の長さの合計を返す関数です。
+// 呼び出した後も文字列を使えるように、所有権ではなく参照を受け取るように修正してください。
+//
+// 確認: cargo run -- exercise check borrowing
+
+pub fn total_length(a: String, b: String) -> usize {
+    a.len() + b.len()
+}
+
+pub fn describe() -> String {
+    let first = String::from("hello");
+    let second = String::from("world");
+    let length = total_length(first, second);
+    format!("{}と{}の長さの合計は{}", first, second, length)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn strings_are_still_usable() {
+        assert_eq!(describe(), "helloとworldの長さの合計は10");
+    }
+}
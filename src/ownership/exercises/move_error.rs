@@ -0,0 +1,26 @@
+// 演習: 所有権の移動
+//
+// デモ`ownership_basics`でコメントアウトされていた、ムーブ後に`s1`を使う行と同じ問題です。
+// `s1`を`s2`に代入した後も`s1`を使えるように修正してください。
+// （`s1`と`s2`の両方が"hello"を持つようにします）
+//
+// 確認: cargo run -- exercise check move_error
+
+pub fn two_greetings() -> (String, String) {
+    let s1 = String::from("hello");
+    let s2 = s1;
+    println!("{}", s1);
+    (s1, s2)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn both_strings_are_usable() {
+        let (s1, s2) = two_greetings();
+        assert_eq!(s1, "hello");
+        assert_eq!(s2, "hello");
+    }
+}
@@ -1,8 +1,12 @@
 // 所有権システムの学習
 // Rustの最も重要な概念の一つである所有権について学びます
 
+use crate::layout::{self, Shape, TypeLayout};
+use crate::section::{self, Demo, DocPage, Exercise, Section, SourceFile};
+use std::io::{self, Write};
+
 // 1. 所有権の基本ルール
-pub fn ownership_basics() {
+pub fn ownership_basics(out: &mut dyn Write) -> io::Result<()> {
     // 所有権のルール：
     // 1. Rustの各値は、所有者と呼ばれる変数を持つ
     // 2. 値の所有者は同時に一つだけ
@@ -10,31 +14,32 @@ pub fn ownership_basics() {
     
     {
         let s1 = String::from("hello");  // s1がStringの所有者
-        println!("s1: {}", s1);
+        writeln!(out, "s1: {}", s1)?;
         
         let s2 = s1;  // 所有権がs2に移動（ムーブ）
-        // println!("{}", s1);  // エラー！s1はもう使えない
-        println!("s2: {}", s2);
+        // writeln!(out, "{}", s1)?;  // エラー！s1はもう使えない
+        writeln!(out, "s2: {}", s2)?;
     }  // ここでs2がスコープを抜け、メモリが解放される
     
     // スタックに保存される型はコピーされる
     let x = 5;
     let y = x;  // xの値がコピーされる
-    println!("x = {}, y = {}", x, y);  // 両方使える！
+    writeln!(out, "x = {}, y = {}", x, y)?;  // 両方使える！
+    Ok(())
 }
 
 // 2. 参照と借用
-pub fn references_and_borrowing() {
+pub fn references_and_borrowing(out: &mut dyn Write) -> io::Result<()> {
     let s1 = String::from("hello");
     
     // 不変な参照（借用）
     let len = calculate_length(&s1);
-    println!("'{}'の長さは{}です", s1, len);  // s1はまだ使える！
+    writeln!(out, "'{}'の長さは{}です", s1, len)?;  // s1はまだ使える！
     
     // 可変な参照
     let mut s = String::from("hello");
     change(&mut s);
-    println!("変更後: {}", s);
+    writeln!(out, "変更後: {}", s)?;
     
     // 参照のルール：
     // 1. 任意の時点で、1つの可変参照か、複数の不変参照のどちらか
@@ -42,10 +47,11 @@ pub fn references_and_borrowing() {
     
     let r1 = &s;
     let r2 = &s;  // OK：複数の不変参照
-    println!("{} and {}", r1, r2);
+    writeln!(out, "{} and {}", r1, r2)?;
     
     let r3 = &mut s;  // OK：r1とr2はもう使われない
-    println!("{}", r3);
+    writeln!(out, "{}", r3)?;
+    Ok(())
 }
 
 fn calculate_length(s: &String) -> usize {
@@ -57,7 +63,7 @@ fn change(s: &mut String) {
 }
 
 // 3. スライス
-pub fn slices() {
+pub fn slices(out: &mut dyn Write) -> io::Result<()> {
     let s = String::from("hello world");
     
     // 文字列スライス
@@ -65,17 +71,18 @@ pub fn slices() {
     let world = &s[6..11]; // または &s[6..]
     let whole = &s[..];    // 文字列全体のスライス
     
-    println!("前半: '{}', 後半: '{}'", hello, world);
-    println!("全体: '{}'", whole);
+    writeln!(out, "前半: '{}', 後半: '{}'", hello, world)?;
+    writeln!(out, "全体: '{}'", whole)?;
     
     // 配列スライス
     let a = [1, 2, 3, 4, 5];
     let slice = &a[1..3];  // [2, 3]
-    println!("配列スライス: {:?}", slice);
+    writeln!(out, "配列スライス: {:?}", slice)?;
     
     // 実用的な例：最初の単語を見つける
     let first = first_word(&s);
-    println!("最初の単語: '{}'", first);
+    writeln!(out, "最初の単語: '{}'", first)?;
+    Ok(())
 }
 
 fn first_word(s: &str) -> &str {
@@ -91,29 +98,30 @@ fn first_word(s: &str) -> &str {
 }
 
 // 4. 所有権とメソッド
-pub fn ownership_and_functions() {
+pub fn ownership_and_functions(out: &mut dyn Write) -> io::Result<()> {
     // 値を関数に渡す
     let s = String::from("hello");
-    takes_ownership(s);  // sの所有権が関数に移動
-    // println!("{}", s);  // エラー！sはもう使えない
+    takes_ownership(out, s)?;  // sの所有権が関数に移動
+    // writeln!(out, "{}", s)?;  // エラー！sはもう使えない
     
     let x = 5;
-    makes_copy(x);  // xはコピーされる
-    println!("xはまだ使える: {}", x);  // OK
+    makes_copy(out, x)?;  // xはコピーされる
+    writeln!(out, "xはまだ使える: {}", x)?;  // OK
     
     // 値を返す
     let s1 = gives_ownership();  // 関数から所有権を受け取る
     let s2 = String::from("hello");
     let s3 = takes_and_gives_back(s2);  // s2を渡して、s3で受け取る
-    println!("s1: {}, s3: {}", s1, s3);
+    writeln!(out, "s1: {}, s3: {}", s1, s3)?;
+    Ok(())
 }
 
-fn takes_ownership(some_string: String) {
-    println!("受け取った文字列: {}", some_string);
+fn takes_ownership(out: &mut dyn Write, some_string: String) -> io::Result<()> {
+    writeln!(out, "受け取った文字列: {}", some_string)
 }  // ここでsome_stringがスコープを抜け、`drop`が呼ばれる
 
-fn makes_copy(some_integer: i32) {
-    println!("受け取った整数: {}", some_integer);
+fn makes_copy(out: &mut dyn Write, some_integer: i32) -> io::Result<()> {
+    writeln!(out, "受け取った整数: {}", some_integer)
 }
 
 fn gives_ownership() -> String {
@@ -135,51 +143,150 @@ struct Book {
 
 impl Book {
     // selfを取る：所有権を奪う
-    fn consume(self) {
-        println!("{}を読み終わりました", self.title);
+    fn consume(self, out: &mut dyn Write) -> io::Result<()> {
+        writeln!(out, "{}を読み終わりました", self.title)
     }
     
     // &selfを取る：借用
-    fn display(&self) {
-        println!("「{}」by {} ({}ページ)", self.title, self.author, self.pages);
+    fn display(&self, out: &mut dyn Write) -> io::Result<()> {
+        writeln!(out, "「{}」by {} ({}ページ)", self.title, self.author, self.pages)
     }
     
     // &mut selfを取る：可変借用
-    fn add_pages(&mut self, additional: u32) {
+    fn add_pages(&mut self, out: &mut dyn Write, additional: u32) -> io::Result<()> {
         self.pages += additional;
-        println!("ページ数を{}に更新しました", self.pages);
+        writeln!(out, "ページ数を{}に更新しました", self.pages)
     }
 }
 
-pub fn struct_ownership_example() {
+pub fn struct_ownership_example(out: &mut dyn Write) -> io::Result<()> {
     let mut book = Book {
         title: String::from("プログラミングRust"),
         author: String::from("Jim Blandy"),
         pages: 600,
     };
     
-    book.display();  // 借用なのでbookはまだ使える
-    book.add_pages(50);  // 可変借用
-    book.display();  // まだ使える
+    book.display(out)?;  // 借用なのでbookはまだ使える
+    book.add_pages(out, 50)?;  // 可変借用
+    book.display(out)?;  // まだ使える
     
-    book.consume();  // 所有権が移動
-    // book.display();  // エラー！bookはもう使えない
+    book.consume(out)?;  // 所有権が移動
+    // book.display(out)?;  // エラー！bookはもう使えない
+    Ok(())
 }
 
+// デモの一覧（実行順）
+pub static DEMOS: &[Demo] = &[
+    Demo { name: "ownership_basics", title: "所有権の基本", title_en: "Ownership Basics", run: ownership_basics },
+    Demo { name: "references_and_borrowing", title: "参照と借用", title_en: "References and Borrowing", run: references_and_borrowing },
+    Demo { name: "slices", title: "スライス", title_en: "Slices", run: slices },
+    Demo { name: "ownership_and_functions", title: "所有権と関数", title_en: "Ownership and Functions", run: ownership_and_functions },
+    Demo { name: "struct_ownership_example", title: "構造体と所有権", title_en: "Structs and Ownership", run: struct_ownership_example },
+];
+
 // メインの実行関数
-pub fn run_all_ownership() {
-    println!("\n=== 所有権の基本 ===");
-    ownership_basics();
-    
-    println!("\n=== 参照と借用 ===");
-    references_and_borrowing();
-    
-    println!("\n=== スライス ===");
-    slices();
-    
-    println!("\n=== 所有権と関数 ===");
-    ownership_and_functions();
-    
-    println!("\n=== 構造体と所有権 ===");
-    struct_ownership_example();
-}
\ No newline at end of file
+pub fn run_all_ownership(out: &mut dyn Write) -> io::Result<()> {
+    section::run_demos(DEMOS, out)
+}
+
+// セクションの登録
+pub struct OwnershipSection;
+
+impl Section for OwnershipSection {
+    fn id(&self) -> &'static str {
+        "ownership"
+    }
+
+    fn title_ja(&self) -> &'static str {
+        "所有権システム"
+    }
+
+    fn title_en(&self) -> &'static str {
+        "Ownership"
+    }
+
+    fn topics(&self) -> &'static str {
+        "所有権、借用、スライス"
+    }
+
+    fn topics_en(&self) -> &'static str {
+        "Ownership, borrowing, slices"
+    }
+
+    fn run(&self, out: &mut dyn Write) -> io::Result<()> {
+        run_all_ownership(out)
+    }
+
+    fn demos(&self) -> &'static [Demo] {
+        DEMOS
+    }
+
+    fn source(&self) -> SourceFile {
+        SourceFile {
+            path: "ownership/mod.rs",
+            content: include_str!("mod.rs"),
+        }
+    }
+
+    fn doc_pages(&self) -> &'static [DocPage] {
+        &[
+            DocPage {
+                name: "readme",
+                path: "ownership/README.md",
+                description: "所有権システムの詳細",
+                content: include_str!("README.md"),
+                path_en: "ownership/README.en.md",
+                description_en: "Details of the ownership system",
+                content_en: include_str!("README.en.md"),
+            },
+            DocPage {
+                name: "stack-heap",
+                path: "ownership/スタックとヒープの使い分け.md",
+                description: "スタックとヒープの使い分けガイド",
+                content: include_str!("スタックとヒープの使い分け.md"),
+                path_en: "ownership/stack-and-heap.en.md",
+                description_en: "A guide to choosing between the stack and the heap",
+                content_en: include_str!("stack-and-heap.en.md"),
+            },
+        ]
+    }
+
+    fn quiz(&self) -> SourceFile {
+        SourceFile {
+            path: "ownership/quiz.md",
+            content: include_str!("quiz.md"),
+        }
+    }
+
+    fn cards(&self) -> SourceFile {
+        SourceFile {
+            path: "ownership/cards.md",
+            content: include_str!("cards.md"),
+        }
+    }
+
+    fn exercises(&self) -> &'static [Exercise] {
+        &[
+            Exercise {
+                name: "move_error",
+                path: "ownership/exercises/move_error.rs",
+                hint: "`String`は代入すると所有権が移動します。`s1.clone()`で複製すれば、両方の変数を使えます。",
+                content: include_str!("exercises/move_error.rs"),
+            },
+            Exercise {
+                name: "borrowing",
+                path: "ownership/exercises/borrowing.rs",
+                hint: "引数の型を`&str`（または`&String`）にし、呼び出し側で`&first`のように参照を渡します。",
+                content: include_str!("exercises/borrowing.rs"),
+            },
+        ]
+    }
+
+    fn layouts(&self) -> Vec<TypeLayout> {
+        vec![TypeLayout::of::<Book>(Shape::Struct(vec![
+            layout::field!(Book, title),
+            layout::field!(Book, author),
+            layout::field!(Book, pages),
+        ]))]
+    }
+}
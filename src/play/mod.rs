@@ -0,0 +1,835 @@
+// 実験用のスクラッチファイル（`play <セクション>::<デモ>`）
+// デモの関数と、それが使う構造体・関数・implだけを切り出して`main`から呼ぶファイルを一時ディレクトリに作り、
+// `$EDITOR`で編集したあと、手元の`rustc`でコンパイルして実行します（ネットワーク接続は不要です）
+//
+// 編集したファイルは名前を付けて記録のディレクトリ（`play/<名前>.rs`）に保存するので、
+// `play open <名前>`でいつでも続きから試せます
+
+use crate::exercise;
+use crate::markdown::{terminal, terminal::Style, Align, Block};
+use crate::progress;
+use crate::section::{self, Demo, Section};
+use crate::source;
+use std::collections::hash_map::RandomState;
+use std::env;
+use std::fs;
+use std::hash::{BuildHasher, Hasher};
+use std::io::{self, BufRead, Write};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+const HEADER: &str = "# hello_cargo play v1";
+
+// 1. デモからスクラッチファイルを作る
+#[derive(Debug, PartialEq)]
+enum Kind<'a> {
+    Use,                     // `use std::…`
+    Definition(&'a str),     // 関数・構造体・列挙型・トレイトなど（定義する名前）
+    Impl(&'a str),           // implの対象の型
+    Other,                   // `use crate::…`など、スクラッチファイルには含めないもの
+}
+
+// 項目の宣言の行（直前のコメントや属性の次の行）から種類を判断する
+fn classify(code: &str) -> Kind<'_> {
+    let declaration = code
+        .lines()
+        .map(str::trim_start)
+        .find(|line| !(line.starts_with("//") || line.starts_with("#[")))
+        .unwrap_or("");
+    let mut words = declaration
+        .split_whitespace()
+        .skip_while(|word| matches!(*word, "pub" | "pub(crate)" | "unsafe" | "async"));
+    match words.next() {
+        Some("use") if declaration.contains("use std::") => Kind::Use,
+        Some(word) if word == "impl" || word.starts_with("impl<") => Kind::Impl(impl_target(declaration)),
+        Some("const") => match words.next() {
+            Some("fn") => words.next().map_or(Kind::Other, |name| Kind::Definition(identifier(name))),
+            name => name.map_or(Kind::Other, |name| Kind::Definition(identifier(name))),
+        },
+        Some("fn" | "struct" | "enum" | "trait" | "type" | "static" | "mod") => {
+            words.next().map_or(Kind::Other, |name| Kind::Definition(identifier(name)))
+        }
+        _ => Kind::Other,
+    }
+}
+
+// `impl<T> Point<T> {`や`impl fmt::Display for MathError {`の対象の型
+fn impl_target(declaration: &str) -> &str {
+    let mut rest = declaration[declaration.find("impl").map_or(0, |i| i + 4)..].trim_start();
+    // `impl<T, U>`の型引数を飛ばす
+    if rest.starts_with('<') {
+        let mut depth = 0;
+        for (i, c) in rest.char_indices() {
+            match c {
+                '<' => depth += 1,
+                '>' if depth == 1 => {
+                    rest = &rest[i + 1..];
+                    break;
+                }
+                '>' => depth -= 1,
+                _ => {}
+            }
+        }
+    }
+    let target = rest.split_once(" for ").map_or(rest, |(_, target)| target);
+    identifier(target.trim_start())
+}
+
+fn identifier(text: &str) -> &str {
+    let end = text.find(|c: char| !is_identifier_char(c)).unwrap_or(text.len());
+    &text[..end]
+}
+
+fn is_identifier_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_'
+}
+
+// コードに名前が単語として出てくるか（`Point`は`Point2D`に含まれない）
+fn mentions(code: &str, name: &str) -> bool {
+    !name.is_empty()
+        && code.match_indices(name).any(|(i, _)| {
+            let before = code[..i].chars().next_back();
+            let after = code[i + name.len()..].chars().next();
+            // `io::Error`の`Error`は、取り込んだ名前ではない
+            !before.is_some_and(is_identifier_char) && !after.is_some_and(is_identifier_char) && !code[..i].ends_with("::")
+        })
+}
+
+// デモの関数から始めて、使われている項目を順にたどって集める
+// （implは対象の型が含まれていれば含める。トレイトのimplなら、宣言に出てくるトレイトもたどられる）
+pub fn seed(section: &dyn Section, demo: &Demo) -> Option<String> {
+    let content = section.source().content;
+    let run = run_function(content, demo.name);
+    let items: Vec<(&str, Kind)> = source::items(content)
+        .into_iter()
+        .map(|item| (item.code, classify(item.code)))
+        .collect();
+    let start = items.iter().position(|(_, kind)| *kind == Kind::Definition(run))?;
+
+    let mut selected = vec![false; items.len()];
+    selected[start] = true;
+    loop {
+        let needed: Vec<usize> = (0..items.len())
+            .filter(|&i| !selected[i])
+            .filter(|&i| {
+                let used = |name: &str| (0..items.len()).any(|j| selected[j] && mentions(items[j].0, name));
+                match items[i].1 {
+                    Kind::Definition(name) => used(name),
+                    Kind::Impl(target) => items.iter().zip(&selected).any(|((_, kind), &s)| s && *kind == Kind::Definition(target)),
+                    Kind::Use | Kind::Other => false,
+                }
+            })
+            .collect();
+        if needed.is_empty() {
+            break;
+        }
+        for i in needed {
+            selected[i] = true;
+        }
+    }
+
+    // `use`は、取り込む名前のどれかが使われていれば行ごと含める
+    // （`Read`のようにメソッドを呼ぶためだけのトレイトは名前が出てこないため、1つずつは選ばない）
+    let code: Vec<&str> = (0..items.len()).filter(|&i| selected[i]).map(|i| items[i].0).collect();
+    let uses: Vec<&str> = items
+        .iter()
+        .filter(|(_, kind)| *kind == Kind::Use)
+        .map(|(use_code, _)| imported_names(use_code))
+        .filter(|(_, names)| names.iter().any(|name| code.iter().any(|code| mentions(code, name))))
+        .map(|(declaration, _)| declaration)
+        .collect();
+
+    // 学習用のコード例には説明のためだけの型やフィールドがあるので、`lib.rs`と同じく警告を抑制する
+    let target = format!("{}::{}", section.id(), demo.name);
+    let mut text = format!("// {}\n// {}\n\n", tr!("play.seed_from", target), tr!("play.seed_hint"));
+    text.push_str("#![allow(dead_code, private_interfaces, unused_imports)]\n\n");
+    for use_code in &uses {
+        text.push_str(use_code);
+        text.push('\n');
+    }
+    for code in code {
+        text.push('\n');
+        text.push_str(code);
+        text.push('\n');
+    }
+    text.push_str(&format!("\nfn main() -> std::io::Result<()> {{\n    {}(&mut std::io::stdout())\n}}\n", run));
+    Some(text)
+}
+
+// デモの一覧（`DEMOS`）で、デモの名前に対応する関数（`name: "find_max", … run: find_max_demo`）
+fn run_function<'a>(source: &'a str, demo: &'a str) -> &'a str {
+    let name = format!("name: \"{}\"", demo);
+    source
+        .lines()
+        .filter(|line| line.contains(&name))
+        .find_map(|line| line.split_once("run:").map(|(_, run)| identifier(run.trim_start())))
+        .unwrap_or(demo)
+}
+
+// `use std::io::{self, Read, Write};`の宣言の行と、取り込む名前（`io`、`Read`、`Write`）
+// 直前のコメント（見出しなど）は含めない
+fn imported_names(use_code: &str) -> (&str, Vec<&str>) {
+    let declaration = use_code.lines().map(str::trim).find(|line| line.starts_with("use ")).unwrap_or("");
+    let path = declaration.trim_start_matches("use ").trim_end_matches(';');
+    let names = match path.split_once("::{") {
+        Some((module, names)) => names
+            .trim_end_matches('}')
+            .split(',')
+            .map(str::trim)
+            .map(|name| if name == "self" { module.rsplit("::").next().unwrap_or(module) } else { name })
+            .collect(),
+        None => vec![path.rsplit("::").next().unwrap_or(path)],
+    };
+    (declaration, names)
+}
+
+// 2. スクラッチファイルの履歴（タブ区切り、1行に1つ）
+//   <名前>  <元のデモ>  <作成した時刻>  <最後に実行した時刻>  <結果>
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Outcome {
+    Succeeded,     // 実行して終了コード0で終わった
+    Failed,        // 実行したが、パニックなどで失敗した
+    CompileError,  // コンパイルできなかった
+}
+
+impl Outcome {
+    fn as_str(self) -> &'static str {
+        match self {
+            Outcome::Succeeded => "ok",
+            Outcome::Failed => "failed",
+            Outcome::CompileError => "error",
+        }
+    }
+
+    fn parse(text: &str) -> Option<Outcome> {
+        match text {
+            "ok" => Some(Outcome::Succeeded),
+            "failed" => Some(Outcome::Failed),
+            "error" => Some(Outcome::CompileError),
+            _ => None,
+        }
+    }
+
+    fn label(self) -> &'static str {
+        match self {
+            Outcome::Succeeded => tr!("play.outcome.ok"),
+            Outcome::Failed => tr!("play.outcome.failed"),
+            Outcome::CompileError => tr!("play.outcome.error"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Scratch {
+    pub name: String,
+    pub seed: String,              // 元にしたデモ（例: `collections::hash_maps`）
+    pub created: u64,
+    pub updated: u64,              // 最後にコンパイル・実行した時刻
+    pub outcome: Option<Outcome>,  // まだ実行していなければNone
+}
+
+// 作成した順に並ぶ
+#[derive(Debug, Default, PartialEq)]
+pub struct History {
+    pub scratches: Vec<Scratch>,
+}
+
+impl History {
+    pub fn parse(text: &str) -> Result<History, String> {
+        let mut history = History::default();
+        for (i, line) in text.lines().enumerate() {
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let error = || tr!("play.invalid_line", i + 1);
+            let fields: Vec<&str> = line.split('\t').collect();
+            let [name, seed, created, updated, outcome] = fields[..] else {
+                return Err(error());
+            };
+            history.scratches.push(Scratch {
+                name: name.to_string(),
+                seed: seed.to_string(),
+                created: created.parse().map_err(|_| error())?,
+                updated: updated.parse().map_err(|_| error())?,
+                outcome: if outcome == "-" { None } else { Some(Outcome::parse(outcome).ok_or_else(error)?) },
+            });
+        }
+        Ok(history)
+    }
+
+    pub fn to_text(&self) -> String {
+        let mut text = format!("{}\n", HEADER);
+        for s in &self.scratches {
+            let outcome = s.outcome.map_or("-", Outcome::as_str);
+            text.push_str(&format!("{}\t{}\t{}\t{}\t{}\n", s.name, s.seed, s.created, s.updated, outcome));
+        }
+        text
+    }
+
+    pub fn load(path: &Path) -> io::Result<History> {
+        match fs::read_to_string(path) {
+            Ok(text) => History::parse(&text).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e)),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(History::default()),
+            Err(e) => Err(e),
+        }
+    }
+
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        // 書き込み途中で中断しても壊れないよう、一時ファイルに書いてから置き換える
+        let temp = path.with_extension("tsv.tmp");
+        fs::write(&temp, self.to_text())?;
+        fs::rename(&temp, path)
+    }
+
+    pub fn find(&self, name: &str) -> Option<&Scratch> {
+        self.scratches.iter().find(|scratch| scratch.name == name)
+    }
+
+    // 同じ名前があれば置き換え、なければ末尾に追加する
+    pub fn record(&mut self, scratch: Scratch) {
+        match self.scratches.iter_mut().find(|s| s.name == scratch.name) {
+            Some(existing) => *existing = scratch,
+            None => self.scratches.push(scratch),
+        }
+    }
+
+    pub fn remove(&mut self, name: &str) -> bool {
+        let before = self.scratches.len();
+        self.scratches.retain(|scratch| scratch.name != name);
+        self.scratches.len() != before
+    }
+
+    // 使われていない名前（`hash_maps`、`hash_maps-2`、`hash_maps-3`…）
+    pub fn unique_name(&self, base: &str) -> String {
+        (1..)
+            .map(|n| if n == 1 { base.to_string() } else { format!("{}-{}", base, n) })
+            .find(|name| self.find(name).is_none())
+            .unwrap_or_default()
+    }
+}
+
+// ファイル名として使える名前だけを受け付ける
+fn valid_name(name: &str) -> bool {
+    !name.is_empty() && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+}
+
+// 保存先（`$XDG_DATA_HOME/hello_cargo/play/`）
+fn history_dir() -> Option<PathBuf> {
+    Some(progress::data_dir()?.join("play"))
+}
+
+// コンパイルする一時ディレクトリ（編集・実行を始めるたびに新しく作り、終わったら削除する）
+// ほかのユーザーが先に作っておけないよう、推測できない名前で作り、すでにあれば別の名前にする
+fn create_project_dir() -> io::Result<PathBuf> {
+    for _ in 0..16 {
+        let mut hasher = RandomState::new().build_hasher();
+        hasher.write_u64(progress::now());
+        let dir = env::temp_dir().join(format!("hello_cargo-play-{:016x}", hasher.finish()));
+        match fs::create_dir(&dir) {
+            Ok(()) => return Ok(dir),
+            Err(e) if e.kind() == io::ErrorKind::AlreadyExists => continue,
+            Err(e) => return Err(e),
+        }
+    }
+    Err(io::Error::new(io::ErrorKind::AlreadyExists, env::temp_dir().display().to_string()))
+}
+
+// 3. コンパイルと実行
+// rustcが作る実行ファイルの名前（Windowsでは`play.exe`）
+fn binary_name() -> String {
+    format!("play{}", env::consts::EXE_SUFFIX)
+}
+
+enum Build {
+    Succeeded(String),  // 警告（あれば）
+    Failed(String),     // rustcの診断メッセージ
+}
+
+fn build(project: &Path, color: bool) -> io::Result<Build> {
+    // 診断メッセージに`main.rs:行:列`と出るように、プロジェクトのディレクトリで相対パスを渡す
+    let compiled = Command::new(exercise::rustc())
+        .args(["--edition", "2024", "--crate-type", "bin", "--crate-name", "play"])
+        .args(["--color", if color { "always" } else { "never" }])
+        .args(["-o", &binary_name(), "main.rs"])
+        .current_dir(project)
+        .output()?;
+    let diagnostics = String::from_utf8_lossy(&compiled.stderr).into_owned();
+    Ok(if compiled.status.success() { Build::Succeeded(diagnostics) } else { Build::Failed(diagnostics) })
+}
+
+// 診断メッセージと実行結果をそのまま表示する（rustcが見つからなければNone）
+fn build_and_run(project: &Path, style: Style) -> Option<Outcome> {
+    println!("{}", style.paint(terminal::DIM, tr!("play.compiling")));
+    let build = match build(project, style.color) {
+        Ok(build) => build,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => {
+            println!("{}", tr!("exercise.rustc_not_found"));
+            return None;
+        }
+        Err(e) => {
+            println!("{}", tr!("play.run_error", e));
+            return None;
+        }
+    };
+    match build {
+        Build::Failed(diagnostics) => {
+            print!("{}", diagnostics);
+            println!("{}", style.paint(terminal::RED, tr!("play.compile_failed")));
+            Some(Outcome::CompileError)
+        }
+        Build::Succeeded(warnings) => {
+            print!("{}", warnings);
+            println!("{}", style.paint(terminal::BOLD, tr!("play.output")));
+            io::stdout().flush().ok();
+            match Command::new(project.join(binary_name())).current_dir(project).status() {
+                Ok(status) if status.success() => Some(Outcome::Succeeded),
+                Ok(status) => {
+                    println!("{}", style.paint(terminal::RED, &tr!("play.exited", status)));
+                    Some(Outcome::Failed)
+                }
+                Err(e) => {
+                    println!("{}", tr!("play.run_error", e));
+                    None
+                }
+            }
+        }
+    }
+}
+
+// 4. エディタ
+// `$VISUAL`、なければ`$EDITOR`（`code --wait`のように引数も書ける）
+fn editor() -> Option<Vec<String>> {
+    ["VISUAL", "EDITOR"]
+        .iter()
+        .filter_map(|name| env::var(name).ok())
+        .map(|value| value.split_whitespace().map(str::to_string).collect::<Vec<_>>())
+        .find(|command| !command.is_empty())
+}
+
+// エディタが設定されていなければ、別の画面で編集してもらいEnterを待つ
+fn edit(file: &Path) -> io::Result<bool> {
+    let Some(command) = editor() else {
+        println!("{}", tr!("play.no_editor", file.display()));
+        return wait_for_enter();
+    };
+    let status = Command::new(&command[0]).args(&command[1..]).arg(file).status()?;
+    Ok(status.success())
+}
+
+// Enterならtrue、`q`か入力の終わりならfalse
+fn wait_for_enter() -> io::Result<bool> {
+    io::stdout().flush()?;
+    let mut line = String::new();
+    let read = io::stdin().lock().read_line(&mut line)?;
+    Ok(read > 0 && line.trim() != "q")
+}
+
+// 5. 編集・実行の繰り返し
+// 一時ディレクトリの`main.rs`を編集して実行し、そのたびに履歴へ保存する（終わったら一時ディレクトリは削除する）
+fn session(mut scratch: Scratch, content: &str, edit_first: bool, style: Style) {
+    let project = match create_project_dir() {
+        Ok(project) => project,
+        Err(e) => {
+            println!("{}", tr!("play.create_error", e, env::temp_dir().display()));
+            return;
+        }
+    };
+    let file = project.join("main.rs");
+    if let Err(e) = fs::write(&file, content) {
+        println!("{}", tr!("play.create_error", e, file.display()));
+        fs::remove_dir_all(&project).ok();
+        return;
+    }
+    println!("{}", tr!("play.created", scratch.name, scratch.seed));
+    println!("  {}", style.paint(terminal::DIM, &file.display().to_string()));
+    if history_dir().is_none() {
+        println!("{}", tr!("play.no_history"));
+    }
+
+    loop {
+        if edit_first {
+            match edit(&file) {
+                Ok(true) => {}
+                Ok(false) => break,
+                Err(e) => {
+                    println!("{}", tr!("play.editor_error", e));
+                    break;
+                }
+            }
+        }
+        println!();
+        scratch.outcome = build_and_run(&project, style);
+        scratch.updated = progress::now();
+        save(&scratch, &file);
+
+        if !edit_first {
+            break;
+        }
+        print!("\n{} ", tr!("play.again"));
+        if !wait_for_enter().unwrap_or(false) {
+            break;
+        }
+    }
+    fs::remove_dir_all(&project).ok();
+    if history_dir().is_some() {
+        println!("{}", tr!("play.saved_hint", scratch.name));
+    }
+}
+
+// 編集したファイルを履歴のディレクトリにコピーし、履歴を更新する
+fn save(scratch: &Scratch, file: &Path) {
+    let Some(dir) = history_dir() else {
+        return;
+    };
+    let result = fs::create_dir_all(&dir)
+        .and_then(|()| fs::copy(file, dir.join(format!("{}.rs", scratch.name))))
+        .and_then(|_| {
+            let path = dir.join("history.tsv");
+            let mut history = History::load(&path)?;
+            history.record(scratch.clone());
+            history.save(&path)
+        });
+    if let Err(e) = result {
+        eprintln!("{}", tr!("play.save_error", e, dir.display()));
+    }
+}
+
+fn load_history() -> Option<(History, PathBuf)> {
+    let Some(dir) = history_dir() else {
+        println!("{}", tr!("progress.no_data_dir"));
+        return None;
+    };
+    let path = dir.join("history.tsv");
+    match History::load(&path) {
+        Ok(history) => Some((history, dir)),
+        Err(e) => {
+            println!("{}", tr!("play.load_error", e, path.display()));
+            None
+        }
+    }
+}
+
+// 6. コマンド
+// `play`: 履歴の一覧、`play <セクション>::<デモ>`: 新しいスクラッチファイル、
+// `play open|run|rm <名前>`: 保存したファイルを開く・実行する・削除する
+pub fn command(args: &[String]) {
+    let mut args: Vec<&str> = args.iter().map(String::as_str).collect();
+    let no_edit = take_flag(&mut args, "--no-edit");
+    let name = match args.iter().position(|arg| *arg == "--name") {
+        Some(index) if index + 1 < args.len() => {
+            let name = args[index + 1];
+            args.drain(index..=index + 1);
+            if !valid_name(name) {
+                println!("{}", tr!("play.invalid_name", name));
+                return;
+            }
+            Some(name)
+        }
+        Some(_) => {
+            println!("{}", tr!("main.option_needs_value", "--name"));
+            return;
+        }
+        None => None,
+    };
+
+    let style = Style::detect();
+    match args.as_slice() {
+        [] => list(style),
+        ["help"] => print_help(),
+        ["open", name] => open(name, !no_edit, style),
+        ["run", name] => open(name, false, style),
+        ["rm", name] => remove(name),
+        target => new(target, name, !no_edit, style),
+    }
+}
+
+fn take_flag(args: &mut Vec<&str>, flag: &str) -> bool {
+    let before = args.len();
+    args.retain(|arg| *arg != flag);
+    args.len() != before
+}
+
+// `collections::hash_maps`、`collections/hash_maps`、`collections hash_maps`のどれでも指定できる
+fn split_target<'a>(target: &[&'a str]) -> Option<(&'a str, Option<&'a str>)> {
+    match target {
+        [one] => Some(match one.split_once("::").or_else(|| one.split_once('/')) {
+            Some((section, demo)) => (section, Some(demo)),
+            None => (one, None),
+        }),
+        [section, demo] => Some((section, Some(demo))),
+        _ => None,
+    }
+}
+
+fn new(target: &[&str], name: Option<&str>, edit: bool, style: Style) {
+    let Some((section_name, demo_name)) = split_target(target) else {
+        print_help();
+        return;
+    };
+    let Some(section) = section::find(section_name) else {
+        // セクションでなければ、保存したファイルの名前として開く
+        if demo_name.is_none() && history_dir().is_some_and(|dir| dir.join(format!("{}.rs", section_name)).exists()) {
+            open(section_name, edit, style);
+        } else {
+            println!("{}", tr!("common.unknown_section", section_name));
+            println!("{}", tr!("play.usage"));
+        }
+        return;
+    };
+    let Some(demo_name) = demo_name else {
+        print_demos(section);
+        return;
+    };
+    let demo = match section::select_demos(section.demos(), &[demo_name.to_string()]) {
+        Ok(demos) if demos.len() == 1 => demos[0],
+        Ok(_) => {
+            println!("{}", tr!("play.choose_one", demo_name));
+            return;
+        }
+        Err(pattern) => {
+            println!("{}", tr!("main.unknown_demo", section.id(), pattern));
+            print_demos(section);
+            return;
+        }
+    };
+    let Some(content) = seed(section, demo) else {
+        println!("{}", tr!("source.unknown_function", section.id(), demo.name));
+        return;
+    };
+
+    let history = history_dir().and_then(|dir| History::load(&dir.join("history.tsv")).ok()).unwrap_or_default();
+    let Some(name) = scratch_name(&history, name, demo.name) else {
+        let name = name.unwrap_or_default();
+        println!("{}", tr!("play.name_taken", name, name));
+        return;
+    };
+    let now = progress::now();
+    let scratch = Scratch {
+        name,
+        seed: format!("{}::{}", section.id(), demo.name),
+        created: now,
+        updated: now,
+        outcome: None,
+    };
+    session(scratch, &content, edit, style);
+}
+
+// `--name`がなければデモの関数名から使われていない名前を選ぶ（指定された名前が使われていればNone）
+fn scratch_name(history: &History, requested: Option<&str>, demo: &str) -> Option<String> {
+    match requested {
+        Some(name) => history.find(name).is_none().then(|| name.to_string()),
+        None => Some(history.unique_name(demo)),
+    }
+}
+
+fn open(name: &str, edit: bool, style: Style) {
+    let Some((history, dir)) = load_history() else {
+        return;
+    };
+    let (Some(scratch), Ok(content)) = (history.find(name), fs::read_to_string(dir.join(format!("{}.rs", name)))) else {
+        println!("{}", tr!("play.unknown", name));
+        println!("{}", tr!("play.list_hint"));
+        return;
+    };
+    session(scratch.clone(), &content, edit, style);
+}
+
+fn remove(name: &str) {
+    let Some((mut history, dir)) = load_history() else {
+        return;
+    };
+    if !history.remove(name) {
+        println!("{}", tr!("play.unknown", name));
+        return;
+    }
+    let result = fs::remove_file(dir.join(format!("{}.rs", name)))
+        .or_else(|e| if e.kind() == io::ErrorKind::NotFound { Ok(()) } else { Err(e) })
+        .and_then(|()| history.save(&dir.join("history.tsv")));
+    match result {
+        Ok(()) => println!("{}", tr!("play.removed", name)),
+        Err(e) => println!("{}", tr!("play.save_error", e, dir.display())),
+    }
+}
+
+fn list(style: Style) {
+    let Some((history, dir)) = load_history() else {
+        return;
+    };
+    if history.scratches.is_empty() {
+        println!("{}", tr!("play.empty"));
+        print_help();
+        return;
+    }
+    let now = progress::now();
+    let rows = history
+        .scratches
+        .iter()
+        .map(|s| {
+            vec![
+                s.name.clone(),
+                s.seed.clone(),
+                progress::relative_time(s.updated, now),
+                s.outcome.map_or("-", Outcome::label).to_string(),
+            ]
+        })
+        .collect();
+    let table = Block::Table {
+        header: vec![
+            tr!("play.col.name").to_string(),
+            tr!("play.col.seed").to_string(),
+            tr!("play.col.updated").to_string(),
+            tr!("play.col.outcome").to_string(),
+        ],
+        aligns: vec![Align::Left; 4],
+        rows,
+    };
+    println!("{}\n", tr!("play.title"));
+    print!("{}", terminal::render_blocks(&[table], style));
+    println!("\n{}", style.paint(terminal::DIM, &tr!("play.location", dir.display())));
+    println!("{}", tr!("play.open_hint"));
+}
+
+fn print_demos(section: &dyn Section) {
+    println!("{}", tr!("source.demos", section.id()));
+    for (i, demo) in section.demos().iter().enumerate() {
+        println!("  {:>2}. {:<30}{}", i + 1, demo.name, demo.heading());
+    }
+    println!("\n{}", tr!("play.usage"));
+}
+
+fn print_help() {
+    println!("{}", tr!("play.usage"));
+    println!("\n{}", tr!("play.subcommands"));
+    println!("  {}", tr!("play.cmd.new"));
+    println!("  {}", tr!("play.cmd.list"));
+    println!("  {}", tr!("play.cmd.open"));
+    println!("  {}", tr!("play.cmd.run"));
+    println!("  {}", tr!("play.cmd.rm"));
+    println!("\n{}", tr!("help.options"));
+    println!("  {}", tr!("play.opt.name"));
+    println!("  {}", tr!("play.opt.no_edit"));
+    println!("\n{}", tr!("help.examples"));
+    println!("  {}", tr!("play.ex.new"));
+    println!("  {}", tr!("play.ex.open"));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::thread;
+
+    #[test]
+    fn classifies_items() {
+        assert_eq!(classify("// 説明\n#[derive(Debug)]\nstruct Point<T> {\n}"), Kind::Definition("Point"));
+        assert_eq!(classify("pub fn longest<'a>(x: &'a str) -> &'a str {}"), Kind::Definition("longest"));
+        assert_eq!(classify("impl<T: Display> Point<T> {}"), Kind::Impl("Point"));
+        assert_eq!(classify("impl From<io::Error> for AppError {}"), Kind::Impl("AppError"));
+        assert_eq!(classify("use std::io::{self, Write};"), Kind::Use);
+        assert_eq!(classify("use crate::section::{self, Demo};"), Kind::Other);
+        assert_eq!(imported_names("// 見出し\nuse std::io::{self, Write};"), ("use std::io::{self, Write};", vec!["io", "Write"]));
+        assert_eq!(imported_names("use std::collections::HashMap;"), ("use std::collections::HashMap;", vec!["HashMap"]));
+        assert_eq!(run_function(section::find("basics").unwrap().source().content, "find_max"), "find_max_demo");
+    }
+
+    #[test]
+    fn mentions_whole_words_only() {
+        assert!(mentions("let p = Point { x: 1 };", "Point"));
+        assert!(!mentions("let p = Point2D { x: 1 };", "Point"));
+        assert!(!mentions("build_user(a, b)", "User"));
+        assert!(!mentions("Result<String, io::Error>", "Error"));
+    }
+
+    // 使われている項目だけが含まれること
+    #[test]
+    fn seeds_demo_with_its_dependencies() {
+        let section = section::find("structs").unwrap();
+        let demo = section.demos().iter().find(|demo| demo.name == "game_example").unwrap();
+        let seed = seed(section, demo).unwrap();
+        assert!(seed.contains("enum GameState"));
+        assert!(seed.contains("impl Game {"));
+        assert!(seed.contains("use std::io::{self, Write};"));
+        assert!(!seed.contains("struct User"));
+        assert!(!seed.contains("crate::"));
+        assert!(seed.ends_with("fn main() -> std::io::Result<()> {\n    game_example(&mut std::io::stdout())\n}\n"));
+    }
+
+    // すべてのデモのスクラッチファイルが、そのまま警告なしでコンパイルできること
+    #[test]
+    fn every_demo_seed_compiles() {
+        let root = env::temp_dir().join(format!("hello_cargo-test-{}-play", std::process::id()));
+        thread::scope(|scope| {
+            for section in section::all() {
+                for demo in section.demos() {
+                    let project = root.join(demo.name);
+                    scope.spawn(move || {
+                        fs::create_dir_all(&project).unwrap();
+                        fs::write(project.join("main.rs"), seed(*section, demo).unwrap()).unwrap();
+                        match build(&project, false).unwrap() {
+                            Build::Succeeded(warnings) => assert_eq!(warnings, "", "{}::{}", section.id(), demo.name),
+                            Build::Failed(diagnostics) => panic!("{}::{}\n{}", section.id(), demo.name, diagnostics),
+                        }
+                    });
+                }
+            }
+        });
+        fs::remove_dir_all(&root).ok();
+    }
+
+    #[test]
+    fn round_trips_history() {
+        let mut history = History::default();
+        history.record(Scratch { name: "hash_maps".into(), seed: "collections::hash_maps".into(), created: 10, updated: 20, outcome: Some(Outcome::Succeeded) });
+        history.record(Scratch { name: "slices".into(), seed: "ownership::slices".into(), created: 30, updated: 30, outcome: None });
+        assert_eq!(History::parse(&history.to_text()), Ok(history));
+        assert!(History::parse("hash_maps\tcollections::hash_maps\t10\t20\tmaybe\n").is_err());
+        assert!(History::parse("hash_maps\t10\n").is_err());
+    }
+
+    #[test]
+    fn picks_unused_names() {
+        let mut history = History::default();
+        assert_eq!(history.unique_name("slices"), "slices");
+        for name in ["slices", "slices-2"] {
+            history.record(Scratch { name: name.into(), seed: "ownership::slices".into(), created: 0, updated: 0, outcome: None });
+        }
+        assert_eq!(history.unique_name("slices"), "slices-3");
+        assert!(history.remove("slices"));
+        assert_eq!(history.unique_name("slices"), "slices");
+        assert!(valid_name("my-test_2") && !valid_name("../x") && !valid_name(""));
+    }
+
+    // `--name`で指定した名前が使われていれば、上書きせずに断る
+    #[test]
+    fn refuses_taken_names() {
+        let mut history = History::default();
+        history.record(Scratch { name: "word_count".into(), seed: "collections::hash_maps".into(), created: 0, updated: 0, outcome: None });
+        assert_eq!(scratch_name(&history, Some("word_count"), "hash_maps"), None);
+        assert_eq!(scratch_name(&history, Some("counter"), "hash_maps").as_deref(), Some("counter"));
+        assert_eq!(scratch_name(&history, None, "hash_maps").as_deref(), Some("hash_maps"));
+    }
+
+    // コンパイルする一時ディレクトリは、呼び出すたびに新しく作られる
+    #[test]
+    fn project_dirs_are_unique() {
+        let first = create_project_dir().unwrap();
+        let second = create_project_dir().unwrap();
+        assert_ne!(first, second);
+        assert!(first.is_dir() && second.is_dir());
+        assert!(fs::read_dir(&first).unwrap().next().is_none());
+        fs::remove_dir_all(&first).ok();
+        fs::remove_dir_all(&second).ok();
+        assert!(binary_name().starts_with("play"));
+    }
+
+    #[test]
+    fn splits_targets() {
+        assert_eq!(split_target(&["collections::hash_maps"]), Some(("collections", Some("hash_maps"))));
+        assert_eq!(split_target(&["collections/hash_maps"]), Some(("collections", Some("hash_maps"))));
+        assert_eq!(split_target(&["collections", "3"]), Some(("collections", Some("3"))));
+        assert_eq!(split_target(&["collections"]), Some(("collections", None)));
+        assert_eq!(split_target(&["a", "b", "c"]), None);
+    }
+}
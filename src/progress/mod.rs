@@ -0,0 +1,548 @@
+// 学習の進捗の記録
+// 実行したデモ・読んだドキュメント・クイズの成績・完了した演習を、XDGのデータディレクトリに保存します
+// （`$XDG_DATA_HOME/hello_cargo/progress.tsv`、未設定なら`~/.local/share/hello_cargo/progress.tsv`）
+
+use crate::markdown::{terminal, terminal::Style, Align, Block};
+use crate::section::{self, Demo, DocPage, Exercise, Section};
+use std::collections::BTreeMap;
+use std::env;
+use std::fs;
+use std::io::{self, BufRead, Write};
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+const HEADER: &str = "# hello_cargo progress v1";
+
+// クイズはこの割合以上正解したら修了とみなす
+const QUIZ_PASS_PERCENT: usize = 80;
+
+// 1. 進捗の状態
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct QuizRecord {
+    pub last: (usize, usize),  // 直近の成績（正解数, 出題数）
+    pub best: (usize, usize),  // 最高の成績
+    pub attempts: u32,
+    pub time: u64,
+}
+
+impl QuizRecord {
+    fn passed(&self) -> bool {
+        let (correct, total) = self.best;
+        total > 0 && correct * 100 >= total * QUIZ_PASS_PERCENT
+    }
+}
+
+// キーはいずれも（セクションID, 名前）で、値は最後に学習した時刻（UNIX時間）
+#[derive(Debug, Default, PartialEq)]
+pub struct Progress {
+    pub demos: BTreeMap<(String, String), u64>,
+    pub docs: BTreeMap<(String, String), u64>,
+    pub exercises: BTreeMap<(String, String), u64>,
+    pub quizzes: BTreeMap<String, QuizRecord>,
+}
+
+impl Progress {
+    pub fn record_demo(&mut self, section: &str, demo: &str, time: u64) {
+        self.demos.insert((section.to_string(), demo.to_string()), time);
+    }
+
+    pub fn record_doc(&mut self, section: &str, page: &str, time: u64) {
+        self.docs.insert((section.to_string(), page.to_string()), time);
+    }
+
+    pub fn record_exercise(&mut self, section: &str, exercise: &str, time: u64) {
+        self.exercises.insert((section.to_string(), exercise.to_string()), time);
+    }
+
+    pub fn record_quiz(&mut self, section: &str, correct: usize, total: usize, time: u64) {
+        let record = self.quizzes.entry(section.to_string()).or_insert(QuizRecord {
+            last: (0, 0),
+            best: (0, 0),
+            attempts: 0,
+            time,
+        });
+        record.last = (correct, total);
+        // 正解率が同じか高ければ最高記録を更新する
+        let (best_correct, best_total) = record.best;
+        if best_total == 0 || correct * best_total >= best_correct * total {
+            record.best = (correct, total);
+        }
+        record.attempts += 1;
+        record.time = time;
+    }
+
+    // セクションの記録を消す（Noneならすべて）
+    pub fn reset(&mut self, section: Option<&str>) {
+        match section {
+            Some(id) => {
+                self.demos.retain(|(s, _), _| s != id);
+                self.docs.retain(|(s, _), _| s != id);
+                self.exercises.retain(|(s, _), _| s != id);
+                self.quizzes.remove(id);
+            }
+            None => *self = Progress::default(),
+        }
+    }
+
+    // 2. ファイル形式（タブ区切り、1行に1件）
+    //   demo  <セクション>  <デモ名>    <時刻>
+    //   doc   <セクション>  <ページ名>  <時刻>
+    //   exercise  <セクション>  <演習名>  <時刻>
+    //   quiz  <セクション>  <直近>      <時刻>  <最高>  <回数>   （成績は`正解数/出題数`）
+    pub fn parse(text: &str) -> Result<Progress, String> {
+        let mut progress = Progress::default();
+
+        for (i, line) in text.lines().enumerate() {
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let error = || tr!("progress.invalid_line", i + 1);
+            let fields: Vec<&str> = line.split('\t').collect();
+            let time = |index: usize| fields.get(index).and_then(|t| t.parse::<u64>().ok());
+
+            match fields[..] {
+                ["demo", section, demo, _] => {
+                    progress.record_demo(section, demo, time(3).ok_or_else(error)?);
+                }
+                ["doc", section, page, _] => {
+                    progress.record_doc(section, page, time(3).ok_or_else(error)?);
+                }
+                ["exercise", section, exercise, _] => {
+                    progress.record_exercise(section, exercise, time(3).ok_or_else(error)?);
+                }
+                ["quiz", section, last, _, best, attempts] => {
+                    let record = QuizRecord {
+                        last: parse_score(last).ok_or_else(error)?,
+                        best: parse_score(best).ok_or_else(error)?,
+                        attempts: attempts.parse().map_err(|_| error())?,
+                        time: time(3).ok_or_else(error)?,
+                    };
+                    progress.quizzes.insert(section.to_string(), record);
+                }
+                _ => return Err(error()),
+            }
+        }
+        Ok(progress)
+    }
+
+    pub fn to_text(&self) -> String {
+        let mut text = format!("{}\n", HEADER);
+        for ((section, demo), time) in &self.demos {
+            text.push_str(&format!("demo\t{}\t{}\t{}\n", section, demo, time));
+        }
+        for ((section, page), time) in &self.docs {
+            text.push_str(&format!("doc\t{}\t{}\t{}\n", section, page, time));
+        }
+        for ((section, exercise), time) in &self.exercises {
+            text.push_str(&format!("exercise\t{}\t{}\t{}\n", section, exercise, time));
+        }
+        for (section, record) in &self.quizzes {
+            text.push_str(&format!(
+                "quiz\t{}\t{}/{}\t{}\t{}/{}\t{}\n",
+                section, record.last.0, record.last.1, record.time, record.best.0, record.best.1, record.attempts
+            ));
+        }
+        text
+    }
+
+    pub fn load(path: &Path) -> io::Result<Progress> {
+        match fs::read_to_string(path) {
+            Ok(text) => Progress::parse(&text).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e)),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Progress::default()),
+            Err(e) => Err(e),
+        }
+    }
+
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        // 書き込み途中で中断しても壊れないよう、一時ファイルに書いてから置き換える
+        let temp = path.with_extension("tsv.tmp");
+        fs::write(&temp, self.to_text())?;
+        fs::rename(&temp, path)
+    }
+}
+
+fn parse_score(text: &str) -> Option<(usize, usize)> {
+    let (correct, total) = text.split_once('/')?;
+    Some((correct.parse().ok()?, total.parse().ok()?))
+}
+
+// 3. 保存先と自動記録
+// この環境変数が設定されていれば、そのディレクトリに保存する（テストで利用者の記録を書き換えないためにも使う）
+const DATA_DIR_VAR: &str = "HELLO_CARGO_DATA_DIR";
+
+// 記録を保存するディレクトリ（カードの予定など、他の記録もここに保存する）
+pub fn data_dir() -> Option<PathBuf> {
+    if let Some(dir) = env::var_os(DATA_DIR_VAR).filter(|dir| !dir.is_empty()) {
+        return Some(PathBuf::from(dir));
+    }
+    // XDG Base Directory仕様では、相対パスのXDG_DATA_HOMEは無視する
+    let data_home = env::var_os("XDG_DATA_HOME")
+        .map(PathBuf::from)
+        .filter(|dir| dir.is_absolute())
+        .or_else(|| env::var_os("HOME").map(|home| PathBuf::from(home).join(".local/share")))?;
+    Some(data_home.join("hello_cargo"))
+}
+
+// テストでは、プロセスごとの一時ディレクトリに保存する（最初の呼び出しで一度だけ設定する）
+#[cfg(test)]
+pub fn use_test_data_dir() {
+    static ONCE: std::sync::Once = std::sync::Once::new();
+    ONCE.call_once(|| {
+        let dir = env::temp_dir().join(format!("hello_cargo-test-{}-data", std::process::id()));
+        // SAFETY: 環境変数を読むのは標準ライブラリの関数だけで、そちらは内部のロックで書き込みと排他される
+        unsafe { env::set_var(DATA_DIR_VAR, dir) };
+    });
+}
+
+pub fn data_file() -> Option<PathBuf> {
+    Some(data_dir()?.join("progress.tsv"))
+}
+
+pub fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs())
+}
+
+// 保存されている記録（読み込めなければメッセージを表示してNoneを返す）
+pub fn load_saved() -> Option<Progress> {
+    let Some(path) = data_file() else {
+        println!("{}", tr!("progress.no_data_dir"));
+        return None;
+    };
+    match Progress::load(&path) {
+        Ok(progress) => Some(progress),
+        Err(e) => {
+            println!("{}", tr!("progress.load_error", e, path.display()));
+            None
+        }
+    }
+}
+
+// 記録を読み込んで更新し、保存する（失敗しても学習の邪魔をしないよう警告だけ表示する）
+fn update(f: impl FnOnce(&mut Progress, u64)) {
+    let Some(path) = data_file() else {
+        return;
+    };
+    let result = Progress::load(&path).and_then(|mut progress| {
+        f(&mut progress, now());
+        progress.save(&path)
+    });
+    if let Err(e) = result {
+        eprintln!("{}", tr!("progress.save_error", e, path.display()));
+    }
+}
+
+pub fn record_demos<'a>(section: &dyn Section, demos: impl IntoIterator<Item = &'a Demo>) {
+    update(|progress, time| {
+        for demo in demos {
+            progress.record_demo(section.id(), demo.name, time);
+        }
+    });
+}
+
+pub fn record_doc(section: &dyn Section, page: &DocPage) {
+    update(|progress, time| progress.record_doc(section.id(), page.name, time));
+}
+
+pub fn record_exercise(section: &dyn Section, exercise: &Exercise) {
+    update(|progress, time| progress.record_exercise(section.id(), exercise.name, time));
+}
+
+pub fn record_quiz(section: &dyn Section, correct: usize, total: usize) {
+    update(|progress, time| progress.record_quiz(section.id(), correct, total, time));
+}
+
+// 4. コマンド
+// `progress [セクション]`
+pub fn command(args: &[String]) {
+    let (Some(path), Some(progress)) = (data_file(), load_saved()) else {
+        return;
+    };
+
+    let style = Style::detect();
+    let now = now();
+    match args.first() {
+        Some(name) => match section::find(name) {
+            Some(section) => print_section(&progress, section, now, style),
+            None => println!("{}", tr!("common.unknown_section", name)),
+        },
+        None => {
+            println!("{}\n", tr!("progress.header"));
+            print!("{}", terminal::render_blocks(&[summary_table(&progress, now)], style));
+            println!("\n{}", tr!("progress.details_hint"));
+            println!("{}", tr!("progress.saved_to", path.display()));
+        }
+    }
+}
+
+// セクションごとの修了状況（デモ・ドキュメントのページ・演習・クイズを1項目ずつ数える）
+fn summary_table(progress: &Progress, now: u64) -> Block {
+    let header = [
+        tr!("progress.col.section"),
+        tr!("progress.col.demos"),
+        tr!("progress.col.docs"),
+        tr!("progress.col.exercises"),
+        tr!("progress.col.quiz"),
+        tr!("progress.col.done"),
+        tr!("progress.col.last"),
+    ];
+    let mut rows = Vec::new();
+
+    for section in section::all() {
+        let id = section.id();
+        let demos = section.demos();
+        let pages = section.doc_pages();
+        let demos_done = demos.iter().filter(|d| progress.demos.contains_key(&key(id, d.name))).count();
+        let pages_done = pages.iter().filter(|p| progress.docs.contains_key(&key(id, p.name))).count();
+        let exercises = section.exercises();
+        let exercises_done = exercises
+            .iter()
+            .filter(|e| progress.exercises.contains_key(&key(id, e.name)))
+            .count();
+        let quiz = progress.quizzes.get(id);
+
+        let done = demos_done + pages_done + exercises_done + usize::from(quiz.is_some_and(QuizRecord::passed));
+        let total = demos.len() + pages.len() + exercises.len() + 1;
+
+        let quiz_cell = match quiz {
+            Some(record) => format!("{}/{}{}", record.best.0, record.best.1, if record.passed() { " ✓" } else { "" }),
+            None => "-".to_string(),
+        };
+        rows.push(vec![
+            id.to_string(),
+            format!("{}/{}", demos_done, demos.len()),
+            format!("{}/{}", pages_done, pages.len()),
+            format!("{}/{}", exercises_done, exercises.len()),
+            quiz_cell,
+            format!("{}%", done * 100 / total),
+            last_studied(progress, id).map_or("-".to_string(), |time| relative_time(time, now)),
+        ]);
+    }
+
+    Block::Table {
+        header: header.iter().map(|h| h.to_string()).collect(),
+        aligns: vec![Align::Left, Align::Right, Align::Right, Align::Right, Align::Right, Align::Right, Align::Left],
+        rows,
+    }
+}
+
+fn print_section(progress: &Progress, section: &dyn Section, now: u64, style: Style) {
+    let id = section.id();
+    let mark = |time: Option<&u64>| match time {
+        Some(time) => format!("{} {}", style.paint(terminal::GREEN, "✓"), relative_time(*time, now)),
+        None => style.paint(terminal::DIM, "-"),
+    };
+
+    println!("{}", tr!("progress.section_header", section.title()));
+    println!("\n{}", tr!("progress.demos"));
+    for demo in section.demos() {
+        println!("  {:<30}{}", demo.name, mark(progress.demos.get(&key(id, demo.name))));
+    }
+    println!("\n{}", tr!("progress.docs"));
+    for page in section.doc_pages() {
+        println!("  {:<30}{}", page.name, mark(progress.docs.get(&key(id, page.name))));
+    }
+    println!("\n{}", tr!("progress.exercises"));
+    for exercise in section.exercises() {
+        println!("  {:<30}{}", exercise.name, mark(progress.exercises.get(&key(id, exercise.name))));
+    }
+    println!("\n{}", tr!("progress.quiz"));
+    match progress.quizzes.get(id) {
+        Some(record) => println!(
+            "  {}",
+            tr!(
+                "progress.quiz_record",
+                record.best.0,
+                record.best.1,
+                record.last.0,
+                record.last.1,
+                record.attempts,
+                relative_time(record.time, now)
+            )
+        ),
+        None => println!("  {}", tr!("progress.quiz_untried", id)),
+    }
+}
+
+fn key(section: &str, name: &str) -> (String, String) {
+    (section.to_string(), name.to_string())
+}
+
+fn last_studied(progress: &Progress, section: &str) -> Option<u64> {
+    let demos = progress.demos.iter().filter(|((s, _), _)| s == section).map(|(_, t)| *t);
+    let docs = progress.docs.iter().filter(|((s, _), _)| s == section).map(|(_, t)| *t);
+    let exercises = progress.exercises.iter().filter(|((s, _), _)| s == section).map(|(_, t)| *t);
+    let quiz = progress.quizzes.get(section).map(|record| record.time);
+    demos.chain(docs).chain(exercises).chain(quiz).max()
+}
+
+// 「3時間前」のような相対的な時刻（30日以上前は日付）
+pub fn relative_time(time: u64, now: u64) -> String {
+    let elapsed = now.saturating_sub(time);
+    match elapsed {
+        0..60 => tr!("progress.just_now").to_string(),
+        60..3600 => tr!("progress.minutes_ago", elapsed / 60),
+        3600..86400 => tr!("progress.hours_ago", elapsed / 3600),
+        86400..2_592_000 => tr!("progress.days_ago", elapsed / 86400),
+        _ => {
+            let (year, month, day) = civil_date(time / 86400);
+            format!("{}-{:02}-{:02}", year, month, day)
+        }
+    }
+}
+
+// 1970-01-01からの日数を年月日に変換する（グレゴリオ暦、UTC）
+fn civil_date(days: u64) -> (i64, u64, u64) {
+    let z = days as i64 + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097) as u64;
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe as i64 + era * 400 + i64::from(month <= 2);
+    (year, month, day)
+}
+
+// `reset [セクション] [--yes]`
+pub fn reset_command(args: &[String]) {
+    let yes = args.iter().any(|arg| arg == "--yes" || arg == "-y");
+    let section = match args.iter().find(|arg| !arg.starts_with('-')) {
+        Some(name) => match section::find(name) {
+            Some(section) => Some(section),
+            None => {
+                println!("{}", tr!("common.unknown_section", name));
+                return;
+            }
+        },
+        None => None,
+    };
+    let Some(path) = data_file() else {
+        println!("{}", tr!("progress.no_data_dir"));
+        return;
+    };
+
+    let prompt = match section {
+        Some(section) => tr!("progress.confirm_section", section.id()),
+        None => tr!("progress.confirm_all").to_string(),
+    };
+    if !yes && !confirm(&prompt) {
+        println!("{}", tr!("progress.cancelled"));
+        return;
+    }
+
+    let result = Progress::load(&path).and_then(|mut progress| {
+        progress.reset(section.map(|s| s.id()));
+        progress.save(&path)
+    });
+    match result {
+        Ok(()) => match section {
+            Some(section) => println!("{}", tr!("progress.reset_section", section.id())),
+            None => println!("{}", tr!("progress.reset_all")),
+        },
+        Err(e) => println!("{}", tr!("progress.reset_error", e, path.display())),
+    }
+}
+
+fn confirm(prompt: &str) -> bool {
+    print!("{} ", prompt);
+    io::stdout().flush().ok();
+    let mut answer = String::new();
+    io::stdin().lock().read_line(&mut answer).is_ok() && matches!(answer.trim(), "y" | "Y" | "yes")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn data_dir_can_be_overridden() {
+        use_test_data_dir();
+        let dir = data_dir().unwrap();
+        assert!(dir.ends_with(format!("hello_cargo-test-{}-data", std::process::id())), "{}", dir.display());
+        assert_eq!(data_file(), Some(dir.join("progress.tsv")));
+    }
+
+    #[test]
+    fn round_trips_through_text() {
+        let mut progress = Progress::default();
+        progress.record_demo("basics", "control_flow", 100);
+        progress.record_doc("ownership", "stack-heap", 200);
+        progress.record_exercise("ownership", "move_error", 250);
+        progress.record_quiz("basics", 5, 7, 300);
+        progress.record_quiz("basics", 4, 7, 400);
+
+        let text = progress.to_text();
+        assert!(text.starts_with(HEADER));
+        assert_eq!(Progress::parse(&text), Ok(progress));
+    }
+
+    #[test]
+    fn keeps_best_quiz_score() {
+        let mut progress = Progress::default();
+        progress.record_quiz("basics", 6, 7, 1);
+        progress.record_quiz("basics", 2, 3, 2);
+        let record = progress.quizzes["basics"];
+        assert_eq!((record.last, record.best, record.attempts), ((2, 3), (6, 7), 2));
+        assert!(record.passed());
+
+        progress.record_quiz("basics", 3, 3, 3);
+        assert_eq!(progress.quizzes["basics"].best, (3, 3));
+    }
+
+    #[test]
+    fn rejects_broken_lines() {
+        assert!(Progress::parse("demo\tbasics\tcontrol_flow\n").is_err());
+        assert!(Progress::parse("quiz\tbasics\t1/x\t0\t1/2\t1\n").is_err());
+        assert!(Progress::parse("unknown\ta\tb\t0\n").is_err());
+    }
+
+    #[test]
+    fn reset_one_section() {
+        let mut progress = Progress::default();
+        progress.record_demo("basics", "control_flow", 1);
+        progress.record_demo("ownership", "slices", 1);
+        progress.record_quiz("basics", 1, 1, 1);
+        progress.reset(Some("basics"));
+        assert_eq!(progress.demos.len(), 1);
+        assert!(progress.quizzes.is_empty());
+        progress.reset(None);
+        assert_eq!(progress, Progress::default());
+    }
+
+    #[test]
+    fn summary_counts_completed_items() {
+        let mut progress = Progress::default();
+        for demo in section::find("basics").unwrap().demos() {
+            progress.record_demo("basics", demo.name, 0);
+        }
+        progress.record_doc("basics", "readme", 0);
+        progress.record_exercise("basics", "variables", 0);
+        progress.record_quiz("basics", 1, 2, 0);
+
+        let Block::Table { rows, .. } = summary_table(&progress, 0) else { panic!() };
+        let basics = &rows[0];
+        assert_eq!(basics[1], "5/5");
+        assert_eq!(basics[2], "1/1");
+        assert_eq!(basics[3], "1/2");
+        assert_eq!(basics[4], "1/2");
+        // デモ5 + ドキュメント1 + 演習1 が修了、クイズは不合格
+        assert_eq!(basics[5], "77%");
+        assert_eq!(rows[1][5], "0%");
+    }
+
+    #[test]
+    fn formats_times() {
+        assert_eq!(relative_time(1000, 1030), "たった今");
+        assert_eq!(relative_time(0, 7200), "2時間前");
+        assert_eq!(relative_time(0, 86400 * 3), "3日前");
+        assert_eq!(civil_date(0), (1970, 1, 1));
+        assert_eq!(civil_date(20_377), (2025, 10, 16));
+        assert_eq!(relative_time(1_709_164_800, 1_800_000_000), "2024-02-29");
+    }
+}
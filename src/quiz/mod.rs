@@ -0,0 +1,467 @@
+// 理解度チェックのクイズ
+// 問題は各セクションの`quiz.md`に書かれており、Markdownとして読み込みます
+//
+// 問題の書き方:
+//
+//   ## 出力: ループから値を返す      ← `種類: タイトル`（種類は選択・出力・コンパイル）
+//   デモ: control_flow               ← 出力問題の元になったデモ（省略可能）
+//   参照: 制御フロー                 ← 解説のあとに案内するREADMEの見出し
+//
+//   問題文（コードブロックも使えます）
+//
+//   - [ ] 不正解の選択肢
+//   - [x] 正解の選択肢
+//
+//   > 解説
+
+use crate::markdown::{self, terminal, terminal::Style, Block};
+use crate::progress;
+use crate::section::{self, Section};
+use std::io::{self, BufRead, Write};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+// 1. 問題
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Kind {
+    Choice,   // 選択問題
+    Output,   // 何が出力されるか
+    Compile,  // コンパイルできるか
+}
+
+impl Kind {
+    fn parse(label: &str) -> Option<Kind> {
+        match label {
+            "選択" => Some(Kind::Choice),
+            "出力" => Some(Kind::Output),
+            "コンパイル" => Some(Kind::Compile),
+            _ => None,
+        }
+    }
+
+    fn label(self) -> &'static str {
+        match self {
+            Kind::Choice => tr!("quiz.kind.choice"),
+            Kind::Output => tr!("quiz.kind.output"),
+            Kind::Compile => tr!("quiz.kind.compile"),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Question {
+    pub kind: Kind,
+    pub title: String,
+    pub line: usize,             // quiz.md内の行番号
+    pub demo: Option<String>,    // 元になったデモの関数名
+    pub reference: String,       // 関連するREADMEの見出し
+    pub body: Vec<Block>,        // 問題文とコード
+    pub choices: Vec<String>,
+    pub answer: usize,           // 正解の選択肢の位置（0始まり）
+    pub explanation: Vec<String>,
+}
+
+// 2. 問題集の読み込み
+pub fn parse_bank(source: &str) -> Result<Vec<Question>, String> {
+    let mut questions = Vec::new();
+    let mut current: Option<(Question, Vec<bool>)> = None;
+
+    for block in markdown::parse(source) {
+        match block {
+            Block::Heading { level: 1, .. } => {}
+            Block::Heading { level: 2, text, line } => {
+                if let Some(question) = current.take() {
+                    questions.push(finish(question)?);
+                }
+                let (label, title) = text
+                    .split_once(':')
+                    .ok_or(format!("{}行目: 見出しは`種類: タイトル`の形式で書いてください", line))?;
+                let kind = Kind::parse(label.trim())
+                    .ok_or(format!("{}行目: 不明な問題の種類 {}", line, label.trim()))?;
+                let question = Question {
+                    kind,
+                    title: title.trim().to_string(),
+                    line,
+                    demo: None,
+                    reference: String::new(),
+                    body: Vec::new(),
+                    choices: Vec::new(),
+                    answer: 0,
+                    explanation: Vec::new(),
+                };
+                current = Some((question, Vec::new()));
+            }
+            block => {
+                let Some((question, correct)) = current.as_mut() else {
+                    continue;
+                };
+                match block {
+                    Block::Paragraph { lines } => {
+                        let mut text = Vec::new();
+                        for line in lines {
+                            if let Some(demo) = line.strip_prefix("デモ:") {
+                                question.demo = Some(demo.trim().to_string());
+                            } else if let Some(heading) = line.strip_prefix("参照:") {
+                                question.reference = heading.trim().to_string();
+                            } else {
+                                text.push(line);
+                            }
+                        }
+                        if !text.is_empty() {
+                            question.body.push(Block::Paragraph { lines: text });
+                        }
+                    }
+                    Block::List { items } => {
+                        for item in items {
+                            let (mark, text) = item.text.split_at(item.text.find(']').map_or(0, |i| i + 1));
+                            correct.push(mark == "[x]");
+                            question.choices.push(text.trim().to_string());
+                        }
+                    }
+                    Block::Quote { lines } => question.explanation.extend(lines),
+                    block => question.body.push(block),
+                }
+            }
+        }
+    }
+
+    if let Some(question) = current.take() {
+        questions.push(finish(question)?);
+    }
+    Ok(questions)
+}
+
+fn finish((mut question, correct): (Question, Vec<bool>)) -> Result<Question, String> {
+    let line = question.line;
+    if question.choices.len() < 2 {
+        return Err(format!("{}行目: 選択肢が2つ以上必要です", line));
+    }
+    let answers: Vec<usize> = (0..correct.len()).filter(|&i| correct[i]).collect();
+    let [answer] = answers[..] else {
+        return Err(format!("{}行目: 正解（`- [x]`）はちょうど1つにしてください", line));
+    };
+    if question.reference.is_empty() {
+        return Err(format!("{}行目: `参照:`で関連する見出しを指定してください", line));
+    }
+    question.answer = answer;
+    Ok(question)
+}
+
+// 3. 出題順のランダム化（xorshift64*）
+pub struct Rng(u64);
+
+impl Rng {
+    pub fn new(seed: u64) -> Rng {
+        // 0だと常に0を返してしまうため、適当な値と混ぜる
+        Rng((seed ^ 0x9E37_79B9_7F4A_7C15) | 1)
+    }
+
+    pub fn from_time() -> Rng {
+        let nanos = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |d| d.as_nanos() as u64);
+        Rng::new(nanos)
+    }
+
+    pub fn next(&mut self) -> u64 {
+        self.0 ^= self.0 >> 12;
+        self.0 ^= self.0 << 25;
+        self.0 ^= self.0 >> 27;
+        self.0.wrapping_mul(0x2545_F491_4F6C_DD1D)
+    }
+
+    // Fisher-Yatesのシャッフル
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            let j = (self.next() % (i as u64 + 1)) as usize;
+            items.swap(i, j);
+        }
+    }
+}
+
+// 問題と選択肢の順番を入れ替える（「できる／できない」の順番はそのまま）
+fn shuffle_questions(questions: &mut [Question], rng: &mut Rng) {
+    rng.shuffle(questions);
+    for question in questions.iter_mut().filter(|q| q.kind != Kind::Compile) {
+        let answer = question.choices[question.answer].clone();
+        rng.shuffle(&mut question.choices);
+        question.answer = question.choices.iter().position(|c| *c == answer).unwrap();
+    }
+}
+
+// 4. 出題
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Score {
+    pub correct: usize,
+    pub total: usize,
+}
+
+// 入力が尽きるか`q`が入力されたら、それまでの結果で終了する
+fn play(section: &dyn Section, questions: &[Question], input: &mut impl BufRead, style: Style) -> Score {
+    let mut score = Score { correct: 0, total: 0 };
+
+    for (i, question) in questions.iter().enumerate() {
+        println!(
+            "\n{} {}",
+            style.paint(terminal::BOLD, &tr!("quiz.question_number", i + 1, questions.len())),
+            style.paint(terminal::CYAN, &tr!("quiz.question_title", question.kind.label(), question.title)),
+        );
+        print!("\n{}", terminal::render_blocks(&question.body, style));
+        println!();
+        for (n, choice) in question.choices.iter().enumerate() {
+            println!("  {}. {}", n + 1, terminal::inline(choice, style));
+        }
+
+        let Some(choice) = read_choice(input, question.choices.len()) else {
+            println!("\n{}", tr!("quiz.aborted"));
+            break;
+        };
+
+        score.total += 1;
+        if choice == question.answer {
+            score.correct += 1;
+            println!("{}", style.paint(terminal::GREEN, tr!("quiz.correct")));
+        } else {
+            let answer = terminal::inline(&question.choices[question.answer], style);
+            println!("{} {}", style.paint(terminal::RED, tr!("quiz.wrong")), tr!("quiz.answer_was", question.answer + 1, answer));
+        }
+        for line in &question.explanation {
+            println!("  {}", terminal::inline(line, style));
+        }
+        println!(
+            "  {} cargo run -- doc {}",
+            style.paint(terminal::DIM, tr!("quiz.see_doc")),
+            shell_quote(&format!("{}#{}", section.id(), question.reference))
+        );
+        if let Some(demo) = &question.demo {
+            println!("  {} cargo run -- {} {}", style.paint(terminal::DIM, tr!("quiz.see_demo")), section.id(), demo);
+        }
+    }
+
+    score
+}
+
+// シェルで特別な意味を持つ文字を含む場合は引用符で囲む（例: `Option<T>型`）
+fn shell_quote(arg: &str) -> String {
+    if arg.contains(|c: char| " <>()|&;*?$'\"`".contains(c)) {
+        format!("'{}'", arg.replace('\'', "'\\''"))
+    } else {
+        arg.to_string()
+    }
+}
+
+fn read_choice(input: &mut impl BufRead, count: usize) -> Option<usize> {
+    loop {
+        print!("\n{} ", tr!("quiz.prompt"));
+        io::stdout().flush().ok();
+
+        let mut line = String::new();
+        if input.read_line(&mut line).ok()? == 0 {
+            return None;
+        }
+        let line = line.trim();
+        if line == "q" {
+            return None;
+        }
+        match line.parse::<usize>() {
+            Ok(n) if (1..=count).contains(&n) => return Some(n - 1),
+            _ => println!("{}", tr!("quiz.invalid_choice", count)),
+        }
+    }
+}
+
+// 5. コマンド
+// `quiz <セクション> [--count N] [--seed N]`
+pub fn command(args: &[String]) {
+    let mut count: Option<usize> = None;
+    let mut seed: Option<u64> = None;
+    let mut name: Option<&str> = None;
+
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--count" | "--seed" => {
+                let Some(value) = iter.next().and_then(|v| v.parse::<u64>().ok()) else {
+                    println!("{}", tr!("quiz.needs_number", arg));
+                    return;
+                };
+                if arg == "--count" {
+                    count = Some(value as usize);
+                } else {
+                    seed = Some(value);
+                }
+            }
+            other => name = Some(other),
+        }
+    }
+
+    let Some(name) = name else {
+        print_help();
+        return;
+    };
+    let Some(section) = section::find(name) else {
+        println!("{}", tr!("common.unknown_section", name));
+        print_help();
+        return;
+    };
+
+    let bank = section.quiz();
+    let mut questions = match parse_bank(bank.content) {
+        Ok(questions) => questions,
+        Err(e) => {
+            println!("{}", tr!("quiz.bank_error", e, bank.path));
+            return;
+        }
+    };
+
+    let mut rng = seed.map_or_else(Rng::from_time, Rng::new);
+    shuffle_questions(&mut questions, &mut rng);
+    questions.truncate(count.unwrap_or(questions.len()));
+
+    let style = Style::detect();
+    println!("{}", tr!("quiz.header", section.title(), questions.len()));
+    let score = play(section, &questions, &mut io::stdin().lock(), style);
+    if score.total > 0 {
+        progress::record_quiz(section, score.correct, score.total);
+    }
+
+    if let Some(percent) = (score.correct * 100).checked_div(score.total) {
+        println!(
+            "\n{}",
+            style.paint(terminal::BOLD, &tr!("quiz.result", score.total, score.correct, percent))
+        );
+    }
+}
+
+fn print_help() {
+    println!("{}", tr!("quiz.usage"));
+    println!("\n{}", tr!("quiz.available"));
+    for section in section::all() {
+        let count = parse_bank(section.quiz().content).map_or(0, |questions| questions.len());
+        println!("  {:<13}- {}", section.id(), tr!("quiz.entry", section.title(), count));
+    }
+    println!("\n{}", tr!("help.options"));
+    println!("  {}", tr!("quiz.opt.count"));
+    println!("  {}", tr!("quiz.opt.seed"));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+    use std::io::Cursor;
+
+    const PLAIN: Style = Style { color: false };
+
+    const SAMPLE: &str = "# クイズ\n\n## 出力: ループ\nデモ: control_flow\n参照: 制御フロー\n\n何が表示されますか？\n\n```rust\nprintln!(\"{}\", 1);\n```\n\n- [ ] 0\n- [x] 1\n\n> 解説です\n\n## コンパイル: 再代入\n参照: 変数と可変性\n\n- [ ] できる\n- [x] できない\n";
+
+    #[test]
+    fn parses_questions() {
+        let questions = parse_bank(SAMPLE).unwrap();
+        assert_eq!(questions.len(), 2);
+        let first = &questions[0];
+        assert_eq!(first.kind, Kind::Output);
+        assert_eq!(first.demo.as_deref(), Some("control_flow"));
+        assert_eq!(first.reference, "制御フロー");
+        assert_eq!(first.choices, vec!["0", "1"]);
+        assert_eq!(first.answer, 1);
+        assert_eq!(first.explanation, vec!["解説です"]);
+        assert_eq!(first.body.len(), 2);
+        assert_eq!(questions[1].kind, Kind::Compile);
+    }
+
+    #[test]
+    fn quotes_shell_arguments() {
+        assert_eq!(shell_quote("basics#制御フロー"), "basics#制御フロー");
+        assert_eq!(shell_quote("structs#Option<T>型"), "'structs#Option<T>型'");
+        assert_eq!(shell_quote("ownership#Copy trait"), "'ownership#Copy trait'");
+    }
+
+    #[test]
+    fn rejects_invalid_questions() {
+        assert!(parse_bank("## 選択: 正解なし\n参照: a\n\n- [ ] a\n- [ ] b\n").is_err());
+        assert!(parse_bank("## 謎: 種類\n参照: a\n\n- [x] a\n- [ ] b\n").is_err());
+        assert!(parse_bank("## 選択: 参照なし\n\n- [x] a\n- [ ] b\n").is_err());
+    }
+
+    #[test]
+    fn shuffle_keeps_the_answer() {
+        let mut questions = parse_bank(SAMPLE).unwrap();
+        let mut rng = Rng::new(42);
+        for _ in 0..10 {
+            shuffle_questions(&mut questions, &mut rng);
+            for question in &questions {
+                let expected = if question.kind == Kind::Output { "1" } else { "できない" };
+                assert_eq!(question.choices[question.answer], expected);
+            }
+        }
+    }
+
+    #[test]
+    fn scores_answers_and_stops_on_quit() {
+        let section = section::find("basics").unwrap();
+        let questions = parse_bank(SAMPLE).unwrap();
+
+        // 1問目は範囲外の入力のあとに正解、2問目は不正解
+        let mut input = Cursor::new("9\n2\n1\n");
+        assert_eq!(play(section, &questions, &mut input, PLAIN), Score { correct: 1, total: 2 });
+
+        let mut input = Cursor::new("2\nq\n");
+        assert_eq!(play(section, &questions, &mut input, PLAIN), Score { correct: 1, total: 1 });
+    }
+
+    // すべてのセクションの問題集が正しく書かれていること
+    #[test]
+    fn every_bank_is_valid() {
+        for section in section::all() {
+            let bank = section.quiz();
+            let questions = parse_bank(bank.content).unwrap_or_else(|e| panic!("{}: {}", bank.path, e));
+            assert!(questions.len() >= 5, "{}: 問題が少なすぎます", bank.path);
+
+            let kinds: HashSet<&str> = questions.iter().map(|q| q.kind.label()).collect();
+            assert_eq!(kinds.len(), 3, "{}: 3種類の問題を含めてください", bank.path);
+
+            let pages: Vec<Vec<Block>> = section
+                .doc_pages()
+                .iter()
+                .map(|page| markdown::parse(page.content))
+                .collect();
+            // デモは`writeln!(out, ...)?;`で出力するので、問題文と同じ`println!`の形に直して比べる
+            let source_lines: Vec<String> = section
+                .source()
+                .content
+                .lines()
+                .map(|line| line.trim().replace("writeln!(out, ", "println!(").replace(")?;", ");"))
+                .collect();
+
+            for question in &questions {
+                let at = format!("{}:{}", bank.path, question.line);
+                assert!(
+                    pages.iter().any(|blocks| markdown::find_heading(blocks, &question.reference).is_some()),
+                    "{}: 見出しが見つかりません {}",
+                    at,
+                    question.reference
+                );
+
+                let Some(demo) = &question.demo else { continue };
+                assert!(
+                    section.demos().iter().any(|d| d.name == demo),
+                    "{}: 不明なデモ {}",
+                    at,
+                    demo
+                );
+                // 出力問題のコードは、デモのソースコードから抜き出したものであること
+                for block in &question.body {
+                    let Block::Code { code, .. } = block else { continue };
+                    for line in code.lines().map(str::trim).filter(|l| !l.is_empty()) {
+                        assert!(
+                            source_lines.iter().any(|source| source.contains(line)),
+                            "{}: デモのソースにない行 {}",
+                            at,
+                            line
+                        );
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,525 @@
+// ドキュメントとデモのソースコードの全文検索
+// 日本語は単語の区切りがないため、文字の2-gramで索引を作り部分一致で検索します
+
+use crate::markdown::{self, terminal::{self, Style}};
+use crate::section::{self, Section};
+use std::collections::{HashMap, HashSet};
+
+const DEFAULT_LIMIT: usize = 10;
+
+// `search <検索語...> [--code|--docs] [--limit N]`
+pub fn command(args: &[String]) {
+    let mut filter = Filter::All;
+    let mut limit = DEFAULT_LIMIT;
+    let mut words: Vec<&str> = Vec::new();
+
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--code" => filter = Filter::Code,
+            "--docs" => filter = Filter::Docs,
+            "--limit" => match iter.next().and_then(|n| n.parse().ok()) {
+                Some(n) => limit = n,
+                None => {
+                    println!("{}", tr!("search.needs_limit"));
+                    return;
+                }
+            },
+            word => words.push(word),
+        }
+    }
+
+    if words.is_empty() {
+        print_help();
+        return;
+    }
+
+    let query = words.join(" ");
+    let index = Index::build(section::all());
+    let hits = index.search(&query, filter);
+    let style = Style::detect();
+
+    if hits.is_empty() {
+        println!("{}", tr!("search.no_hits", query));
+        return;
+    }
+
+    println!("{}", tr!("search.hits", query, hits.len()));
+    for (i, hit) in hits.iter().take(limit).enumerate() {
+        let chunk = hit.chunk;
+        let kind = match chunk.kind {
+            Kind::Doc => tr!("search.kind.doc"),
+            Kind::Code => tr!("search.kind.code"),
+        };
+        let location = format!("{}:{}", chunk.path, hit.line);
+        println!(
+            "\n{:>2}. {} {} {}",
+            i + 1,
+            style.paint(terminal::BOLD, &format!("[{}] {}", chunk.section.id(), chunk.section.title())),
+            style.paint(terminal::CYAN, kind),
+            style.paint(terminal::DIM, &location),
+        );
+        if !chunk.heading.is_empty() {
+            println!("    {}", style.paint(terminal::YELLOW, &chunk.heading));
+        }
+        println!("    {}", snippet(hit.snippet, &query, style));
+    }
+    if hits.len() > limit {
+        println!("\n{}", tr!("search.more", hits.len() - limit));
+    }
+}
+
+fn print_help() {
+    println!("{}", tr!("search.usage"));
+    println!("\n{}", tr!("search.description"));
+    println!("{}", tr!("search.description_terms"));
+    println!("\n{}", tr!("help.options"));
+    println!("  {}", tr!("search.opt.docs"));
+    println!("  {}", tr!("search.opt.code"));
+    println!("  {}", tr!("search.opt.limit", DEFAULT_LIMIT));
+    println!("\n{}", tr!("help.examples"));
+    println!("  cargo run -- search 'entry().or_insert'");
+    println!("  {}", tr!("search.ex.terms"));
+}
+
+// 1. 検索対象の単位
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Kind {
+    Doc,
+    Code,
+}
+
+// 検索対象の絞り込み（`--docs`/`--code`）
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Filter {
+    All,
+    Docs,
+    Code,
+}
+
+impl Filter {
+    fn accepts(self, kind: Kind) -> bool {
+        matches!(
+            (self, kind),
+            (Filter::All, _) | (Filter::Docs, Kind::Doc) | (Filter::Code, Kind::Code)
+        )
+    }
+}
+
+// 見出し（ソースコードでは関数や型）ごとに区切った本文
+pub struct Chunk {
+    pub section: &'static dyn Section,
+    pub kind: Kind,
+    pub path: &'static str,
+    pub heading: String,                    // 例: `ハッシュマップ › エントリーAPI`、`fn hash_maps`
+    pub lines: Vec<(usize, &'static str)>,  // (行番号, 行の内容)
+    text: Vec<char>,                        // 正規化した本文（見出しを含む）
+}
+
+impl Chunk {
+    fn new(section: &'static dyn Section, kind: Kind, path: &'static str, heading: String) -> Chunk {
+        Chunk { section, kind, path, heading, lines: Vec::new(), text: Vec::new() }
+    }
+}
+
+// 2. 索引
+pub struct Index {
+    chunks: Vec<Chunk>,
+    grams: HashMap<(char, char), Vec<usize>>,  // 2-gram → それを含むチャンクの番号
+}
+
+pub struct Hit<'a> {
+    pub chunk: &'a Chunk,
+    pub score: f64,
+    pub line: usize,
+    pub snippet: &'static str,
+}
+
+impl Index {
+    pub fn build(sections: &[&'static dyn Section]) -> Index {
+        let mut chunks = Vec::new();
+        for &section in sections {
+            for page in section.doc_pages() {
+                split_doc(&mut chunks, section, page.localized_path(), page.localized_content());
+            }
+            let source = section.source();
+            split_code(&mut chunks, section, source.path, source.content);
+        }
+
+        chunks.retain(|chunk| !chunk.lines.is_empty());
+        let mut grams: HashMap<(char, char), Vec<usize>> = HashMap::new();
+        for (i, chunk) in chunks.iter_mut().enumerate() {
+            let mut text = normalize(&chunk.heading);
+            for (_, line) in &chunk.lines {
+                text.push('\n');
+                text.extend(normalize(line));
+            }
+            for gram in bigrams(&text) {
+                let postings = grams.entry(gram).or_default();
+                if postings.last() != Some(&i) {
+                    postings.push(i);
+                }
+            }
+            chunk.text = text;
+        }
+
+        Index { chunks, grams }
+    }
+
+    // スペース区切りの各語をすべて含むチャンクを、スコアの高い順に返す
+    pub fn search(&self, query: &str, filter: Filter) -> Vec<Hit<'_>> {
+        let terms: Vec<Vec<char>> = query.split_whitespace().map(normalize).collect();
+        if terms.is_empty() {
+            return Vec::new();
+        }
+
+        let mut scores: Vec<Option<f64>> = self
+            .chunks
+            .iter()
+            .map(|chunk| filter.accepts(chunk.kind).then_some(0.0))
+            .collect();
+
+        for term in &terms {
+            let coverage = self.coverage(term);
+            for (i, score) in scores.iter_mut().enumerate() {
+                let Some(total) = score else { continue };
+                match term_score(term, &self.chunks[i], coverage.get(&i).copied()) {
+                    Some(s) => *total += s,
+                    None => *score = None,
+                }
+            }
+        }
+
+        let mut hits: Vec<Hit> = scores
+            .into_iter()
+            .enumerate()
+            .filter_map(|(i, score)| {
+                let chunk = &self.chunks[i];
+                let (line, snippet) = best_line(chunk, &terms);
+                score.map(|score| Hit { chunk, score, line, snippet })
+            })
+            .collect();
+
+        hits.sort_by(|a, b| b.score.partial_cmp(&a.score).unwrap().then(a.line.cmp(&b.line)));
+        hits
+    }
+
+    // 語の2-gramのうち、各チャンクに含まれている割合
+    fn coverage(&self, term: &[char]) -> HashMap<usize, f64> {
+        let grams: HashSet<(char, char)> = bigrams(term).collect();
+        let mut counts: HashMap<usize, usize> = HashMap::new();
+        for gram in &grams {
+            for &chunk in self.grams.get(gram).map(Vec::as_slice).unwrap_or(&[]) {
+                *counts.entry(chunk).or_default() += 1;
+            }
+        }
+        counts
+            .into_iter()
+            .map(|(chunk, count)| (chunk, count as f64 / grams.len() as f64))
+            .collect()
+    }
+}
+
+// 語1つ分のスコア（一致しなければNone）
+// 完全一致を重視し、2-gramの一致率が高ければ表記ゆれ（「所有権の移動」と「所有権が移動」など）も拾う
+fn term_score(term: &[char], chunk: &Chunk, coverage: Option<f64>) -> Option<f64> {
+    let exact = count_occurrences(&chunk.text, term);
+    let coverage = if term.len() < 2 {
+        if exact > 0 { 1.0 } else { 0.0 }
+    } else {
+        coverage.unwrap_or(0.0)
+    };
+
+    // 英数字だけの語（コードなど）は部分一致のみ
+    // `entry().or_insert`のようなコードは識別子ごとに分けて、すべて含まれていれば一致とする
+    let fuzzy = if term.iter().all(char::is_ascii) {
+        let parts = identifier_parts(term);
+        parts.len() > 1 && parts.iter().all(|part| count_occurrences(&chunk.text, part) > 0)
+    } else {
+        term.len() >= 3 && coverage >= 0.75
+    };
+    if exact == 0 && !fuzzy {
+        return None;
+    }
+
+    let in_heading = count_occurrences(&normalize(&chunk.heading), term) > 0;
+    Some(coverage + exact.min(5) as f64 * 0.5 + if in_heading { 2.0 } else { 0.0 })
+}
+
+// 英数字と`_`の並びごとに分ける（1文字のものは除く）
+fn identifier_parts(term: &[char]) -> Vec<Vec<char>> {
+    term.split(|c| !(c.is_alphanumeric() || *c == '_'))
+        .filter(|part| part.len() >= 2)
+        .map(|part| part.to_vec())
+        .collect()
+}
+
+// 3. 本文の分割
+fn split_doc(chunks: &mut Vec<Chunk>, section: &'static dyn Section, path: &'static str, content: &'static str) {
+    let mut headings: Vec<(usize, String)> = Vec::new();
+    let mut current = Chunk::new(section, Kind::Doc, path, String::new());
+    let mut in_code = false;
+
+    for (i, line) in content.lines().enumerate() {
+        if line.trim_start().starts_with("```") {
+            in_code = !in_code;
+        }
+        if !in_code && let Some((level, text)) = markdown::heading(line) {
+            headings.retain(|(l, _)| *l < level);
+            headings.push((level, markdown::inline_text(text)));
+            let path_text: Vec<&str> = headings.iter().map(|(_, text)| text.as_str()).collect();
+            chunks.push(std::mem::replace(
+                &mut current,
+                Chunk::new(section, Kind::Doc, path, path_text.join(" › ")),
+            ));
+        }
+        if !line.trim().is_empty() {
+            current.lines.push((i + 1, line));
+        }
+    }
+    chunks.push(current);
+}
+
+fn split_code(chunks: &mut Vec<Chunk>, section: &'static dyn Section, path: &'static str, content: &'static str) {
+    let mut current = Chunk::new(section, Kind::Code, path, String::new());
+
+    for (i, line) in content.lines().enumerate() {
+        if let Some(item) = top_level_item(line) {
+            // 直前のコメントは次の項目の説明として移す
+            let split = current
+                .lines
+                .iter()
+                .rposition(|(_, l)| !l.starts_with("//"))
+                .map_or(0, |pos| pos + 1);
+            let comments = current.lines.split_off(split);
+            let mut next = Chunk::new(section, Kind::Code, path, item);
+            next.lines = comments;
+            chunks.push(std::mem::replace(&mut current, next));
+        }
+        if !line.trim().is_empty() {
+            current.lines.push((i + 1, line));
+        }
+    }
+    chunks.push(current);
+}
+
+// `pub fn hash_maps() {`のようなトップレベルの定義なら`fn hash_maps`を返す
+fn top_level_item(line: &str) -> Option<String> {
+    let rest = line.strip_prefix("pub ").unwrap_or(line);
+    ["fn ", "struct ", "enum ", "impl ", "impl<", "trait ", "static ", "const "]
+        .into_iter()
+        .find(|keyword| rest.starts_with(keyword))?;
+    let end = rest.find(['(', '{', ':', '=', ';']).unwrap_or(rest.len());
+    Some(rest[..end].trim().to_string())
+}
+
+// 4. 文字列の正規化
+// 全角英数字を半角に、英字を小文字にそろえる（1文字ずつ変換するので位置はずれない）
+fn normalize(text: &str) -> Vec<char> {
+    text.chars().map(normalize_char).collect()
+}
+
+fn normalize_char(c: char) -> char {
+    let c = match c as u32 {
+        0xFF01..=0xFF5E => char::from_u32(c as u32 - 0xFEE0).unwrap_or(c),
+        0x3000 => ' ',
+        _ => c,
+    };
+    if c.is_uppercase() {
+        c.to_lowercase().next().unwrap_or(c)
+    } else {
+        c
+    }
+}
+
+fn bigrams(text: &[char]) -> impl Iterator<Item = (char, char)> + '_ {
+    text.windows(2)
+        .filter(|pair| !pair[0].is_whitespace() && !pair[1].is_whitespace())
+        .map(|pair| (pair[0], pair[1]))
+}
+
+fn count_occurrences(text: &[char], term: &[char]) -> usize {
+    if term.is_empty() || term.len() > text.len() {
+        return 0;
+    }
+    text.windows(term.len()).filter(|window| *window == term).count()
+}
+
+// 5. 抜粋の選択と強調
+fn best_line(chunk: &Chunk, terms: &[Vec<char>]) -> (usize, &'static str) {
+    let mut best = (0.0, chunk.lines[0]);
+    for &(number, line) in &chunk.lines {
+        let text = normalize(line);
+        let marks = match_marks(line, terms);
+        let mut score = marks.iter().filter(|&&m| m).count() as f64;
+        for term in terms {
+            let parts = identifier_parts(term);
+            if count_occurrences(&text, term) > 0 {
+                score += 10.0;
+            } else if !parts.is_empty() && parts.iter().all(|part| count_occurrences(&text, part) > 0) {
+                score += 5.0;
+            }
+        }
+        if score > best.0 {
+            best = (score, (number, line));
+        }
+    }
+    best.1
+}
+
+// 各文字が検索語（または検索語の2-gram）に一致するかどうか
+fn match_marks(line: &str, terms: &[Vec<char>]) -> Vec<bool> {
+    let text = normalize(line);
+    let mut marks = vec![false; text.len()];
+
+    for term in terms {
+        // 英数字の語は語全体と識別子ごと、日本語を含む語は2-gramごとに印を付ける
+        if term.iter().all(char::is_ascii) {
+            mark(&text, term, &mut marks);
+            for part in identifier_parts(term) {
+                mark(&text, &part, &mut marks);
+            }
+        } else if term.len() >= 2 {
+            for window in term.windows(2) {
+                mark(&text, window, &mut marks);
+            }
+        } else {
+            mark(&text, term, &mut marks);
+        }
+    }
+    marks
+}
+
+fn mark(text: &[char], pattern: &[char], marks: &mut [bool]) {
+    if pattern.is_empty() || pattern.len() > text.len() {
+        return;
+    }
+    for start in 0..=text.len() - pattern.len() {
+        if text[start..start + pattern.len()] == *pattern {
+            marks[start..start + pattern.len()].iter_mut().for_each(|m| *m = true);
+        }
+    }
+}
+
+const SNIPPET_WIDTH: usize = 100;
+
+// 一致した部分を強調した抜粋（長い行は一致箇所の周辺だけを切り出す）
+pub fn snippet(line: &str, query: &str, style: Style) -> String {
+    let terms: Vec<Vec<char>> = query.split_whitespace().map(normalize).collect();
+    let chars: Vec<char> = line.trim().chars().collect();
+    let marks = match_marks(&chars.iter().collect::<String>(), &terms);
+
+    let first = marks.iter().position(|&m| m).unwrap_or(0);
+    let mut start = first.saturating_sub(20);
+    let mut width: usize = 0;
+    let mut end = start;
+    while end < chars.len() && width + markdown::char_width(chars[end]) <= SNIPPET_WIDTH {
+        width += markdown::char_width(chars[end]);
+        end += 1;
+    }
+    if end == chars.len() {
+        // 末尾まで収まるなら先頭側をできるだけ戻す
+        while start > 0 && width + markdown::char_width(chars[start - 1]) <= SNIPPET_WIDTH {
+            start -= 1;
+            width += markdown::char_width(chars[start]);
+        }
+    }
+
+    let mut out = String::new();
+    if start > 0 {
+        out.push('…');
+    }
+    let mut i = start;
+    while i < end {
+        let marked = marks[i];
+        let run_end = (i..end).find(|&j| marks[j] != marked).unwrap_or(end);
+        let text: String = chars[i..run_end].iter().collect();
+        if marked {
+            if style.color {
+                out.push_str(&style.paint(&format!("{}{}", terminal::BOLD, terminal::RED), &text));
+            } else {
+                out.push_str(&format!("【{}】", text));
+            }
+        } else {
+            out.push_str(&text);
+        }
+        i = run_end;
+    }
+    if end < chars.len() {
+        out.push('…');
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::section;
+
+    fn index() -> Index {
+        Index::build(section::all())
+    }
+
+    #[test]
+    fn finds_code_and_docs_for_exact_query() {
+        let index = index();
+        let hits = index.search("entry().or_insert", Filter::All);
+        assert!(hits.iter().any(|hit| hit.chunk.kind == Kind::Doc && hit.chunk.section.id() == "collections"));
+        assert!(hits.iter().any(|hit| hit.chunk.kind == Kind::Code && hit.chunk.heading == "fn hash_maps"));
+        assert!(hits.iter().all(|hit| hit.snippet.contains("or_insert")));
+    }
+
+    #[test]
+    fn filters_by_kind() {
+        let index = index();
+        assert!(index.search("or_insert", Filter::Docs).iter().all(|hit| hit.chunk.kind == Kind::Doc));
+        assert!(index.search("or_insert", Filter::Code).iter().all(|hit| hit.chunk.kind == Kind::Code));
+    }
+
+    #[test]
+    fn japanese_text_matches_by_ngrams() {
+        let index = index();
+        // 語順が違っても2-gramの一致で「所有権とハッシュマップ」が見つかる
+        let hits = index.search("ハッシュマップと所有権", Filter::Docs);
+        assert!(!hits.is_empty());
+        assert_eq!(hits[0].chunk.section.id(), "collections");
+        assert!(hits[0].chunk.heading.ends_with("所有権とハッシュマップ"));
+    }
+
+    #[test]
+    fn headings_are_tracked() {
+        let index = index();
+        let hits = index.search("シャドーイング", Filter::Docs);
+        assert!(hits.iter().any(|hit| hit.chunk.heading.ends_with("シャドーイング（Shadowing）")));
+    }
+
+    #[test]
+    fn unmatched_terms_exclude_chunks() {
+        let index = index();
+        assert!(index.search("or_insert 存在しない語句ですよ", Filter::All).is_empty());
+        assert!(index.search("   ", Filter::All).is_empty());
+    }
+
+    #[test]
+    fn full_width_query_is_normalized() {
+        assert_eq!(normalize("ＨａｓｈＭａｐ"), normalize("hashmap"));
+    }
+
+    #[test]
+    fn snippet_marks_matches() {
+        let style = Style { color: false };
+        assert_eq!(snippet("  let v = Vec::new();", "vec", style), "let v = 【Vec】::new();");
+        let long = format!("{}所有権{}", "あ".repeat(60), "い".repeat(60));
+        let result = snippet(&long, "所有権", style);
+        assert!(result.starts_with('…') && result.ends_with('…'));
+        assert!(result.contains("【所有権】"));
+    }
+
+    #[test]
+    fn top_level_items() {
+        assert_eq!(top_level_item("pub fn hash_maps() {"), Some("fn hash_maps".to_string()));
+        assert_eq!(top_level_item("impl<T> Point<T> {"), Some("impl<T> Point<T>".to_string()));
+        assert_eq!(top_level_item("    fn inner() {"), None);
+        assert_eq!(top_level_item("implicit"), None);
+    }
+}
@@ -0,0 +1,354 @@
+// セクションの登録
+// 各学習モジュールをCLIから使えるようにするための共通の仕組みです
+
+use crate::basics;
+use crate::collections;
+use crate::error_handling;
+use crate::generics_traits;
+use crate::i18n;
+use crate::layout::TypeLayout;
+use crate::ownership;
+use crate::structs_enums;
+use crate::testing;
+use std::borrow::Cow;
+use std::fs;
+use std::io::{self, Write};
+use std::path::Path;
+
+// 1. ドキュメントのページ
+// 本文はビルド時にバイナリへ埋め込まれるため、どのディレクトリからでも表示できる
+#[derive(Debug, Clone, Copy)]
+pub struct DocPage {
+    pub name: &'static str,            // `doc`コマンドで指定するページ名（例: `stack-heap`）
+    pub path: &'static str,            // `src`ディレクトリからの相対パス
+    pub description: &'static str,     // ドキュメント一覧に表示する説明
+    pub content: &'static str,         // 埋め込まれた本文
+    pub path_en: &'static str,         // 英語版の相対パス（例: `basics/README.en.md`）
+    pub description_en: &'static str,  // 英語版の説明
+    pub content_en: &'static str,      // 埋め込まれた英語版の本文
+}
+
+impl DocPage {
+    // 表示言語に合わせたパス・説明・本文
+    pub fn localized_path(&self) -> &'static str {
+        i18n::pick(self.path, self.path_en)
+    }
+
+    pub fn localized_description(&self) -> &'static str {
+        i18n::pick(self.description, self.description_en)
+    }
+
+    pub fn localized_content(&self) -> &'static str {
+        i18n::pick(self.content, self.content_en)
+    }
+
+    // `docs_dir`が指定されていればそこから読み込み、なければ埋め込まれた本文を返す
+    // （ドキュメントを編集しながら確認するための仕組み）
+    pub fn load(&self, docs_dir: Option<&Path>) -> io::Result<Cow<'static, str>> {
+        match docs_dir {
+            Some(dir) => fs::read_to_string(dir.join(self.localized_path())).map(Cow::Owned),
+            None => Ok(Cow::Borrowed(self.localized_content())),
+        }
+    }
+}
+
+// 埋め込まれたファイル（デモのソースコードやクイズの問題集）
+#[derive(Debug, Clone, Copy)]
+pub struct SourceFile {
+    pub path: &'static str,     // `src`ディレクトリからの相対パス
+    pub content: &'static str,  // 埋め込まれたソースコード
+}
+
+// 演習問題（学習者が修正して`exercise check`で確認するファイル）
+#[derive(Debug, Clone, Copy)]
+pub struct Exercise {
+    pub name: &'static str,     // `exercise check`で指定する名前（全セクションで一意）
+    pub path: &'static str,     // `src`ディレクトリからの相対パス
+    pub hint: &'static str,     // `exercise hint`で表示するヒント
+    pub content: &'static str,  // 埋め込まれた修正前のファイル
+}
+
+// 2. セクション内の個別のデモ
+#[derive(Debug)]
+pub struct Demo {
+    pub name: &'static str,      // 関数名（例: `control_flow`）
+    pub title: &'static str,     // 実行時の見出し（例: `制御フロー`）
+    pub title_en: &'static str,  // 英語の見出し（例: `Control Flow`）
+    pub run: fn(&mut dyn Write) -> io::Result<()>,  // 出力先を受け取って実行する
+}
+
+impl Demo {
+    // 表示言語に合わせた見出し
+    pub fn heading(&self) -> &'static str {
+        i18n::pick(self.title, self.title_en)
+    }
+}
+
+// デモを見出し付きで順番に実行する
+// （見出しは日本語で書き込み、英語で表示するときは`i18n::demo`が出力ごと置き換える）
+pub fn run_demos<'a>(demos: impl IntoIterator<Item = &'a Demo>, out: &mut dyn Write) -> io::Result<()> {
+    for demo in demos {
+        writeln!(out, "\n=== {} ===", demo.title)?;
+        (demo.run)(out)?;
+    }
+    Ok(())
+}
+
+// 3. セクションのトレイト
+/// CLIから実行・参照できる学習セクション
+///
+/// 新しいモジュールを追加するときは、このトレイトを実装して
+/// `SECTIONS`に登録するだけで、実行・ヘルプ・`doc`・`all`のすべてに反映されます。
+pub trait Section: Sync {
+    /// コマンドラインで指定する名前（例: `basics`）
+    fn id(&self) -> &'static str;
+
+    /// `id`の代わりに使える別名
+    fn aliases(&self) -> &'static [&'static str] {
+        &[]
+    }
+
+    /// 日本語のタイトル（例: `基本的な文法`）
+    fn title_ja(&self) -> &'static str;
+
+    /// 英語のタイトル（例: `Basics`）
+    fn title_en(&self) -> &'static str;
+
+    /// ヘルプに表示する主なトピック
+    fn topics(&self) -> &'static str;
+
+    /// 英語の主なトピック
+    fn topics_en(&self) -> &'static str;
+
+    /// セクションのコードを実行し、出力を`out`に書き込む
+    fn run(&self, out: &mut dyn Write) -> io::Result<()>;
+
+    /// セクションに含まれるデモ（実行順）
+    fn demos(&self) -> &'static [Demo];
+
+    /// デモが定義されているソースコード
+    fn source(&self) -> SourceFile;
+
+    /// セクションのドキュメント（先頭のページが`doc`コマンドの既定）
+    fn doc_pages(&self) -> &'static [DocPage];
+
+    /// クイズの問題集（`quiz.md`）
+    fn quiz(&self) -> SourceFile;
+
+    /// 復習用のカード（`cards.md`）
+    fn cards(&self) -> SourceFile;
+
+    /// 演習問題（取り組む順）
+    fn exercises(&self) -> &'static [Exercise];
+
+    /// `layout`コマンドでメモリレイアウトを表示する型
+    fn layouts(&self) -> Vec<TypeLayout> {
+        Vec::new()
+    }
+
+    /// 表示言語に合わせたタイトル
+    fn title(&self) -> &'static str {
+        i18n::pick(self.title_ja(), self.title_en())
+    }
+
+    /// 表示言語に合わせた主なトピック
+    fn topic_summary(&self) -> &'static str {
+        i18n::pick(self.topics(), self.topics_en())
+    }
+
+    /// 名前でドキュメントのページを探す
+    fn doc_page(&self, name: &str) -> Option<&'static DocPage> {
+        self.doc_pages().iter().find(|page| page.name == name)
+    }
+
+    /// 名前がこのセクションを指しているかどうか
+    fn matches(&self, name: &str) -> bool {
+        self.id() == name || self.aliases().contains(&name)
+    }
+}
+
+// 4. 登録されたセクションの一覧（表示順）
+static SECTIONS: &[&dyn Section] = &[
+    &basics::BasicsSection,
+    &ownership::OwnershipSection,
+    &structs_enums::StructsEnumsSection,
+    &error_handling::ErrorHandlingSection,
+    &generics_traits::GenericsTraitsSection,
+    &collections::CollectionsSection,
+    &testing::TestingSection,
+];
+
+pub fn all() -> &'static [&'static dyn Section] {
+    SECTIONS
+}
+
+pub fn find(name: &str) -> Option<&'static dyn Section> {
+    SECTIONS.iter().copied().find(|section| section.matches(name))
+}
+
+// 5. デモの選択
+// 各パターンは番号（1始まり）、関数名、またはワイルドカード（`*`と`?`）で指定する
+pub fn select_demos<'a>(
+    demos: &'a [Demo],
+    patterns: &[String],
+) -> Result<Vec<&'a Demo>, String> {
+    let mut selected: Vec<&Demo> = Vec::new();
+
+    for pattern in patterns {
+        let matched: Vec<&Demo> = match pattern.parse::<usize>() {
+            Ok(index) => demos.get(index.wrapping_sub(1)).into_iter().collect(),
+            Err(_) => demos
+                .iter()
+                .filter(|demo| glob_match(pattern, demo.name))
+                .collect(),
+        };
+
+        if matched.is_empty() {
+            return Err(pattern.clone());
+        }
+
+        for demo in matched {
+            // 同じデモを二度実行しない
+            if !selected.iter().any(|d| std::ptr::eq(*d, demo)) {
+                selected.push(demo);
+            }
+        }
+    }
+
+    Ok(selected)
+}
+
+// `*`（任意の文字列）と`?`（任意の1文字）だけを扱う簡易的なワイルドカード
+pub fn glob_match(pattern: &str, text: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let text: Vec<char> = text.chars().collect();
+
+    let (mut p, mut t) = (0, 0);
+    let mut backtrack: Option<(usize, usize)> = None;
+
+    while t < text.len() {
+        if p < pattern.len() && (pattern[p] == '?' || pattern[p] == text[t]) {
+            p += 1;
+            t += 1;
+        } else if p < pattern.len() && pattern[p] == '*' {
+            backtrack = Some((p, t));
+            p += 1;
+        } else if let Some((star, matched)) = backtrack {
+            // 直前の`*`にもう1文字吸収させてやり直す
+            p = star + 1;
+            t = matched + 1;
+            backtrack = Some((star, matched + 1));
+        } else {
+            return false;
+        }
+    }
+
+    pattern[p..].iter().all(|&c| c == '*')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    #[test]
+    fn ids_and_aliases_are_unique() {
+        let mut names = HashSet::new();
+        for section in all() {
+            assert!(names.insert(section.id()), "重複したID: {}", section.id());
+            for alias in section.aliases() {
+                assert!(names.insert(*alias), "重複した別名: {}", alias);
+            }
+        }
+        for command in crate::cli::COMMANDS {
+            assert!(!names.contains(command), "`{}`はセクション名に使えない", command);
+        }
+    }
+
+    #[test]
+    fn find_by_id_and_alias() {
+        assert_eq!(find("basics").map(|s| s.id()), Some("basics"));
+        assert_eq!(find("structs_enums").map(|s| s.id()), Some("structs"));
+        assert!(find("unknown").is_none());
+    }
+
+    #[test]
+    fn demo_names_are_unique_within_section() {
+        for section in all() {
+            let mut names = HashSet::new();
+            for demo in section.demos() {
+                assert!(names.insert(demo.name), "{}: 重複したデモ名 {}", section.id(), demo.name);
+            }
+        }
+    }
+
+    #[test]
+    fn glob_patterns() {
+        assert!(glob_match("hash*", "hash_maps"));
+        assert!(glob_match("*_example", "game_example"));
+        assert!(glob_match("s?ices", "slices"));
+        assert!(glob_match("*", ""));
+        assert!(!glob_match("hash*", "vectors"));
+        assert!(!glob_match("slice", "slices"));
+    }
+
+    #[test]
+    fn select_by_index_name_and_glob() {
+        let demos = find("collections").unwrap().demos();
+        let names = |patterns: &[&str]| {
+            let patterns: Vec<String> = patterns.iter().map(|p| p.to_string()).collect();
+            select_demos(demos, &patterns).map(|v| v.iter().map(|d| d.name).collect::<Vec<_>>())
+        };
+
+        assert_eq!(names(&["1"]), Ok(vec!["vectors"]));
+        assert_eq!(names(&["hash*"]), Ok(vec!["hash_maps"]));
+        assert_eq!(names(&["strings", "2"]), Ok(vec!["strings"]));
+        assert_eq!(names(&["0"]), Err("0".to_string()));
+        assert_eq!(names(&["nothing*"]), Err("nothing*".to_string()));
+    }
+
+    #[test]
+    fn every_section_has_docs() {
+        for section in all() {
+            assert!(!section.doc_pages().is_empty(), "{}にドキュメントがない", section.id());
+            let mut names = HashSet::new();
+            for page in section.doc_pages() {
+                assert!(names.insert(page.name), "{}: 重複したページ名 {}", section.id(), page.name);
+            }
+        }
+    }
+
+    // 埋め込まれた本文と`src`以下のファイルが一致していること
+    #[test]
+    fn embedded_docs_match_source_tree() {
+        let src = Path::new(env!("CARGO_MANIFEST_DIR")).join("src");
+        for section in all() {
+            for page in section.doc_pages() {
+                for (path, content) in [(page.path, page.content), (page.path_en, page.content_en)] {
+                    let from_disk = fs::read_to_string(src.join(path)).expect(path);
+                    assert_eq!(from_disk, content, "{}", path);
+                }
+            }
+        }
+    }
+
+    // 英語版のドキュメントが日本語版と同じ構成（見出しの数と階層）になっていること
+    // （日本語の見出しを指定されたとき、英語版の同じ位置の見出しを表示するため）
+    #[test]
+    fn english_docs_have_same_structure() {
+        let levels = |content: &str| -> Vec<usize> {
+            crate::markdown::parse(content)
+                .iter()
+                .filter_map(|block| match block {
+                    crate::markdown::Block::Heading { level, .. } => Some(*level),
+                    _ => None,
+                })
+                .collect()
+        };
+        for section in all() {
+            for page in section.doc_pages() {
+                assert_eq!(levels(page.content), levels(page.content_en), "{}と{}の見出し", page.path, page.path_en);
+            }
+        }
+    }
+}
@@ -0,0 +1,192 @@
+// デモの出力のスナップショットテスト
+// 各セクションの出力を`tests/snapshots/<セクション>.txt`と比較し、違いがあれば差分を表示して失敗します
+//
+// 出力を意図して変更したときは、次のコマンドでスナップショットを更新します:
+//
+//   UPDATE_SNAPSHOTS=1 cargo test snapshot
+
+use crate::section::{self, Section};
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+
+// この環境変数が設定されていれば、比較せずにスナップショットを書き換える
+const UPDATE_VAR: &str = "UPDATE_SNAPSHOTS";
+
+// 差分の前後に表示する行数
+const CONTEXT: usize = 2;
+
+fn snapshot_path(section: &dyn Section) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/snapshots")
+        .join(format!("{}.txt", section.id()))
+}
+
+// 1. 出力の取得と正規化
+fn capture(section: &dyn Section) -> String {
+    let mut out = Vec::new();
+    section.run(&mut out).expect("Vec<u8>への書き込みは失敗しない");
+    normalize(&String::from_utf8(out).expect("デモの出力はUTF-8"))
+}
+
+// HashMapやHashSetの`{:?}`は要素の順序が実行ごとに変わるため、`{a, b}`の中身を並べ替える
+// （構造体の`Name { field: .. }`は`{`の直後が空白なので対象外）
+fn normalize(text: &str) -> String {
+    let mut result = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find('{') {
+        result.push_str(&rest[..start]);
+        let after = &rest[start + 1..];
+        match after.find(['{', '}']) {
+            Some(end) if after[end..].starts_with('}') && !after.starts_with([' ', '\n', '}']) => {
+                let mut items: Vec<&str> = after[..end].split(", ").collect();
+                items.sort_unstable();
+                result.push('{');
+                result.push_str(&items.join(", "));
+                result.push('}');
+                rest = &after[end + 1..];
+            }
+            _ => {
+                result.push('{');
+                rest = after;
+            }
+        }
+    }
+    result.push_str(rest);
+    result
+}
+
+// 2. 差分の表示
+#[derive(Debug, PartialEq)]
+enum Change<'a> {
+    Same(&'a str),
+    Removed(&'a str),
+    Added(&'a str),
+}
+
+// 最長共通部分列で行単位の差分を求める
+fn diff<'a>(expected: &[&'a str], actual: &[&'a str]) -> Vec<Change<'a>> {
+    let (n, m) = (expected.len(), actual.len());
+    // lcs[i][j]: expected[i..]とactual[j..]の最長共通部分列の長さ
+    let mut lcs = vec![vec![0usize; m + 1]; n + 1];
+    for i in (0..n).rev() {
+        for j in (0..m).rev() {
+            lcs[i][j] = if expected[i] == actual[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    let mut changes = Vec::new();
+    let (mut i, mut j) = (0, 0);
+    while i < n || j < m {
+        if i < n && j < m && expected[i] == actual[j] {
+            changes.push(Change::Same(expected[i]));
+            i += 1;
+            j += 1;
+        } else if i < n && (j == m || lcs[i + 1][j] >= lcs[i][j + 1]) {
+            changes.push(Change::Removed(expected[i]));
+            i += 1;
+        } else {
+            changes.push(Change::Added(actual[j]));
+            j += 1;
+        }
+    }
+    changes
+}
+
+// 変更のあった行とその前後だけを`-`（期待値）と`+`（実際の出力）で表示する
+fn render_diff(changes: &[Change]) -> String {
+    let changed: Vec<usize> = changes
+        .iter()
+        .enumerate()
+        .filter(|(_, change)| !matches!(change, Change::Same(_)))
+        .map(|(i, _)| i)
+        .collect();
+
+    let mut text = String::new();
+    let mut last_shown: Option<usize> = None;
+    let mut line = 0;  // 期待値での行番号
+    for (i, change) in changes.iter().enumerate() {
+        if !matches!(change, Change::Added(_)) {
+            line += 1;
+        }
+        let near = changed.iter().any(|&c| c.abs_diff(i) <= CONTEXT);
+        if !near {
+            continue;
+        }
+        if last_shown.is_none_or(|last| last + 1 != i) {
+            text.push_str(&format!("@@ {}行目付近 @@\n", line));
+        }
+        last_shown = Some(i);
+        match change {
+            Change::Same(l) => text.push_str(&format!("  {}\n", l)),
+            Change::Removed(l) => text.push_str(&format!("- {}\n", l)),
+            Change::Added(l) => text.push_str(&format!("+ {}\n", l)),
+        }
+    }
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sorts_hash_collection_items() {
+        assert_eq!(normalize(r#"スコア: {"Yellow": 50, "Blue": 10}"#), r#"スコア: {"Blue": 10, "Yellow": 50}"#);
+        assert_eq!(normalize("和集合: {3, 1, 2}, 空: {}"), "和集合: {1, 2, 3}, 空: {}");
+        // 構造体のDebug表示はそのまま
+        let user = r#"User { username: "a", email: "b" }"#;
+        assert_eq!(normalize(user), user);
+    }
+
+    #[test]
+    fn shows_changed_lines_with_context() {
+        let expected = ["a", "b", "c", "d", "e", "f", "g"];
+        let actual = ["a", "b", "c", "X", "e", "f", "g"];
+        let changes = diff(&expected, &actual);
+        assert_eq!(
+            render_diff(&changes),
+            "@@ 2行目付近 @@\n  b\n  c\n- d\n+ X\n  e\n  f\n"
+        );
+        assert!(render_diff(&diff(&expected, &expected)).is_empty());
+    }
+
+    // 各セクションの出力がスナップショットと一致すること
+    #[test]
+    fn demo_output_matches_snapshots() {
+        let update = env::var_os(UPDATE_VAR).is_some_and(|value| !value.is_empty());
+        let mut failures = Vec::new();
+
+        for section in section::all() {
+            let actual = capture(*section);
+            let path = snapshot_path(*section);
+            if update {
+                fs::create_dir_all(path.parent().unwrap()).unwrap();
+                fs::write(&path, &actual).unwrap();
+                continue;
+            }
+
+            let Ok(expected) = fs::read_to_string(&path) else {
+                failures.push(format!("{}: スナップショットがありません", path.display()));
+                continue;
+            };
+            if expected != actual {
+                let expected: Vec<&str> = expected.lines().collect();
+                let actual: Vec<&str> = actual.lines().collect();
+                failures.push(format!("{}:\n{}", path.display(), render_diff(&diff(&expected, &actual))));
+            }
+        }
+
+        assert!(
+            failures.is_empty(),
+            "デモの出力がスナップショットと異なります（-: スナップショット、+: 実際の出力）\n\n{}\n\
+             意図した変更なら`{}=1 cargo test snapshot`で更新してください",
+            failures.join("\n"),
+            UPDATE_VAR
+        );
+    }
+}
@@ -0,0 +1,238 @@
+// デモのソースコードの表示
+// `source <セクション> [関数]`の形式で、バイナリに埋め込まれたソースコードから
+// デモの関数を切り出し、行番号と色分けを付けて表示します
+
+use crate::markdown::highlight::{self, Token};
+use crate::markdown::{terminal, terminal::Style};
+use crate::section::{self, Section};
+
+// 1. ソースコードから関数を切り出す
+#[derive(Debug, PartialEq)]
+pub struct Function<'a> {
+    pub first_line: usize,  // 1行目の行番号（1始まり、直前のコメントを含む）
+    pub code: &'a str,      // 関数の直前のコメントから、閉じ括弧の行まで
+}
+
+// `fn 名前(`または`fn 名前<`で始まる最初の関数を探す
+pub fn find_function<'a>(source: &'a str, name: &str) -> Option<Function<'a>> {
+    let lines: Vec<(usize, &str)> = line_offsets(source);
+    let index = lines.iter().position(|(_, line)| declares(line, name))?;
+
+    // 直前のコメントや属性は関数の説明として含める
+    let mut start = index;
+    while start > 0 {
+        let previous = lines[start - 1].1.trim_start();
+        if !(previous.starts_with("//") || previous.starts_with("#[")) {
+            break;
+        }
+        start -= 1;
+    }
+
+    let body = lines[index].0;
+    let end = body + body_len(&source[body..]);
+    // 閉じ括弧の後ろのコメント（`}  // ここで…`）も含めて行末まで
+    let end = source[end..].find('\n').map_or(source.len(), |newline| end + newline);
+
+    Some(Function { first_line: start + 1, code: &source[lines[start].0..end] })
+}
+
+// ファイルの一番外側の項目（関数・構造体・impl・useなど）を、直前のコメントや属性とともにすべて切り出す
+// 項目に付いていないコメント（空行の前のコメント）は含めない
+pub fn items(source: &str) -> Vec<Function<'_>> {
+    let lines = line_offsets(source);
+    let mut items = Vec::new();
+    let mut i = 0;
+    while i < lines.len() {
+        let start = i;
+        while i < lines.len() && is_comment_or_attribute(lines[i].1) {
+            i += 1;
+        }
+        if i == lines.len() || lines[i].1.trim().is_empty() {
+            i += 1;
+            continue;
+        }
+
+        let body = lines[i].0;
+        let end = body + body_len(&source[body..]);
+        let end = source[end..].find('\n').map_or(source.len(), |newline| end + newline);
+        items.push(Function { first_line: start + 1, code: &source[lines[start].0..end] });
+        i = lines.iter().position(|(offset, _)| *offset > end).unwrap_or(lines.len());
+    }
+    items
+}
+
+fn is_comment_or_attribute(line: &str) -> bool {
+    let line = line.trim_start();
+    line.starts_with("//") || line.starts_with("#[")
+}
+
+// 各行の開始位置と内容
+fn line_offsets(source: &str) -> Vec<(usize, &str)> {
+    let mut offset = 0;
+    source
+        .split('\n')
+        .map(|line| {
+            let start = offset;
+            offset += line.len() + 1;
+            (start, line)
+        })
+        .collect()
+}
+
+fn declares(line: &str, name: &str) -> bool {
+    let Some(position) = line.find("fn ") else {
+        return false;
+    };
+    let before = line[..position].trim();
+    let modifiers_only = before
+        .split_whitespace()
+        .all(|word| matches!(word, "pub" | "pub(crate)" | "const" | "unsafe" | "async"));
+    let rest = &line[position + 3..];
+    modifiers_only && rest.strip_prefix(name).is_some_and(|rest| rest.starts_with(['(', '<']))
+}
+
+// 関数の本体の閉じ括弧までの長さ
+// 字句に分けてから数えるので、文字列やコメントの中の括弧は無視される
+// （`[i32; 3]`や`&[Demo { .. }]`の中で終わらないよう、丸括弧と角括弧も数える）
+fn body_len(code: &str) -> usize {
+    let mut depth = 0;
+    let mut offset = 0;
+    for (token, text) in highlight::rust(code) {
+        if token == Token::Plain {
+            for (i, c) in text.char_indices() {
+                match c {
+                    '{' | '(' | '[' => depth += 1,
+                    '}' if depth == 1 => return offset + i + 1,
+                    '}' | ')' | ']' => depth -= 1,
+                    // 本体のない宣言（トレイトのメソッド、`use`、タプル構造体など）
+                    ';' if depth == 0 => return offset + i + 1,
+                    _ => {}
+                }
+            }
+        }
+        offset += text.len();
+    }
+    code.len()
+}
+
+// 2. コマンドの実行
+pub fn command(args: &[String]) {
+    let Some(name) = args.first() else {
+        print_help();
+        return;
+    };
+    let Some(section) = section::find(name) else {
+        println!("{}", tr!("common.unknown_section", name));
+        print_help();
+        return;
+    };
+
+    let source = section.source();
+    let style = Style::detect();
+    let Some(function) = args.get(1) else {
+        // 関数を指定しなければファイル全体を表示する
+        println!("{}", style.paint(terminal::DIM, &format!("src/{}", source.path)));
+        for line in terminal::render_source(source.content, 1, style) {
+            println!("{}", line);
+        }
+        return;
+    };
+
+    // デモは番号やワイルドカードでも指定できる。それ以外の名前は補助関数として探す
+    let names: Vec<&str> = match section::select_demos(section.demos(), &args[1..2]) {
+        Ok(demos) => demos.iter().map(|demo| demo.name).collect(),
+        Err(_) => vec![function.as_str()],
+    };
+    for (i, name) in names.iter().enumerate() {
+        let Some(found) = find_function(source.content, name) else {
+            println!("{}", tr!("source.unknown_function", section.id(), name));
+            print_functions(section);
+            return;
+        };
+        if i > 0 {
+            println!();
+        }
+        print_function(source.path, &found, style);
+    }
+}
+
+pub fn print_function(path: &str, function: &Function, style: Style) {
+    let last = function.first_line + function.code.lines().count().saturating_sub(1);
+    let location = format!("src/{}:{}-{}", path, function.first_line, last);
+    println!("{}", style.paint(terminal::DIM, &location));
+    for line in terminal::render_source(function.code, function.first_line, style) {
+        println!("{}", line);
+    }
+}
+
+// 3. 一覧
+fn print_functions(section: &dyn Section) {
+    println!("{}", tr!("source.demos", section.id()));
+    for (i, demo) in section.demos().iter().enumerate() {
+        println!("  {:>2}. {:<30}{}", i + 1, demo.name, demo.heading());
+    }
+}
+
+fn print_help() {
+    println!("{}", tr!("source.usage"));
+    println!("\n{}", tr!("help.sections"));
+    for section in section::all() {
+        println!("  {:<13}- {}", section.id(), section.title());
+    }
+    println!("\n{}", tr!("help.examples"));
+    println!("  {}", tr!("source.ex.demo"));
+    println!("  {}", tr!("source.ex.helper"));
+    println!("  {}", tr!("source.ex.split"));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SOURCE: &str = "use std::io;\n\n// 1. 例\n/// 説明\n#[inline]\npub fn demo(x: i32) -> i32 {\n    let s = \"}\"; // {\n    if x > 0 { 1 } else { 0 }\n}  // 終わり\n\nfn other() {}\n";
+
+    #[test]
+    fn extracts_function_with_leading_comments() {
+        let function = find_function(SOURCE, "demo").unwrap();
+        assert_eq!(function.first_line, 3);
+        assert!(function.code.starts_with("// 1. 例\n/// 説明\n#[inline]\npub fn demo("));
+        assert!(function.code.ends_with("}  // 終わり"));
+
+        let other = find_function(SOURCE, "other").unwrap();
+        assert_eq!(other, Function { first_line: 11, code: "fn other() {}" });
+        assert_eq!(find_function(SOURCE, "dem"), None);
+    }
+
+    #[test]
+    fn splits_top_level_items() {
+        let source = "// ファイルの説明\n\nuse std::io;\n\n#[derive(Debug)]\nstruct Point(i32, i32);\n\n// 1. 例\nimpl Point {\n    fn x(&self) -> i32 { self.0 }\n}\nstatic NAMES: &[Point] = &[Point(1, 2)];\nfn f(a: [i32; 2]) {}\n";
+        let codes: Vec<&str> = items(source).iter().map(|item| item.code).collect();
+        assert_eq!(
+            codes,
+            [
+                "use std::io;",
+                "#[derive(Debug)]\nstruct Point(i32, i32);",
+                "// 1. 例\nimpl Point {\n    fn x(&self) -> i32 { self.0 }\n}",
+                "static NAMES: &[Point] = &[Point(1, 2)];",
+                "fn f(a: [i32; 2]) {}",
+            ]
+        );
+        assert_eq!(items(source)[2].first_line, 8);
+        assert_eq!(items(SOURCE)[1], find_function(SOURCE, "demo").unwrap());
+    }
+
+    // すべてのデモの関数がソースコードから見つかること
+    #[test]
+    fn every_demo_function_is_found() {
+        for section in section::all() {
+            let source = section.source();
+            for demo in section.demos() {
+                let function = find_function(source.content, demo.name)
+                    .unwrap_or_else(|| panic!("{}: {}", source.path, demo.name));
+                let code = function.code.trim_end();
+                assert!(code.contains(&format!("fn {}", demo.name)));
+                assert!(code.lines().last().unwrap().trim_start().starts_with('}'), "{}", demo.name);
+            }
+        }
+    }
+}
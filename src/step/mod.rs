@@ -0,0 +1,118 @@
+// ステップ実行（`--step`）
+// デモを1つ実行するごとに、READMEの対応する節の抜粋を表示し、
+// Enterで次のデモへ、`q`で終了します
+
+use crate::doc;
+use crate::i18n;
+use crate::markdown::{self, terminal, terminal::Style};
+use crate::progress;
+use crate::section::{self, Demo, Section};
+use std::io::{self, BufRead, Write};
+
+// 抜粋として表示する行数（超えた分は`doc`コマンドの案内に置き換える）
+const EXCERPT_LINES: usize = 12;
+
+// 1. 実行
+// 最後まで進んだらtrue、`q`や入力の終わりで中断したらfalseを返す
+pub fn run(
+    section: &dyn Section,
+    demos: &[&Demo],
+    input: &mut dyn BufRead,
+    out: &mut dyn Write,
+    style: Style,
+) -> io::Result<bool> {
+    for (i, demo) in demos.iter().enumerate() {
+        i18n::demo::run(out, |out| section::run_demos([*demo], out))?;
+        progress::record_demos(section, [*demo]);
+
+        if let Some(lines) = excerpt(section, demo, style) {
+            writeln!(out, "\n{}", style.paint(terminal::DIM, &format!("── {} ──", tr!("step.explanation"))))?;
+            for line in lines {
+                writeln!(out, "{}", line)?;
+            }
+        }
+
+        if i + 1 == demos.len() {
+            break;
+        }
+        let position = format!("[{}/{}]", i + 1, demos.len());
+        write!(out, "\n{} {} ", style.paint(terminal::DIM, &position), tr!("step.prompt"))?;
+        out.flush()?;
+
+        let mut line = String::new();
+        if input.read_line(&mut line)? == 0 || line.trim().eq_ignore_ascii_case("q") {
+            writeln!(out, "\n{}", tr!("step.quit"))?;
+            return Ok(false);
+        }
+    }
+    Ok(true)
+}
+
+// 2. 説明の抜粋
+// デモの見出しに対応するREADMEの節を、先頭から`EXCERPT_LINES`行まで
+fn excerpt(section: &dyn Section, demo: &Demo, style: Style) -> Option<Vec<String>> {
+    let page = &section.doc_pages()[0];
+    let blocks = markdown::parse(page.localized_content());
+    let index = doc::find_heading(page, &blocks, demo.title)?;
+    let rendered = terminal::render_blocks(markdown::heading_section(&blocks, index), style);
+
+    let mut lines: Vec<String> = rendered.lines().map(str::to_string).collect();
+    if lines.len() > EXCERPT_LINES {
+        lines.truncate(EXCERPT_LINES);
+        let anchor = match &blocks[index] {
+            markdown::Block::Heading { text, .. } => markdown::anchor(text),
+            _ => String::new(),
+        };
+        let command = format!("cargo run -- doc {}#{}", section.id(), anchor);
+        lines.push(style.paint(terminal::DIM, &tr!("step.more", command)));
+    }
+    Some(lines)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PLAIN: Style = Style { color: false };
+
+    fn step(input: &str) -> (bool, String) {
+        // デモを実行すると進捗が記録されるので、利用者の記録ではなく一時ディレクトリに保存する
+        crate::progress::use_test_data_dir();
+        let ownership = section::find("ownership").unwrap();
+        let demos: Vec<&Demo> = ownership.demos().iter().collect();
+        let mut out = Vec::new();
+        let finished = run(ownership, &demos, &mut input.as_bytes(), &mut out, PLAIN).unwrap();
+        (finished, String::from_utf8(out).unwrap())
+    }
+
+    #[test]
+    fn pauses_after_each_demo_with_excerpt() {
+        let (finished, output) = step("\nq\n");
+        assert!(!finished);
+        assert!(output.contains("=== 所有権の基本 ==="));
+        assert!(output.contains("=== 参照と借用 ==="));
+        assert!(!output.contains("=== スライス ==="));
+        assert!(output.contains("1. 所有権の基本ルール"), "{}", output);
+        assert!(output.contains("[1/5]"));
+        assert!(output.ends_with(&format!("{}\n", tr!("step.quit"))));
+    }
+
+    #[test]
+    fn runs_to_the_end_and_stops_on_eof() {
+        let (finished, output) = step("\n\n\n\n");
+        assert!(finished);
+        assert!(output.contains("=== 構造体と所有権 ==="));
+        assert!(!output.contains("[5/5]"));
+
+        let (finished, _) = step("");
+        assert!(!finished);
+    }
+
+    #[test]
+    fn long_sections_are_cut_with_doc_hint() {
+        let ownership = section::find("ownership").unwrap();
+        let lines = excerpt(ownership, &ownership.demos()[1], PLAIN).unwrap();
+        assert_eq!(lines.len(), EXCERPT_LINES + 1);
+        assert!(lines[EXCERPT_LINES].contains("doc ownership#5-参照と借用references-and-borrowing"), "{:?}", lines);
+    }
+}
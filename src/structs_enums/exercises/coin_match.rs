@@ -0,0 +1,34 @@
+// 演習: match式
+//
+// `value_in_cents`は硬貨の価値をセント単位で返す関数です。
+// `match`はすべてのパターンを網羅する必要があります。足りない腕を追加してください。
+// （Penny=1、Nickel=5、Dime=10、Quarter=25）
+//
+// 確認: cargo run -- exercise check coin_match
+
+pub enum Coin {
+    Penny,
+    Nickel,
+    Dime,
+    Quarter,
+}
+
+pub fn value_in_cents(coin: Coin) -> u8 {
+    match coin {
+        Coin::Penny => 1,
+        Coin::Nickel => 5,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn values() {
+        assert_eq!(value_in_cents(Coin::Penny), 1);
+        assert_eq!(value_in_cents(Coin::Nickel), 5);
+        assert_eq!(value_in_cents(Coin::Dime), 10);
+        assert_eq!(value_in_cents(Coin::Quarter), 25);
+    }
+}
@@ -0,0 +1,42 @@
+// 演習: メソッドの定義
+//
+// `Rectangle`に面積を返す`area`メソッドと、
+// 他の長方形を完全に含むかどうかを返す`can_hold`メソッドを実装してください。
+//
+// 確認: cargo run -- exercise check rectangle
+
+pub struct Rectangle {
+    pub width: u32,
+    pub height: u32,
+}
+
+impl Rectangle {
+    pub fn area(&self) -> u32 {
+        todo!()
+    }
+
+    pub fn can_hold(&self, other: &Rectangle) -> bool {
+        todo!()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn area() {
+        let rect = Rectangle { width: 30, height: 50 };
+        assert_eq!(rect.area(), 1500);
+    }
+
+    #[test]
+    fn can_hold() {
+        let large = Rectangle { width: 30, height: 50 };
+        let small = Rectangle { width: 10, height: 40 };
+        let wide = Rectangle { width: 60, height: 45 };
+        assert!(large.can_hold(&small));
+        assert!(!large.can_hold(&wide));
+        assert!(!small.can_hold(&large));
+    }
+}
@@ -1,7 +1,9 @@
 // 構造体と列挙型の学習
 // Rustのカスタムデータ型について学びます
 
-use std::iter::Zip;
+use crate::layout::{self, Shape, TypeLayout};
+use crate::section::{self, Demo, DocPage, Exercise, Section, SourceFile};
+use std::io::{self, Write};
 
 // 1. 構造体の定義と使用
 #[derive(Debug)]  // Debug出力を可能にする
@@ -48,7 +50,7 @@ impl Point {
 // ユニット様構造体（フィールドなし）
 struct AlwaysEqual;
 
-pub fn structs_basics() {
+pub fn structs_basics(out: &mut dyn Write) -> io::Result<()> {
     // 構造体のインスタンス化
     let user1 = User {
         email: String::from("someone@example.com"),
@@ -57,7 +59,7 @@ pub fn structs_basics() {
         sign_in_count: 1,
     };
     
-    println!("ユーザー名: {}", user1.username);
+    writeln!(out, "ユーザー名: {}", user1.username)?;
     
     // 可変な構造体
     let mut user2 = User {
@@ -76,9 +78,9 @@ pub fn structs_basics() {
     };
 
     // Debug表示
-    println!("{:?}", user2);  // 1行で表示
-    println!("{:#?}", user2); // 見やすく整形して表示
-    println!("{:?}", user3);  // user3もDebug表示可能
+    writeln!(out, "{:?}", user2)?;  // 1行で表示
+    writeln!(out, "{:#?}", user2)?; // 見やすく整形して表示
+    writeln!(out, "{:?}", user3)?;  // user3もDebug表示可能
     
     // タプル構造体
     let black = Color(0, 0, 0);
@@ -87,13 +89,13 @@ pub fn structs_basics() {
     let origin = Point(0, 0, 0);
     let center = Point(50, 50, 50);
     
-    println!("黒色: ({}, {}, {})", black.0, black.1, black.2);
-    println!("白色: ({}, {}, {})", white.0, white.1, white.2);
-    println!("赤色: ({}, {}, {})", red.0, red.1, red.2);
+    writeln!(out, "黒色: ({}, {}, {})", black.0, black.1, black.2)?;
+    writeln!(out, "白色: ({}, {}, {})", white.0, white.1, white.2)?;
+    writeln!(out, "赤色: ({}, {}, {})", red.0, red.1, red.2)?;
     
     // タプル構造体の操作
     let Color(r, g, b) = red;  // 分解
-    println!("赤色の成分 - R: {}, G: {}, B: {}", r, g, b);
+    writeln!(out, "赤色の成分 - R: {}, G: {}, B: {}", r, g, b)?;
     
     // 関数に渡す
     fn mix_colors(c1: Color, c2: Color) -> Color {
@@ -105,7 +107,7 @@ pub fn structs_basics() {
     }
     
     let gray = mix_colors(black, white);
-    println!("混合色（グレー）: ({}, {}, {})", gray.0, gray.1, gray.2);
+    writeln!(out, "混合色（グレー）: ({}, {}, {})", gray.0, gray.1, gray.2)?;
     
     // 3D空間での距離計算
     fn distance(p1: &Point, p2: &Point) -> f64 {
@@ -115,20 +117,21 @@ pub fn structs_basics() {
         (dx * dx + dy * dy + dz * dz).sqrt()
     }
     
-    println!("原点から中心への距離: {:.2}", distance(&origin, &center));
+    writeln!(out, "原点から中心への距離: {:.2}", distance(&origin, &center))?;
     
-    println!("\n--- タプル構造体のメソッド ---");
-    println!("黒のHEX: {}", black.to_hex());
-    println!("白のHEX: {}", white.to_hex());
-    println!("赤のHEX: {}", red.to_hex());
-    println!("グレーはグレースケール？ {}", gray.is_grayscale());
-    println!("白の明度: {:.2}", white.brightness());
-    println!("黒の明度: {:.2}", black.brightness());
+    writeln!(out, "\n--- タプル構造体のメソッド ---")?;
+    writeln!(out, "黒のHEX: {}", black.to_hex())?;
+    writeln!(out, "白のHEX: {}", white.to_hex())?;
+    writeln!(out, "赤のHEX: {}", red.to_hex())?;
+    writeln!(out, "グレーはグレースケール？ {}", gray.is_grayscale())?;
+    writeln!(out, "白の明度: {:.2}", white.brightness())?;
+    writeln!(out, "黒の明度: {:.2}", black.brightness())?;
     
     let mut movable_point = Point::origin();
     movable_point.translate(10, 20, 30);
-    println!("移動後の点: ({}, {}, {})", movable_point.0, movable_point.1, movable_point.2);
+    writeln!(out, "移動後の点: ({}, {}, {})", movable_point.0, movable_point.1, movable_point.2)?;
     
+    Ok(())
 }
 
 // ビルダー関数
@@ -181,14 +184,14 @@ impl Rectangle {
     }
 }
 
-pub fn methods_example() {
+pub fn methods_example(out: &mut dyn Write) -> io::Result<()> {
     let rect1 = Rectangle {
         width: 30,
         height: 50,
     };
     
-    println!("長方形の面積: {}", rect1.area());
-    println!("長方形の周囲: {}", rect1.perimeter());
+    writeln!(out, "長方形の面積: {}", rect1.area())?;
+    writeln!(out, "長方形の周囲: {}", rect1.perimeter())?;
     
     let rect2 = Rectangle {
         width: 10,
@@ -200,21 +203,22 @@ pub fn methods_example() {
         height: 45,
     };
     
-    println!("rect1はrect2を含む？ {}", rect1.can_hold(&rect2));
-    println!("rect1はrect3を含む？ {}", rect1.can_hold(&rect3));
+    writeln!(out, "rect1はrect2を含む？ {}", rect1.can_hold(&rect2))?;
+    writeln!(out, "rect1はrect3を含む？ {}", rect1.can_hold(&rect3))?;
     
     // 関連関数の呼び出し
     let square = Rectangle::square(20);
-    println!("正方形: {:?}", square);
+    writeln!(out, "正方形: {:?}", square)?;
     
     // 可変メソッド
     let mut rect4 = Rectangle {
         width: 10,
         height: 20,
     };
-    println!("変更前: {:?}", rect4);
+    writeln!(out, "変更前: {:?}", rect4)?;
     rect4.double_size();
-    println!("変更後: {:?}", rect4);
+    writeln!(out, "変更後: {:?}", rect4)?;
+    Ok(())
 }
 
 // 3. 列挙型（Enum）
@@ -241,28 +245,29 @@ enum Message {
 }
 
 impl Message {
-    fn call(&self) {
+    fn call(&self, out: &mut dyn Write) -> io::Result<()> {
         match self {
-            Message::Quit => println!("終了メッセージ"),
-            Message::Move { x, y } => println!("({}, {})へ移動", x, y),
-            Message::Write(text) => println!("メッセージ: {}", text),
-            Message::ChangeColor(r, g, b) => println!("色変更: RGB({}, {}, {})", r, g, b),
+            Message::Quit => writeln!(out, "終了メッセージ")?,
+            Message::Move { x, y } => writeln!(out, "({}, {})へ移動", x, y)?,
+            Message::Write(text) => writeln!(out, "メッセージ: {}", text)?,
+            Message::ChangeColor(r, g, b) => writeln!(out, "色変更: RGB({}, {}, {})", r, g, b)?,
         }
+        Ok(())
     }
 }
 
-pub fn enums_basics() {
+pub fn enums_basics(out: &mut dyn Write) -> io::Result<()> {
     let four = IpAddrKind::V4;
     let six = IpAddrKind::V6;
     
-    println!("IPアドレスの種類: {:?}, {:?}", four, six);
+    writeln!(out, "IPアドレスの種類: {:?}, {:?}", four, six)?;
     
     // データを持つ列挙型
     let home = IpAddr::V4(127, 0, 0, 1);
     let loopback = IpAddr::V6(String::from("::1"));
     
-    println!("ホーム: {:?}", home);
-    println!("ループバック: {:?}", loopback);
+    writeln!(out, "ホーム: {:?}", home)?;
+    writeln!(out, "ループバック: {:?}", loopback)?;
     
     // メッセージの使用
     let m1 = Message::Write(String::from("hello"));
@@ -270,14 +275,15 @@ pub fn enums_basics() {
     let m3 = Message::ChangeColor(255, 0, 0);
     let m4 = Message::Quit;
     
-    m1.call();
-    m2.call();
-    m3.call();
-    m4.call();
+    m1.call(out)?;
+    m2.call(out)?;
+    m3.call(out)?;
+    m4.call(out)?;
+    Ok(())
 }
 
 // 4. Option<T>型
-pub fn option_example() {
+pub fn option_example(out: &mut dyn Write) -> io::Result<()> {
     // Option<T>は標準ライブラリで定義されている
     // enum Option<T> {
     //     None,
@@ -297,26 +303,27 @@ pub fn option_example() {
         Some(val) => x + val,  // Optionから値を取り出す
         None => x,             // Noneの場合はxをそのまま使う
     };
-    println!("合計: {}", sum);
+    writeln!(out, "合計: {}", sum)?;
 
-    let noneSum = match z {
+    let none_sum = match z {
         Some(val) => x + val,  // Optionから値を取り出す
         None => x,             // Noneの場合はxをそのまま使う
     };
-    println!("Noneの場合の合計: {}", noneSum);
+    writeln!(out, "Noneの場合の合計: {}", none_sum)?;
     
     // let sum = x + y;  // エラー！Option<i8>とi8は足せない
     
     // Optionから値を取り出す
     match y {
-        Some(value) => println!("yの値: {}", value),
-        None => println!("yは値を持たない"),
+        Some(value) => writeln!(out, "yの値: {}", value)?,
+        None => writeln!(out, "yは値を持たない")?,
     }
     
     // if letを使った簡潔な書き方
     if let Some(value) = some_number {
-        println!("値がある: {}", value);
+        writeln!(out, "値がある: {}", value)?;
     }
+    Ok(())
 }
 
 // 5. パターンマッチング
@@ -328,27 +335,30 @@ enum Coin {
     Quarter(String),  // 州の名前を持つ
 }
 
-fn value_in_cents(coin: Coin) -> u8 {
-    match coin {
+fn value_in_cents(out: &mut dyn Write, coin: Coin) -> io::Result<u8> {
+    let cents = match coin {
         Coin::Penny => {
-            println!("Lucky penny!");
+            writeln!(out, "Lucky penny!")?;
             1
         }
         Coin::Nickel => 5,
         Coin::Dime => 10,
         Coin::Quarter(state) => {
-            println!("State quarter from {}", state);
+            writeln!(out, "State quarter from {}", state)?;
             25
         }
-    }
+    };
+    Ok(cents)
 }
 
-pub fn pattern_matching() {
+pub fn pattern_matching(out: &mut dyn Write) -> io::Result<()> {
     let coin1 = Coin::Quarter(String::from("Alaska"));
     let coin2 = Coin::Penny;
     
-    println!("コイン1の価値: {} cents", value_in_cents(coin1));
-    println!("コイン2の価値: {} cents", value_in_cents(coin2));
+    let cents1 = value_in_cents(out, coin1)?;
+    writeln!(out, "コイン1の価値: {} cents", cents1)?;
+    let cents2 = value_in_cents(out, coin2)?;
+    writeln!(out, "コイン2の価値: {} cents", cents2)?;
     
     // Option<T>とのマッチング
     fn plus_one(x: Option<i32>) -> Option<i32> {
@@ -362,18 +372,19 @@ pub fn pattern_matching() {
     let six = plus_one(five);
     let none = plus_one(None);
     
-    println!("5 + 1 = {:?}", six);
-    println!("None + 1 = {:?}", none);
+    writeln!(out, "5 + 1 = {:?}", six)?;
+    writeln!(out, "None + 1 = {:?}", none)?;
     
     // _プレースホルダー
     let some_value = 0u8;
     match some_value {
-        1 => println!("one"),
-        3 => println!("three"),
-        5 => println!("five"),
-        7 => println!("seven"),
+        1 => writeln!(out, "one")?,
+        3 => writeln!(out, "three")?,
+        5 => writeln!(out, "five")?,
+        7 => writeln!(out, "seven")?,
         _ => (),  // その他のケースは何もしない
     }
+    Ok(())
 }
 
 // 6. 実践的な例：ゲームの状態管理
@@ -398,62 +409,174 @@ impl Game {
         }
     }
     
-    fn start(&mut self) {
+    fn start(&mut self, out: &mut dyn Write) -> io::Result<()> {
         self.state = GameState::Playing { score: 0, level: 1 };
-        println!("ゲーム開始！");
+        writeln!(out, "ゲーム開始！")
     }
     
-    fn pause(&mut self) {
+    fn pause(&mut self, out: &mut dyn Write) -> io::Result<()> {
         if let GameState::Playing { .. } = self.state {
             self.state = GameState::Paused;
-            println!("ゲーム一時停止");
+            writeln!(out, "ゲーム一時停止")?;
         }
+        Ok(())
     }
     
-    fn update_score(&mut self, points: u32) {
+    fn update_score(&mut self, out: &mut dyn Write, points: u32) -> io::Result<()> {
         if let GameState::Playing { ref mut score, .. } = self.state {
             *score += points;
-            println!("スコア更新: {}", score);
+            writeln!(out, "スコア更新: {}", score)?;
         }
+        Ok(())
     }
     
-    fn game_over(&mut self) {
+    fn game_over(&mut self, out: &mut dyn Write) -> io::Result<()> {
         if let GameState::Playing { score, .. } = self.state {
             self.state = GameState::GameOver { final_score: score };
-            println!("ゲームオーバー！最終スコア: {}", score);
+            writeln!(out, "ゲームオーバー！最終スコア: {}", score)?;
         }
+        Ok(())
     }
 }
 
-pub fn game_example() {
+pub fn game_example(out: &mut dyn Write) -> io::Result<()> {
     let mut game = Game::new(String::from("プレイヤー1"));
     
-    game.start();
-    game.update_score(100);
-    game.update_score(50);
-    game.pause();
-    game.game_over();
+    game.start(out)?;
+    game.update_score(out, 100)?;
+    game.update_score(out, 50)?;
+    game.pause(out)?;
+    game.game_over(out)?;
     
-    println!("最終状態: {:?}", game.state);
+    writeln!(out, "最終状態: {:?}", game.state)?;
+    Ok(())
 }
 
+// デモの一覧（実行順）
+pub static DEMOS: &[Demo] = &[
+    Demo { name: "structs_basics", title: "構造体の基本", title_en: "Struct Basics", run: structs_basics },
+    Demo { name: "methods_example", title: "メソッドの例", title_en: "Methods", run: methods_example },
+    Demo { name: "enums_basics", title: "列挙型の基本", title_en: "Enum Basics", run: enums_basics },
+    Demo { name: "option_example", title: "Option型", title_en: "The Option Type", run: option_example },
+    Demo { name: "pattern_matching", title: "パターンマッチング", title_en: "Pattern Matching", run: pattern_matching },
+    Demo { name: "game_example", title: "ゲームの例", title_en: "Example: Game State", run: game_example },
+];
+
 // メインの実行関数
-pub fn run_all_structs_enums() {
-    println!("\n=== 構造体の基本 ===");
-    structs_basics();
-    
-    println!("\n=== メソッドの例 ===");
-    methods_example();
-    
-    println!("\n=== 列挙型の基本 ===");
-    enums_basics();
-    
-    println!("\n=== Option型 ===");
-    option_example();
-    
-    println!("\n=== パターンマッチング ===");
-    pattern_matching();
-    
-    println!("\n=== ゲームの例 ===");
-    game_example();
-}
\ No newline at end of file
+pub fn run_all_structs_enums(out: &mut dyn Write) -> io::Result<()> {
+    section::run_demos(DEMOS, out)
+}
+
+// セクションの登録
+pub struct StructsEnumsSection;
+
+impl Section for StructsEnumsSection {
+    fn id(&self) -> &'static str {
+        "structs"
+    }
+
+    fn aliases(&self) -> &'static [&'static str] {
+        &["structs_enums", "enums"]
+    }
+
+    fn title_ja(&self) -> &'static str {
+        "構造体と列挙型"
+    }
+
+    fn title_en(&self) -> &'static str {
+        "Structs and Enums"
+    }
+
+    fn topics(&self) -> &'static str {
+        "struct、enum、パターンマッチング"
+    }
+
+    fn topics_en(&self) -> &'static str {
+        "struct, enum, pattern matching"
+    }
+
+    fn run(&self, out: &mut dyn Write) -> io::Result<()> {
+        run_all_structs_enums(out)
+    }
+
+    fn demos(&self) -> &'static [Demo] {
+        DEMOS
+    }
+
+    fn source(&self) -> SourceFile {
+        SourceFile {
+            path: "structs_enums/mod.rs",
+            content: include_str!("mod.rs"),
+        }
+    }
+
+    fn doc_pages(&self) -> &'static [DocPage] {
+        &[DocPage {
+            name: "readme",
+            path: "structs_enums/README.md",
+            description: "構造体と列挙型の詳細",
+            content: include_str!("README.md"),
+            path_en: "structs_enums/README.en.md",
+            description_en: "Details of structs and enums",
+            content_en: include_str!("README.en.md"),
+        }]
+    }
+
+    fn quiz(&self) -> SourceFile {
+        SourceFile {
+            path: "structs_enums/quiz.md",
+            content: include_str!("quiz.md"),
+        }
+    }
+
+    fn cards(&self) -> SourceFile {
+        SourceFile {
+            path: "structs_enums/cards.md",
+            content: include_str!("cards.md"),
+        }
+    }
+
+    fn exercises(&self) -> &'static [Exercise] {
+        &[
+            Exercise {
+                name: "rectangle",
+                path: "structs_enums/exercises/rectangle.rs",
+                hint: "面積は`self.width * self.height`です。`can_hold`は幅と高さの両方が大きいかどうかを`&&`で確認します。",
+                content: include_str!("exercises/rectangle.rs"),
+            },
+            Exercise {
+                name: "coin_match",
+                path: "structs_enums/exercises/coin_match.rs",
+                hint: "`Coin::Dime => 10`と`Coin::Quarter => 25`の腕を追加します。",
+                content: include_str!("exercises/coin_match.rs"),
+            },
+        ]
+    }
+
+    fn layouts(&self) -> Vec<TypeLayout> {
+        vec![
+            TypeLayout::of::<User>(Shape::Struct(vec![
+                layout::field!(User, username),
+                layout::field!(User, email),
+                layout::field!(User, active),
+                layout::field!(User, sign_in_count),
+            ])),
+            TypeLayout::of::<Message>(Shape::Enum(layout::variants!(Message {
+                Quit,
+                Move { x: i32, y: i32 },
+                Write(String),
+                ChangeColor(i32, i32, i32),
+            }))),
+            TypeLayout::of::<GameState>(Shape::Enum(layout::variants!(GameState {
+                Menu,
+                Playing { score: u32, level: u8 },
+                Paused,
+                GameOver { final_score: u32 },
+            }))),
+            TypeLayout::of::<IpAddr>(Shape::Enum(layout::variants!(IpAddr {
+                V4(u8, u8, u8, u8),
+                V6(String),
+            }))),
+        ]
+    }
+}
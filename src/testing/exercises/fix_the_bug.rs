@@ -0,0 +1,28 @@
+// 演習: テストが見つけたバグ
+//
+// `is_leap_year`はうるう年かどうかを判定する関数です。
+// テストが失敗するので、失敗したアサーションのメッセージを読んでバグを修正してください。
+// （4で割り切れる年はうるう年。ただし100で割り切れる年は除く。ただし400で割り切れる年はうるう年）
+//
+// 確認: cargo run -- exercise check fix_the_bug
+
+pub fn is_leap_year(year: u32) -> bool {
+    year % 4 == 0 && year % 100 != 0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn common_years() {
+        assert!(!is_leap_year(2023));
+        assert!(!is_leap_year(1900), "1900年は100で割り切れるのでうるう年ではない");
+    }
+
+    #[test]
+    fn leap_years() {
+        assert!(is_leap_year(2024));
+        assert!(is_leap_year(2000), "2000年は400で割り切れるのでうるう年");
+    }
+}
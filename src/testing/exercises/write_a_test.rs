@@ -0,0 +1,39 @@
+// 演習: テストを書く
+//
+// `Guess::new`は1から100の範囲外の値でpanicします。
+// 範囲外の値でpanicすることを確認するテストに、必要な属性を付けてください。
+// （属性がないと、panicしたテストは失敗として扱われます）
+//
+// 確認: cargo run -- exercise check write_a_test
+
+pub struct Guess {
+    value: i32,
+}
+
+impl Guess {
+    pub fn new(value: i32) -> Guess {
+        if value < 1 || value > 100 {
+            panic!("Guessの値は1から100の間でなければなりません。入力値: {}", value);
+        }
+        Guess { value }
+    }
+
+    pub fn value(&self) -> i32 {
+        self.value
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn accepts_valid_values() {
+        assert_eq!(Guess::new(50).value(), 50);
+    }
+
+    #[test]
+    fn greater_than_100() {
+        Guess::new(200);
+    }
+}
@@ -1,6 +1,9 @@
 // テストの書き方の学習
 // Rustのテスト機能について学びます
 
+use crate::section::{self, Demo, DocPage, Exercise, Section, SourceFile};
+use std::io::{self, Write};
+
 // 1. 基本的なテスト
 pub fn add(a: i32, b: i32) -> i32 {
     a + b
@@ -158,7 +161,7 @@ mod result_tests {
     
     #[test]
     fn test_parse_valid_number() -> Result<(), String> {
-        let result = parse_number("42")?;
+        let result = parse_number("42").map_err(|e| e.to_string())?;
         assert_eq!(result, 42);
         Ok(())
     }
@@ -353,26 +356,123 @@ mod property_tests {
     }
 }
 
-// 統合テストの例（通常は tests/ ディレクトリに配置）
-pub fn integration_example() {
-    println!("これは統合テストで使用される公開関数です");
+// 統合テストの例（tests/integration_test.rs から呼び出す）
+pub fn integration_example(out: &mut dyn Write) -> io::Result<()> {
+    writeln!(out, "これは統合テストで使用される公開関数です")?;
+    Ok(())
 }
 
 // テスト実行のデモ関数
-pub fn run_testing_demo() {
-    println!("テストは以下のコマンドで実行できます:");
-    println!("  cargo test                    # 全てのテストを実行");
-    println!("  cargo test test_add           # 特定のテストを実行");
-    println!("  cargo test basic              # 名前に'basic'を含むテストを実行");
-    println!("  cargo test -- --nocapture     # printlnの出力を表示");
-    println!("  cargo test -- --test-threads=1 # シングルスレッドで実行");
-    println!("  cargo test -- --ignored       # 無視されたテストのみ実行");
-    
-    println!("\nテストの実行例:");
-    println!("加算のテスト: 2 + 3 = {}", add(2, 3));
-    println!("減算のテスト: 5 - 3 = {}", subtract(5, 3));
+pub fn test_commands(out: &mut dyn Write) -> io::Result<()> {
+    writeln!(out, "テストは以下のコマンドで実行できます:")?;
+    writeln!(out, "  cargo test                    # 全てのテストを実行")?;
+    writeln!(out, "  cargo test test_add           # 特定のテストを実行")?;
+    writeln!(out, "  cargo test basic              # 名前に'basic'を含むテストを実行")?;
+    writeln!(out, "  cargo test -- --nocapture     # printlnの出力を表示")?;
+    writeln!(out, "  cargo test -- --test-threads=1 # シングルスレッドで実行")?;
+    writeln!(out, "  cargo test -- --ignored       # 無視されたテストのみ実行")?;
+    Ok(())
+}
+
+pub fn test_examples(out: &mut dyn Write) -> io::Result<()> {
+    writeln!(out, "加算のテスト: 2 + 3 = {}", add(2, 3))?;
+    writeln!(out, "減算のテスト: 5 - 3 = {}", subtract(5, 3))?;
     
     let rect1 = Rectangle { width: 10, height: 20 };
     let rect2 = Rectangle { width: 5, height: 10 };
-    println!("長方形の包含テスト: rect1はrect2を含む？ {}", rect1.can_hold(&rect2));
-}
\ No newline at end of file
+    writeln!(out, "長方形の包含テスト: rect1はrect2を含む？ {}", rect1.can_hold(&rect2))?;
+    Ok(())
+}
+
+// デモの一覧（実行順）
+pub static DEMOS: &[Demo] = &[
+    Demo { name: "test_commands", title: "テストの実行コマンド", title_en: "Commands for Running Tests", run: test_commands },
+    Demo { name: "test_examples", title: "テストの実行例", title_en: "Test Examples", run: test_examples },
+];
+
+pub fn run_testing_demo(out: &mut dyn Write) -> io::Result<()> {
+    section::run_demos(DEMOS, out)
+}
+
+// セクションの登録
+pub struct TestingSection;
+
+impl Section for TestingSection {
+    fn id(&self) -> &'static str {
+        "testing"
+    }
+
+    fn title_ja(&self) -> &'static str {
+        "テストの書き方"
+    }
+
+    fn title_en(&self) -> &'static str {
+        "Testing"
+    }
+
+    fn topics(&self) -> &'static str {
+        "単体テスト、統合テスト"
+    }
+
+    fn topics_en(&self) -> &'static str {
+        "Unit tests, integration tests"
+    }
+
+    fn run(&self, out: &mut dyn Write) -> io::Result<()> {
+        run_testing_demo(out)
+    }
+
+    fn demos(&self) -> &'static [Demo] {
+        DEMOS
+    }
+
+    fn source(&self) -> SourceFile {
+        SourceFile {
+            path: "testing/mod.rs",
+            content: include_str!("mod.rs"),
+        }
+    }
+
+    fn doc_pages(&self) -> &'static [DocPage] {
+        &[DocPage {
+            name: "readme",
+            path: "testing/README.md",
+            description: "テストの書き方の詳細",
+            content: include_str!("README.md"),
+            path_en: "testing/README.en.md",
+            description_en: "Details of testing",
+            content_en: include_str!("README.en.md"),
+        }]
+    }
+
+    fn quiz(&self) -> SourceFile {
+        SourceFile {
+            path: "testing/quiz.md",
+            content: include_str!("quiz.md"),
+        }
+    }
+
+    fn cards(&self) -> SourceFile {
+        SourceFile {
+            path: "testing/cards.md",
+            content: include_str!("cards.md"),
+        }
+    }
+
+    fn exercises(&self) -> &'static [Exercise] {
+        &[
+            Exercise {
+                name: "fix_the_bug",
+                path: "testing/exercises/fix_the_bug.rs",
+                hint: "400で割り切れる年の条件を`||`で追加します: `(year % 4 == 0 && year % 100 != 0) || year % 400 == 0`",
+                content: include_str!("exercises/fix_the_bug.rs"),
+            },
+            Exercise {
+                name: "write_a_test",
+                path: "testing/exercises/write_a_test.rs",
+                hint: "panicすることを期待するテストには`#[should_panic]`を付けます。`expected = \"...\"`でメッセージも確認できます。",
+                content: include_str!("exercises/write_a_test.rs"),
+            },
+        ]
+    }
+}
@@ -0,0 +1,234 @@
+// デモの実行結果の記録（`--format json`）
+// デモを1つずつ実行して出力を取り込み、機械で読める形式で書き出します。
+// 他のツールに渡せるように、記録は1件ずつ書き込んでフラッシュします
+//
+// JSON Linesの場合は1行に1件:
+//
+//   {"section":"basics","demo":"variables_and_mutability","title":"変数と可変性","lines":["xの値: 5",...],"duration_ms":0.042,"status":"ok"}
+
+use crate::i18n;
+use crate::section::{Demo, Section};
+use std::any::Any;
+use std::io::{self, Write};
+use std::panic::{self, AssertUnwindSafe};
+use std::time::{Duration, Instant};
+
+// 1. 出力形式
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Text,       // これまでどおり、デモの出力をそのまま表示する
+    Json,       // 記録の配列を1つのJSONとして書き出す
+    JsonLines,  // 記録を1行に1つずつ書き出す
+}
+
+impl Format {
+    pub fn parse(name: &str) -> Option<Format> {
+        match name {
+            "text" => Some(Format::Text),
+            "json" => Some(Format::Json),
+            "jsonl" | "json-lines" | "ndjson" => Some(Format::JsonLines),
+            _ => None,
+        }
+    }
+}
+
+// 2. 1つのデモの実行結果
+#[derive(Debug, PartialEq)]
+pub enum Status {
+    Ok,
+    Error(String),     // デモが`Err`を返した
+    Panicked(String),  // デモがpanicした（panicのメッセージ）
+}
+
+#[derive(Debug)]
+pub struct Record {
+    pub section: &'static str,
+    pub demo: &'static str,
+    pub title: &'static str,
+    pub lines: Vec<String>,
+    pub duration: Duration,
+    pub status: Status,
+}
+
+impl Record {
+    // 1行のJSONに変換する（外部クレートを使わずに組み立てる）
+    pub fn to_json(&self) -> String {
+        let lines: Vec<String> = self.lines.iter().map(|line| quote(line)).collect();
+        let mut json = format!(
+            "{{\"section\":{},\"demo\":{},\"title\":{},\"lines\":[{}],\"duration_ms\":{:.3}",
+            quote(self.section),
+            quote(self.demo),
+            quote(self.title),
+            lines.join(","),
+            self.duration.as_secs_f64() * 1000.0,
+        );
+        match &self.status {
+            Status::Ok => json.push_str(",\"status\":\"ok\""),
+            Status::Error(message) => {
+                json.push_str(&format!(",\"status\":\"error\",\"message\":{}", quote(message)));
+            }
+            Status::Panicked(message) => {
+                json.push_str(&format!(",\"status\":\"panicked\",\"message\":{}", quote(message)));
+            }
+        }
+        json.push('}');
+        json
+    }
+}
+
+// JSONの文字列リテラルにする
+pub fn quote(text: &str) -> String {
+    let mut quoted = String::with_capacity(text.len() + 2);
+    quoted.push('"');
+    for c in text.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            '\r' => quoted.push_str("\\r"),
+            '\t' => quoted.push_str("\\t"),
+            c if (c as u32) < 0x20 => quoted.push_str(&format!("\\u{:04x}", c as u32)),
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+// 3. デモの実行
+// 出力を`Vec<u8>`に取り込み、panicしても次のデモに進めるようにする
+pub fn run_demo(section: &dyn Section, demo: &Demo) -> Record {
+    let mut output = Vec::new();
+    let start = Instant::now();
+    let result = panic::catch_unwind(AssertUnwindSafe(|| i18n::demo::run(&mut output, demo.run)));
+    let duration = start.elapsed();
+
+    let status = match result {
+        Ok(Ok(())) => Status::Ok,
+        Ok(Err(e)) => Status::Error(e.to_string()),
+        Err(payload) => Status::Panicked(panic_message(payload.as_ref())),
+    };
+    Record {
+        section: section.id(),
+        demo: demo.name,
+        title: demo.heading(),
+        lines: String::from_utf8_lossy(&output).lines().map(str::to_string).collect(),
+        duration,
+        status,
+    }
+}
+
+fn panic_message(payload: &(dyn Any + Send)) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        message.to_string()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        String::from("panic")
+    }
+}
+
+// 4. 書き出し
+// `runs`の各セクションの指定されたデモを順に実行し、1件ずつ`out`に書き込む。
+// 正常に終わったデモを`on_success`に渡す（進捗の記録に使う）
+pub fn write<'a>(
+    out: &mut dyn Write,
+    format: Format,
+    runs: impl IntoIterator<Item = (&'a dyn Section, Vec<&'a Demo>)>,
+    mut on_success: impl FnMut(&'a dyn Section, &'a Demo),
+) -> io::Result<()> {
+    let mut first = true;
+    if format == Format::Json {
+        write!(out, "[")?;
+    }
+    for (section, demos) in runs {
+        for demo in demos {
+            let record = run_demo(section, demo);
+            if record.status == Status::Ok {
+                on_success(section, demo);
+            }
+            match format {
+                Format::Json => {
+                    let separator = if first { "" } else { "," };
+                    write!(out, "{}\n  {}", separator, record.to_json())?;
+                }
+                _ => writeln!(out, "{}", record.to_json())?,
+            }
+            out.flush()?;
+            first = false;
+        }
+    }
+    if format == Format::Json {
+        writeln!(out, "{}]", if first { "" } else { "\n" })?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::section;
+
+    fn failing(out: &mut dyn Write) -> io::Result<()> {
+        writeln!(out, "途中まで")?;
+        Err(io::Error::other("書き込めません"))
+    }
+
+    fn panicking(out: &mut dyn Write) -> io::Result<()> {
+        writeln!(out, "panicの前")?;
+        panic!("デモが失敗しました: {}", 42);
+    }
+
+    static DEMOS: &[Demo] = &[
+        Demo { name: "failing", title: "失敗", title_en: "Failing", run: failing },
+        Demo { name: "panicking", title: "panic", title_en: "Panicking", run: panicking },
+    ];
+
+    #[test]
+    fn escapes_json_strings() {
+        assert_eq!(quote("a\"b\\c"), "\"a\\\"b\\\\c\"");
+        assert_eq!(quote("改行\n\tタブ"), "\"改行\\n\\tタブ\"");
+        assert_eq!(quote("\u{1}"), "\"\\u0001\"");
+    }
+
+    #[test]
+    fn records_output_and_status() {
+        let basics = section::find("basics").unwrap();
+        let record = run_demo(basics, &basics.demos()[0]);
+        assert_eq!(record.status, Status::Ok);
+        assert_eq!(record.lines[0], "xの値: 5");
+
+        let record = run_demo(basics, &DEMOS[0]);
+        assert_eq!(record.lines, vec!["途中まで"]);
+        assert_eq!(record.status, Status::Error(String::from("書き込めません")));
+
+        let record = run_demo(basics, &DEMOS[1]);
+        assert_eq!(record.status, Status::Panicked(String::from("デモが失敗しました: 42")));
+        assert!(record.to_json().contains("\"status\":\"panicked\",\"message\":\"デモが失敗しました: 42\""));
+    }
+
+    #[test]
+    fn writes_json_and_json_lines() {
+        let basics = section::find("basics").unwrap();
+        let runs = || vec![(basics, DEMOS.iter().collect::<Vec<_>>())];
+
+        let mut lines = Vec::new();
+        let mut succeeded = 0;
+        write(&mut lines, Format::JsonLines, runs(), |_, _| succeeded += 1).unwrap();
+        let lines = String::from_utf8(lines).unwrap();
+        assert_eq!(lines.lines().count(), 2);
+        assert!(lines.lines().all(|line| line.starts_with("{\"section\":\"basics\",") && line.ends_with('}')));
+        assert_eq!(succeeded, 0);
+
+        let mut json = Vec::new();
+        write(&mut json, Format::Json, runs(), |_, _| {}).unwrap();
+        let json = String::from_utf8(json).unwrap();
+        assert!(json.starts_with("[\n  {\"section\":\"basics\",\"demo\":\"failing\""));
+        assert!(json.contains("},\n  {\"section\":\"basics\",\"demo\":\"panicking\""));
+        assert!(json.ends_with("}\n]\n"));
+
+        let mut empty = Vec::new();
+        write(&mut empty, Format::Json, Vec::new(), |_, _| {}).unwrap();
+        assert_eq!(String::from_utf8(empty).unwrap(), "[]\n");
+    }
+}
@@ -0,0 +1,287 @@
+// 監視モード（`watch <セクション> [デモ...]`）
+// セクションのソースコードとドキュメントの更新時刻を定期的に確認し、変更されたら
+// `cargo build`でビルドし直してから、新しいバイナリでセクション（または選んだデモ）を実行します
+//
+// 保存が続けて起きても一度だけビルドするように、最後の変更から`DEBOUNCE`だけ待ちます
+
+use crate::i18n;
+use crate::markdown::{terminal, terminal::Style};
+use crate::section::{self, Section};
+use std::env;
+use std::ffi::OsString;
+use std::fs;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::thread;
+use std::time::{Duration, Instant, SystemTime};
+
+// 更新時刻を確認する間隔
+const POLL_INTERVAL: Duration = Duration::from_millis(300);
+
+// 最後の変更からビルドを始めるまでの待ち時間
+const DEBOUNCE: Duration = Duration::from_millis(500);
+
+// 1. ファイルの更新時刻の監視
+pub struct Watcher {
+    files: Vec<PathBuf>,
+    stamps: Vec<Option<SystemTime>>,  // 前回確認したときの更新時刻（ファイルがなければNone）
+}
+
+impl Watcher {
+    pub fn new(files: Vec<PathBuf>) -> Self {
+        let stamps = files.iter().map(|file| modified(file)).collect();
+        Watcher { files, stamps }
+    }
+
+    // 前回から更新時刻が変わったファイル
+    pub fn poll(&mut self) -> Vec<&Path> {
+        let mut changed = Vec::new();
+        for (file, stamp) in self.files.iter().zip(self.stamps.iter_mut()) {
+            let current = modified(file);
+            if current != *stamp {
+                *stamp = current;
+                changed.push(file.as_path());
+            }
+        }
+        changed
+    }
+}
+
+fn modified(file: &Path) -> Option<SystemTime> {
+    fs::metadata(file).and_then(|metadata| metadata.modified()).ok()
+}
+
+// 2. 変更の待ち合わせ
+// 変更が止まってから`DEBOUNCE`が過ぎたら、それまでに変更されたファイルを返す
+#[derive(Debug, Default)]
+pub struct Debounce {
+    pending: Vec<PathBuf>,
+    last_change: Option<Instant>,
+}
+
+impl Debounce {
+    pub fn record(&mut self, changed: &[&Path], now: Instant) {
+        if changed.is_empty() {
+            return;
+        }
+        for file in changed {
+            if !self.pending.iter().any(|pending| pending == file) {
+                self.pending.push(file.to_path_buf());
+            }
+        }
+        self.last_change = Some(now);
+    }
+
+    pub fn settled(&mut self, now: Instant) -> Option<Vec<PathBuf>> {
+        let last_change = self.last_change?;
+        if now.duration_since(last_change) < DEBOUNCE {
+            return None;
+        }
+        self.last_change = None;
+        Some(std::mem::take(&mut self.pending))
+    }
+}
+
+// 3. ビルドと実行
+enum Build {
+    Succeeded,
+    Failed(Vec<String>),  // コンパイルエラーの行
+}
+
+// `cargo run`から起動された場合は同じcargoを使う
+fn cargo() -> OsString {
+    env::var_os("CARGO").unwrap_or_else(|| OsString::from("cargo"))
+}
+
+fn project_dir() -> &'static Path {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+}
+
+fn build() -> io::Result<Build> {
+    let output = Command::new(cargo())
+        .args(["build", "--quiet", "--message-format", "short"])
+        .current_dir(project_dir())
+        .output()?;
+    if output.status.success() {
+        return Ok(Build::Succeeded);
+    }
+    Ok(Build::Failed(compile_errors(&String::from_utf8_lossy(&output.stderr))))
+}
+
+// `--message-format short`の出力から、エラーの行だけを取り出す（警告は表示しない）
+fn compile_errors(stderr: &str) -> Vec<String> {
+    stderr
+        .lines()
+        .filter(|line| line.starts_with("error") || line.contains(": error"))
+        .map(str::to_string)
+        .collect()
+}
+
+// ビルドしたバイナリでセクションを実行する（出力はそのまま端末に流す）
+fn run(section: &dyn Section, demos: &[String]) -> io::Result<()> {
+    Command::new(cargo())
+        .args(["run", "--quiet", "--"])
+        .args(["--lang", i18n::lang().code(), section.id()])
+        .args(demos)
+        .current_dir(project_dir())
+        .stdin(Stdio::null())
+        .status()?;
+    Ok(())
+}
+
+// 4. コマンドの実行
+pub fn command(args: &[String]) {
+    let Some(name) = args.first() else {
+        print_help();
+        return;
+    };
+    let Some(section) = section::find(name) else {
+        println!("{}", tr!("common.unknown_section", name));
+        print_help();
+        return;
+    };
+    let demos = &args[1..];
+    if let Err(pattern) = section::select_demos(section.demos(), demos) {
+        println!("{}", tr!("main.unknown_demo", section.id(), pattern));
+        return;
+    }
+
+    let files = watched_files(section);
+    let style = Style::detect();
+    println!("{}", tr!("watch.start", section.id()));
+    for file in &files {
+        println!("  {}", style.paint(terminal::DIM, &display_path(file)));
+    }
+    println!("{}", tr!("watch.stop_hint"));
+
+    if let Err(e) = rebuild_and_run(section, demos, style) {
+        println!("{}", tr!("watch.cargo_error", e));
+        return;
+    }
+
+    let mut watcher = Watcher::new(files);
+    let mut debounce = Debounce::default();
+    loop {
+        thread::sleep(POLL_INTERVAL);
+        let now = Instant::now();
+        debounce.record(&watcher.poll(), now);
+        let Some(changed) = debounce.settled(now) else {
+            continue;
+        };
+
+        // 端末なら画面を消してから、変更されたファイルを表示する
+        if style.color {
+            print!("\x1b[2J\x1b[H");
+        }
+        let names: Vec<String> = changed.iter().map(|file| display_path(file)).collect();
+        println!("{}", style.paint(terminal::CYAN, &tr!("watch.changed", names.join(", "))));
+        if let Err(e) = rebuild_and_run(section, demos, style) {
+            println!("{}", tr!("watch.cargo_error", e));
+            return;
+        }
+    }
+}
+
+fn rebuild_and_run(section: &dyn Section, demos: &[String], style: Style) -> io::Result<()> {
+    println!("{}", style.paint(terminal::DIM, tr!("watch.building")));
+    io::stdout().flush()?;
+    match build()? {
+        Build::Succeeded => run(section, demos)?,
+        Build::Failed(errors) => {
+            println!("{}", style.paint(terminal::RED, tr!("watch.build_failed")));
+            for error in errors {
+                println!("  {}", style.paint(terminal::RED, &error));
+            }
+        }
+    }
+    println!("\n{}", style.paint(terminal::DIM, tr!("watch.waiting")));
+    Ok(())
+}
+
+// セクションのソースコードと、すべてのページの日本語版・英語版
+fn watched_files(section: &dyn Section) -> Vec<PathBuf> {
+    let src = project_dir().join("src");
+    let mut files = vec![src.join(section.source().path)];
+    for page in section.doc_pages() {
+        files.push(src.join(page.path));
+        files.push(src.join(page.path_en));
+    }
+    files
+}
+
+// プロジェクトのディレクトリからの相対パスで表示する
+fn display_path(file: &Path) -> String {
+    file.strip_prefix(project_dir()).unwrap_or(file).display().to_string()
+}
+
+fn print_help() {
+    println!("{}", tr!("watch.usage"));
+    println!("\n{}", tr!("help.examples"));
+    println!("  {}", tr!("watch.ex.section"));
+    println!("  {}", tr!("watch.ex.demo"));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn detects_modified_and_removed_files() {
+        let dir = env::temp_dir().join(format!("hello_cargo-test-{}-watch", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let file = dir.join("mod.rs");
+        fs::write(&file, "fn main() {}").unwrap();
+
+        let mut watcher = Watcher::new(vec![file.clone(), dir.join("missing.md")]);
+        assert!(watcher.poll().is_empty());
+
+        let later = SystemTime::now() + Duration::from_secs(10);
+        fs::File::options().write(true).open(&file).unwrap().set_modified(later).unwrap();
+        assert_eq!(watcher.poll(), vec![file.as_path()]);
+        assert!(watcher.poll().is_empty());
+
+        fs::remove_dir_all(&dir).unwrap();
+        assert_eq!(watcher.poll(), vec![file.as_path()]);
+    }
+
+    #[test]
+    fn waits_until_changes_settle() {
+        let start = Instant::now();
+        let (a, b) = (Path::new("a.rs"), Path::new("b.md"));
+        let mut debounce = Debounce::default();
+        assert_eq!(debounce.settled(start), None);
+
+        debounce.record(&[a], start);
+        debounce.record(&[b, a], start + Duration::from_millis(300));
+        assert_eq!(debounce.settled(start + Duration::from_millis(600)), None);
+
+        let settled = debounce.settled(start + Duration::from_millis(800)).unwrap();
+        assert_eq!(settled, vec![a.to_path_buf(), b.to_path_buf()]);
+        assert_eq!(debounce.settled(start + Duration::from_secs(5)), None);
+    }
+
+    #[test]
+    fn keeps_only_error_lines() {
+        let stderr = "src/collections/mod.rs:12:5: warning: unused variable: `x`\n\
+                      src/collections/mod.rs:20:9: error[E0425]: cannot find value `y` in this scope\n\
+                      error: could not compile `hello_cargo` (lib) due to 1 previous error\n";
+        assert_eq!(
+            compile_errors(stderr),
+            vec![
+                "src/collections/mod.rs:20:9: error[E0425]: cannot find value `y` in this scope",
+                "error: could not compile `hello_cargo` (lib) due to 1 previous error",
+            ]
+        );
+    }
+
+    #[test]
+    fn watches_source_and_both_languages_of_docs() {
+        let ownership = section::find("ownership").unwrap();
+        let files = watched_files(ownership);
+        assert!(files.iter().all(|file| file.exists()), "{:?}", files);
+        let names: Vec<String> = files.iter().map(|file| display_path(file)).collect();
+        assert!(names.contains(&"src/ownership/mod.rs".to_string()));
+        assert!(names.contains(&"src/ownership/README.en.md".to_string()));
+    }
+}
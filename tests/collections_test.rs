@@ -0,0 +1,43 @@
+// collectionsセクションの成績管理システム（GradeBook）の統合テスト
+
+use hello_cargo::collections::{self, GradeBook};
+
+mod common;
+
+fn gradebook() -> GradeBook {
+    let mut gradebook = GradeBook::new();
+    gradebook.add_course("数学".to_string());
+    gradebook.add_course("英語".to_string());
+    gradebook.add_student("田中".to_string(), 1);
+    gradebook.add_student("佐藤".to_string(), 2);
+    gradebook
+}
+
+#[test]
+fn averages_grades_of_registered_courses() {
+    let mut gradebook = gradebook();
+    gradebook.add_grade(1, "数学".to_string(), 80.0);
+    gradebook.add_grade(1, "英語".to_string(), 90.0);
+    // 登録されていないコースの成績は無視される
+    gradebook.add_grade(1, "理科".to_string(), 0.0);
+
+    assert_eq!(gradebook.get_average_grade(1), Some(85.0));
+    assert_eq!(gradebook.get_average_grade(2), Some(0.0));
+    assert_eq!(gradebook.get_average_grade(3), None);
+}
+
+#[test]
+fn ranks_students_by_average() {
+    let mut gradebook = gradebook();
+    gradebook.add_grade(1, "数学".to_string(), 70.0);
+    gradebook.add_grade(2, "数学".to_string(), 95.0);
+
+    assert_eq!(gradebook.get_top_students(1), vec![("佐藤", 95.0)]);
+    assert_eq!(gradebook.get_top_students(5).len(), 2);
+}
+
+#[test]
+fn gradebook_demo_prints_top_students() {
+    let output = common::output(collections::gradebook_example);
+    assert!(output.contains("トップ2の学生:"), "{}", output);
+}
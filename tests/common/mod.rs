@@ -0,0 +1,23 @@
+// 統合テストの共通ヘルパー
+
+use std::env;
+use std::io::{self, Write};
+use std::sync::Once;
+
+// 記録の保存先（`HELLO_CARGO_DATA_DIR`）を、利用者のディレクトリではなくプロセスごとの一時ディレクトリにする
+pub fn use_test_data_dir() {
+    static ONCE: Once = Once::new();
+    ONCE.call_once(|| {
+        let dir = env::temp_dir().join(format!("hello_cargo-test-{}-data", std::process::id()));
+        // SAFETY: 環境変数を読むのは標準ライブラリの関数だけで、そちらは内部のロックで書き込みと排他される
+        unsafe { env::set_var("HELLO_CARGO_DATA_DIR", dir) };
+    });
+}
+
+// デモの関数を実行し、出力を文字列として返す
+pub fn output(run: fn(&mut dyn Write) -> io::Result<()>) -> String {
+    use_test_data_dir();
+    let mut out = Vec::new();
+    run(&mut out).expect("Vec<u8>への書き込みは失敗しない");
+    String::from_utf8(out).expect("デモの出力はUTF-8")
+}
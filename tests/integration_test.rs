@@ -0,0 +1,53 @@
+// testingセクションの公開APIの統合テスト
+// 統合テストはクレートの外側から、公開された関数や型だけを使います
+
+use hello_cargo::testing::{self, Calculator, Guess, Rectangle};
+
+mod common;
+
+#[test]
+fn adds_and_subtracts() {
+    assert_eq!(testing::add(2, 3), 5);
+    assert_eq!(testing::subtract(5, 3), 2);
+    assert_eq!(testing::internal_adder(-1, 1), 0);
+}
+
+#[test]
+fn calculator_reports_division_by_zero() {
+    assert_eq!(Calculator::multiply(3.0, 4.0), 12.0);
+    assert_eq!(Calculator::divide(9.0, 3.0), Ok(3.0));
+    assert!(Calculator::divide(1.0, 0.0).is_err());
+    assert_eq!(Calculator::power(2.0, 10), 1024.0);
+}
+
+#[test]
+fn larger_rectangle_can_hold_smaller() {
+    let large = Rectangle::square(10);
+    let small = Rectangle::square(5);
+    assert!(large.can_hold(&small));
+    assert!(!small.can_hold(&large));
+}
+
+#[test]
+fn guess_keeps_value_in_range() {
+    assert_eq!(Guess::new(50).value(), 50);
+}
+
+#[test]
+#[should_panic(expected = "1から100の間")]
+fn guess_rejects_out_of_range() {
+    Guess::new(200);
+}
+
+#[test]
+fn parses_numbers() -> Result<(), std::num::ParseIntError> {
+    assert_eq!(testing::parse_number("42")?, 42);
+    assert!(testing::parse_number("abc").is_err());
+    Ok(())
+}
+
+#[test]
+fn integration_example_writes_message() {
+    let output = common::output(testing::integration_example);
+    assert_eq!(output, "これは統合テストで使用される公開関数です\n");
+}
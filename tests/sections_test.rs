@@ -0,0 +1,31 @@
+// セクションの登録（hello_cargo::section）と各セクションの公開関数の統合テスト
+
+use hello_cargo::section;
+use hello_cargo::{basics, ownership};
+
+mod common;
+
+#[test]
+fn sections_are_found_by_id_and_alias() {
+    let ids: Vec<&str> = section::all().iter().map(|section| section.id()).collect();
+    assert_eq!(ids[0], "basics");
+    assert!(ids.contains(&"collections"));
+    assert_eq!(section::find("structs").map(|section| section.id()), Some("structs"));
+    assert!(section::find("no-such-section").is_none());
+}
+
+#[test]
+fn demos_are_selected_by_number_and_wildcard() {
+    let basics = section::find("basics").unwrap();
+    let patterns = ["1".to_string(), "control*".to_string()];
+    let demos = section::select_demos(basics.demos(), &patterns).unwrap();
+    let names: Vec<&str> = demos.iter().map(|demo| demo.name).collect();
+    assert_eq!(names, ["variables_and_mutability", "control_flow"]);
+}
+
+#[test]
+fn demos_write_to_any_writer() {
+    let output = common::output(ownership::slices);
+    assert!(output.contains("最初の単語: 'hello'"), "{}", output);
+    assert_eq!(basics::find_max(10, 20), 20);
+}